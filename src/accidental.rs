@@ -0,0 +1,136 @@
+use std::fmt;
+use std::str::FromStr;
+use super::{
+    calc,
+    Error,
+    Letter,
+    ParseError,
+};
+
+/// An accidental altering the pitch of a natural letter, including the quarter-tone accidentals
+/// used for 24-EDO and Arabic, Persian and Turkish music.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Accidental {
+    DoubleFlat,
+    ThreeQuarterFlat,
+    Flat,
+    HalfFlat,
+    Natural,
+    HalfSharp,
+    Sharp,
+    ThreeQuarterSharp,
+    DoubleSharp,
+}
+
+/// Every Accidental, ordered from the flattest to the sharpest.
+pub const ALL_ACCIDENTALS: [Accidental; 9] = [
+    Accidental::DoubleFlat,
+    Accidental::ThreeQuarterFlat,
+    Accidental::Flat,
+    Accidental::HalfFlat,
+    Accidental::Natural,
+    Accidental::HalfSharp,
+    Accidental::Sharp,
+    Accidental::ThreeQuarterSharp,
+    Accidental::DoubleSharp,
+];
+
+impl Accidental {
+
+    /// The alteration applied by the accidental in steps (e.g. `0.5` for a half sharp).
+    #[inline]
+    pub fn step_offset(&self) -> calc::Step {
+        let index = ALL_ACCIDENTALS.iter().position(|a| a == self).unwrap();
+        (index as calc::Step - 4.0) / 2.0
+    }
+
+    /// The accidental that applies the given alteration in steps, if there is one.
+    pub fn from_step_offset(offset: calc::Step) -> Option<Accidental> {
+        let quarters = offset * 2.0;
+        if quarters.fract() != 0.0 || quarters.abs() > 4.0 {
+            return None;
+        }
+        Some(ALL_ACCIDENTALS[(quarters + 4.0) as usize])
+    }
+
+    /// The ASCII symbol used for the accidental when formatting and parsing pitch names.
+    ///
+    /// Sharps and flats are written `#` and `b`, while the half sharp and half flat use `+` and
+    /// `d` (after the reversed flat sign). The natural has no symbol.
+    pub fn symbol(&self) -> &'static str {
+        match *self {
+            Accidental::DoubleFlat => "bb",
+            Accidental::ThreeQuarterFlat => "db",
+            Accidental::Flat => "b",
+            Accidental::HalfFlat => "d",
+            Accidental::Natural => "",
+            Accidental::HalfSharp => "+",
+            Accidental::Sharp => "#",
+            Accidental::ThreeQuarterSharp => "#+",
+            Accidental::DoubleSharp => "##",
+        }
+    }
+
+    /// The Unicode symbol for the accidental (e.g. `♯`, `♭`, `𝄪` and `𝄫`), used when formatting
+    /// with the alternate flag (`{:#}`).
+    pub fn unicode_symbol(&self) -> &'static str {
+        match *self {
+            Accidental::DoubleFlat => "\u{1D12B}",
+            Accidental::ThreeQuarterFlat => "\u{1D133}\u{266D}",
+            Accidental::Flat => "\u{266D}",
+            Accidental::HalfFlat => "\u{1D133}",
+            Accidental::Natural => "",
+            Accidental::HalfSharp => "\u{1D132}",
+            Accidental::Sharp => "\u{266F}",
+            Accidental::ThreeQuarterSharp => "\u{266F}\u{1D132}",
+            Accidental::DoubleSharp => "\u{1D12A}",
+        }
+    }
+
+    /// The accidental written with the given symbol, in either its ASCII or Unicode form. The
+    /// natural sign `♮` and `x` for a double sharp are also accepted.
+    pub fn from_symbol(symbol: &str) -> Option<Accidental> {
+        match symbol {
+            "\u{266E}" => Some(Accidental::Natural),
+            "x" => Some(Accidental::DoubleSharp),
+            _ => ALL_ACCIDENTALS.iter()
+                .find(|a| a.symbol() == symbol || a.unicode_symbol() == symbol)
+                .cloned(),
+        }
+    }
+
+}
+
+impl fmt::Display for Accidental {
+    /// Formats the ASCII symbol, or the Unicode symbol with the alternate flag (`{:#}`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(if f.alternate() { self.unicode_symbol() } else { self.symbol() })
+    }
+}
+
+impl FromStr for Accidental {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Accidental, Error> {
+        Accidental::from_symbol(s).ok_or(ParseError::InvalidAccidental.into())
+    }
+}
+
+/// Split a pitch name into its natural letter, its accidental and the remainder that follows.
+pub(crate) fn split_note_name(name: &str) -> Result<(Letter, Accidental, &str), Error> {
+    let mut chars = name.chars();
+    let natural = match chars.next() {
+        Some('C') | Some('c') => Letter::C,
+        Some('D') | Some('d') => Letter::D,
+        Some('E') | Some('e') => Letter::E,
+        Some('F') | Some('f') => Letter::F,
+        Some('G') | Some('g') => Letter::G,
+        Some('A') | Some('a') => Letter::A,
+        Some('B') | Some('b') => Letter::B,
+        _ => return Err(ParseError::InvalidLetter.into()),
+    };
+    let rest = chars.as_str();
+    let split = rest.find(|c: char| c.is_ascii_digit() || c == '-').unwrap_or(rest.len());
+    let (accidental, rest) = rest.split_at(split);
+    Ok((natural, accidental.parse()?, rest))
+}
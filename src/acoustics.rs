@@ -0,0 +1,202 @@
+//! Physical acoustics relating frequencies to motion, strings, pipes and temperature.
+
+use super::{
+    calc,
+    cents_from_ratio,
+    Hz,
+};
+
+/// A speed in metres per second.
+pub type Velocity = f32;
+/// A length in metres.
+pub type Metres = f32;
+/// A force in newtons.
+pub type Newtons = f32;
+/// A mass per unit length in kilograms per metre.
+pub type LinearDensity = f32;
+/// A temperature in degrees Celsius.
+pub type Celsius = f32;
+
+/// The speed of sound in dry air at 20°C, in metres per second.
+pub const SPEED_OF_SOUND: Velocity = 343.0;
+/// The speed of sound in dry air at 0°C, in metres per second.
+pub const SPEED_OF_SOUND_AT_ZERO_C: Velocity = 331.3;
+/// The temperature at which wind instruments are conventionally tuned.
+pub const REFERENCE_TEMPERATURE: Celsius = 20.0;
+/// Absolute zero in degrees Celsius.
+const ABSOLUTE_ZERO: Celsius = -273.15;
+
+/// The frequency perceived by an observer when a source and observer move relative to each other
+/// (the Doppler effect).
+///
+/// Both velocities are measured along the line between the source and the observer, and are
+/// positive when moving *towards* the other party. `speed_of_sound` is the speed of sound in the
+/// medium (see `SPEED_OF_SOUND`).
+#[inline]
+pub fn doppler_shift(source: Hz,
+                     source_velocity: Velocity,
+                     observer_velocity: Velocity,
+                     speed_of_sound: Velocity) -> Hz {
+    let ratio = (speed_of_sound + observer_velocity) / (speed_of_sound - source_velocity);
+    Hz(source.hz() * ratio as calc::Hz)
+}
+
+/// The inverse of `doppler_shift`: the frequency emitted by a source given the frequency heard by
+/// the observer.
+#[inline]
+pub fn doppler_source(observed: Hz,
+                      source_velocity: Velocity,
+                      observer_velocity: Velocity,
+                      speed_of_sound: Velocity) -> Hz {
+    let ratio = (speed_of_sound - source_velocity) / (speed_of_sound + observer_velocity);
+    Hz(observed.hz() * ratio as calc::Hz)
+}
+
+/// The fundamental frequency of an ideal vibrating string (Mersenne's laws).
+///
+/// `length` is the vibrating length, `tension` the string tension and `density` the mass per unit
+/// length of the string.
+#[inline]
+pub fn string_hz(length: Metres, tension: Newtons, density: LinearDensity) -> Hz {
+    Hz((tension / density).sqrt() / (2.0 * length))
+}
+
+/// The vibrating length required for a string with the given tension and linear density to sound
+/// the given fundamental frequency.
+#[inline]
+pub fn string_length(hz: Hz, tension: Newtons, density: LinearDensity) -> Metres {
+    (tension / density).sqrt() / (2.0 * hz.hz())
+}
+
+/// The tension required for a string with the given vibrating length and linear density to sound
+/// the given fundamental frequency.
+#[inline]
+pub fn string_tension(hz: Hz, length: Metres, density: LinearDensity) -> Newtons {
+    density * (2.0 * length * hz.hz()).powi(2)
+}
+
+/// The linear density required for a string with the given vibrating length and tension to sound
+/// the given fundamental frequency.
+#[inline]
+pub fn string_density(hz: Hz, length: Metres, tension: Newtons) -> LinearDensity {
+    tension / (2.0 * length * hz.hz()).powi(2)
+}
+
+/// The ratio between the end correction and the radius of an unflanged open pipe end.
+pub const END_CORRECTION_FACTOR: f32 = 0.6;
+
+/// The approximate end correction of a single unflanged open end of a pipe with the given radius.
+#[inline]
+pub fn end_correction(radius: Metres) -> Metres {
+    radius * END_CORRECTION_FACTOR
+}
+
+/// The fundamental resonance of a pipe open at both ends.
+///
+/// `end_correction` is added once for each open end (see `end_correction`).
+#[inline]
+pub fn open_pipe_hz(length: Metres, end_correction: Metres, speed_of_sound: Velocity) -> Hz {
+    Hz(speed_of_sound / (2.0 * (length + 2.0 * end_correction)))
+}
+
+/// The fundamental resonance of a pipe closed at one end and open at the other.
+///
+/// `end_correction` is added once for the single open end (see `end_correction`).
+#[inline]
+pub fn closed_pipe_hz(length: Metres, end_correction: Metres, speed_of_sound: Velocity) -> Hz {
+    Hz(speed_of_sound / (4.0 * (length + end_correction)))
+}
+
+/// The first `count` resonances of a pipe open at both ends. An open pipe supports every
+/// harmonic of its fundamental.
+pub fn open_pipe_harmonics(length: Metres,
+                           end_correction: Metres,
+                           speed_of_sound: Velocity,
+                           count: usize) -> Vec<Hz> {
+    let fundamental = open_pipe_hz(length, end_correction, speed_of_sound).hz();
+    (1..count + 1).map(|n| Hz(fundamental * n as calc::Hz)).collect()
+}
+
+/// The first `count` resonances of a pipe closed at one end. A closed pipe only supports the odd
+/// harmonics of its fundamental.
+pub fn closed_pipe_harmonics(length: Metres,
+                             end_correction: Metres,
+                             speed_of_sound: Velocity,
+                             count: usize) -> Vec<Hz> {
+    let fundamental = closed_pipe_hz(length, end_correction, speed_of_sound).hz();
+    (0..count).map(|n| Hz(fundamental * (2 * n + 1) as calc::Hz)).collect()
+}
+
+/// The approximate speed of sound in dry air at the given temperature.
+#[inline]
+pub fn speed_of_sound_at(temperature: Celsius) -> Velocity {
+    SPEED_OF_SOUND_AT_ZERO_C * (1.0 - temperature / ABSOLUTE_ZERO).sqrt()
+}
+
+/// Adjust a pitch whose frequency is proportional to the speed of sound (e.g. a wind instrument
+/// or pipe resonance) tuned at the `reference` temperature for being played at `temperature`.
+///
+/// Returns the drifted frequency along with its deviation from the original pitch in cents.
+pub fn temperature_drift(hz: Hz, reference: Celsius, temperature: Celsius) -> (Hz, calc::Cents) {
+    let ratio = speed_of_sound_at(temperature) as f64 / speed_of_sound_at(reference) as f64;
+    (Hz((hz.hz() as f64 * ratio) as calc::Hz), cents_from_ratio(ratio))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doppler() {
+        // A source approaching at a tenth of the speed of sound.
+        let approaching = doppler_shift(Hz(440.0), 34.3, 0.0, SPEED_OF_SOUND);
+        assert!((approaching.hz() - 488.889).abs() < 0.01);
+        let towards_source = doppler_shift(Hz(440.0), 0.0, 34.3, SPEED_OF_SOUND);
+        assert!((towards_source.hz() - 484.0).abs() < 0.01);
+        let receding = doppler_shift(Hz(440.0), -34.3, 0.0, SPEED_OF_SOUND);
+        assert!(receding.hz() < 440.0);
+        let source = doppler_source(approaching, 34.3, 0.0, SPEED_OF_SOUND);
+        assert!((source.hz() - 440.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn strings() {
+        // 100N over a string of 1g per metre, 65cm long.
+        let hz = string_hz(0.65, 100.0, 0.001);
+        assert!((hz.hz() - 243.252).abs() < 0.01);
+        assert!((string_length(hz, 100.0, 0.001) - 0.65).abs() < 0.0001);
+        assert!((string_tension(hz, 0.65, 0.001) - 100.0).abs() < 0.01);
+        assert!((string_density(hz, 0.65, 100.0) - 0.001).abs() < 0.000_001);
+        // Halving the length doubles the frequency, quadrupling the tension does the same.
+        assert!((string_hz(0.325, 100.0, 0.001).hz() - 2.0 * hz.hz()).abs() < 0.01);
+        assert!((string_hz(0.65, 400.0, 0.001).hz() - 2.0 * hz.hz()).abs() < 0.01);
+    }
+
+    #[test]
+    fn pipes() {
+        assert!((end_correction(0.01) - 0.006).abs() < 0.000_001);
+        assert!((open_pipe_hz(0.5, 0.0, SPEED_OF_SOUND).hz() - 343.0).abs() < 0.001);
+        assert!((closed_pipe_hz(0.5, 0.0, SPEED_OF_SOUND).hz() - 171.5).abs() < 0.001);
+        // End corrections lengthen the pipe, lowering its resonance.
+        let corrected = open_pipe_hz(0.494, 0.003, SPEED_OF_SOUND);
+        assert!((corrected.hz() - 343.0).abs() < 0.001);
+        assert_eq!(open_pipe_harmonics(0.5, 0.0, SPEED_OF_SOUND, 3),
+                   vec![Hz(343.0), Hz(686.0), Hz(1_029.0)]);
+        assert_eq!(closed_pipe_harmonics(0.5, 0.0, SPEED_OF_SOUND, 3),
+                   vec![Hz(171.5), Hz(514.5), Hz(857.5)]);
+        assert!(open_pipe_harmonics(0.5, 0.0, SPEED_OF_SOUND, 0).is_empty());
+    }
+
+    #[test]
+    fn temperature() {
+        assert!((speed_of_sound_at(0.0) - SPEED_OF_SOUND_AT_ZERO_C).abs() < 0.001);
+        assert!((speed_of_sound_at(REFERENCE_TEMPERATURE) - 343.215).abs() < 0.01);
+        assert_eq!(temperature_drift(Hz(440.0), 20.0, 20.0), (Hz(440.0), 0.0));
+        // Ten degrees warmer raises a wind instrument by roughly 29 cents.
+        let (hz, cents) = temperature_drift(Hz(440.0), 20.0, 30.0);
+        assert!((hz.hz() - 447.442).abs() < 0.01);
+        assert!((cents - 29.036).abs() < 0.01);
+        let (_, colder) = temperature_drift(Hz(440.0), 20.0, 10.0);
+        assert!(colder < 0.0);
+    }
+}
@@ -0,0 +1,252 @@
+//! A pitch in any of the representations, along with a single entry point for parsing pitches
+//! from text.
+
+use std::fmt;
+use std::str::FromStr;
+use super::{
+    calc,
+    Error,
+    Hz,
+    Letter,
+    LetterOctave,
+    LogPerc,
+    Mel,
+    Octave,
+    OctaveFraction,
+    ParseError,
+    Perc,
+    Pitch,
+    QuarterTone,
+    ScaledPerc,
+    Step,
+};
+
+/// A pitch in any of the representations, tagged with the representation it was given in.
+///
+/// This allows heterogeneous pitches (e.g. user input mixing pitch names and frequencies) to be
+/// stored and processed together without first normalizing them all to a single type. Each
+/// conversion uses the exact conversion of the wrapped representation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum AnyPitch {
+    Hz(Hz),
+    LetterOctave(LetterOctave),
+    LogPerc(LogPerc),
+    Mel(Mel),
+    OctaveFraction(OctaveFraction),
+    Perc(Perc),
+    QuarterTone(QuarterTone),
+    ScaledPerc(ScaledPerc),
+    Step(Step),
+}
+
+/// Parse a pitch from text, understanding:
+///
+/// - frequencies with units, e.g. `440Hz`, `440 hz` or `1.2kHz`,
+/// - mels, e.g. `1000 mel`,
+/// - MIDI note numbers and fractional steps, e.g. `69`, `60.5` or `60.5 st`,
+/// - pitch names, e.g. `A4`, `Bb3`, `F♯5` or the quarter tone `C+4`,
+/// - pitch names with an offset in cents, e.g. `A4 +14c`, which become a `Step` as they no
+///   longer lie on a named pitch.
+pub fn parse_pitch(s: &str) -> Result<AnyPitch, Error> {
+    let s = s.trim();
+    let lower = s.to_lowercase();
+    let number = |digits: &str| digits.trim().parse::<f32>().map_err(|_| ParseError::InvalidNumber);
+    if let Some(digits) = lower.strip_suffix("khz") {
+        return Ok(AnyPitch::Hz(Hz(number(digits)? * 1_000.0)));
+    }
+    if let Some(digits) = lower.strip_suffix("hz") {
+        return Ok(AnyPitch::Hz(Hz(number(digits)?)));
+    }
+    if let Some(digits) = lower.strip_suffix("mels").or_else(|| lower.strip_suffix("mel")) {
+        return Ok(AnyPitch::Mel(Mel(number(digits)?)));
+    }
+    if let Some(digits) = lower.strip_suffix("st") {
+        return Ok(AnyPitch::Step(Step(number(digits)?)));
+    }
+    if let Ok(letter_octave) = s.parse::<LetterOctave>() {
+        return Ok(AnyPitch::LetterOctave(letter_octave));
+    }
+    if let Ok(quarter_tone) = s.parse::<QuarterTone>() {
+        return Ok(AnyPitch::QuarterTone(quarter_tone));
+    }
+    s.parse::<Step>().map(AnyPitch::Step)
+}
+
+impl AnyPitch {
+
+    /// The wrapped representation.
+    fn as_pitch(&self) -> &dyn Pitch {
+        match *self {
+            AnyPitch::Hz(ref hz) => hz,
+            AnyPitch::LetterOctave(ref letter_octave) => letter_octave,
+            AnyPitch::LogPerc(ref log_perc) => log_perc,
+            AnyPitch::Mel(ref mel) => mel,
+            AnyPitch::OctaveFraction(ref octave_fraction) => octave_fraction,
+            AnyPitch::Perc(ref perc) => perc,
+            AnyPitch::QuarterTone(ref quarter_tone) => quarter_tone,
+            AnyPitch::ScaledPerc(ref scaled_perc) => scaled_perc,
+            AnyPitch::Step(ref step) => step,
+        }
+    }
+
+    /// Return the unit value of the equivalent frequency.
+    #[inline]
+    pub fn hz(&self) -> calc::Hz {
+        self.to_hz().hz()
+    }
+
+    /// Convert to the equivalent frequency.
+    #[inline]
+    pub fn to_hz(&self) -> Hz {
+        self.as_pitch().to_hz()
+    }
+
+    /// Convert to the closest (Letter, Octave).
+    #[inline]
+    pub fn letter_octave(&self) -> (Letter, Octave) {
+        self.to_letter_octave().letter_octave()
+    }
+
+    /// Convert to the closest LetterOctave.
+    #[inline]
+    pub fn to_letter_octave(&self) -> LetterOctave {
+        self.as_pitch().to_letter_octave()
+    }
+
+    /// Return the unit value of the equivalent Mel.
+    #[inline]
+    pub fn mel(&self) -> calc::Mel {
+        self.to_mel().mel()
+    }
+
+    /// Convert to the equivalent Mel.
+    #[inline]
+    pub fn to_mel(&self) -> Mel {
+        self.as_pitch().to_mel()
+    }
+
+    /// Return the unit value of the equivalent OctaveFraction.
+    #[inline]
+    pub fn octave_fraction(&self) -> calc::OctaveFraction {
+        self.to_octave_fraction().octave_fraction()
+    }
+
+    /// Convert to the equivalent OctaveFraction.
+    #[inline]
+    pub fn to_octave_fraction(&self) -> OctaveFraction {
+        self.as_pitch().to_octave_fraction()
+    }
+
+    /// Return the unit value of the equivalent percentage of the human hearing range.
+    #[inline]
+    pub fn perc(&self) -> calc::Perc {
+        self.to_perc().perc()
+    }
+
+    /// Convert to a percentage of the human hearing range.
+    #[inline]
+    pub fn to_perc(&self) -> Perc {
+        self.as_pitch().to_perc()
+    }
+
+    /// Return the unit value of the equivalent percentage of the human hearing range in
+    /// log-frequency space.
+    #[inline]
+    pub fn log_perc(&self) -> calc::Perc {
+        self.to_log_perc().log_perc()
+    }
+
+    /// Convert to a percentage of the human hearing range in log-frequency space.
+    #[inline]
+    pub fn to_log_perc(&self) -> LogPerc {
+        self.as_pitch().to_log_perc()
+    }
+
+    /// Convert to a scaled percentage of the human hearing range with the default weight.
+    #[inline]
+    pub fn to_scaled_perc(&self) -> ScaledPerc {
+        self.as_pitch().to_scaled_perc()
+    }
+
+    /// Return the unit value of the equivalent Step.
+    #[inline]
+    pub fn step(&self) -> calc::Step {
+        self.to_step().step()
+    }
+
+    /// Convert to the equivalent Step.
+    #[inline]
+    pub fn to_step(&self) -> Step {
+        self.as_pitch().to_step()
+    }
+
+}
+
+impl fmt::Display for AnyPitch {
+    /// Formats the wrapped representation, passing through any flags (e.g. `{:#}` for units).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AnyPitch::Hz(ref hz) => fmt::Display::fmt(hz, f),
+            AnyPitch::LetterOctave(ref letter_octave) => fmt::Display::fmt(letter_octave, f),
+            AnyPitch::LogPerc(ref log_perc) => fmt::Display::fmt(log_perc, f),
+            AnyPitch::Mel(ref mel) => fmt::Display::fmt(mel, f),
+            AnyPitch::OctaveFraction(ref octave_fraction) => fmt::Display::fmt(octave_fraction, f),
+            AnyPitch::Perc(ref perc) => fmt::Display::fmt(perc, f),
+            AnyPitch::QuarterTone(ref quarter_tone) => fmt::Display::fmt(quarter_tone, f),
+            AnyPitch::ScaledPerc(ref scaled_perc) => fmt::Display::fmt(scaled_perc, f),
+            AnyPitch::Step(ref step) => fmt::Display::fmt(step, f),
+        }
+    }
+}
+
+impl FromStr for AnyPitch {
+    type Err = Error;
+    #[inline]
+    fn from_str(s: &str) -> Result<AnyPitch, Error> {
+        parse_pitch(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_each_representation() {
+        assert_eq!(parse_pitch("440Hz"), Ok(AnyPitch::Hz(Hz(440.0))));
+        assert_eq!(parse_pitch(" 1.5 kHz "), Ok(AnyPitch::Hz(Hz(1_500.0))));
+        assert_eq!(parse_pitch("1000 mel"), Ok(AnyPitch::Mel(Mel(1_000.0))));
+        assert_eq!(parse_pitch("69"), Ok(AnyPitch::Step(Step(69.0))));
+        assert_eq!(parse_pitch("60.5 st"), Ok(AnyPitch::Step(Step(60.5))));
+        assert_eq!(parse_pitch("A4"), Ok(AnyPitch::LetterOctave(LetterOctave(Letter::A, 4))));
+        assert!(matches!(parse_pitch("C+4"), Ok(AnyPitch::QuarterTone(_))));
+        assert_eq!(parse_pitch("A4 +14c"), Ok(AnyPitch::Step(Step(69.14))));
+        assert_eq!(parse_pitch("x Hz"), Err(ParseError::InvalidNumber.into()));
+        assert!(parse_pitch("H4").is_err());
+        assert_eq!(parse_pitch("A4").unwrap().to_hz(), Hz(440.0));
+    }
+
+    #[test]
+    fn mixed_representations() {
+        let pitches = vec![
+            AnyPitch::Hz(Hz(440.0)),
+            AnyPitch::LetterOctave(LetterOctave(Letter::A, 4)),
+            AnyPitch::Step(Step(69.0)),
+            AnyPitch::Mel(Hz(440.0).to_mel()),
+            AnyPitch::OctaveFraction(OctaveFraction(4.75)),
+        ];
+        for pitch in &pitches {
+            assert!((pitch.step() - 69.0).abs() < 0.001);
+            assert_eq!(pitch.letter_octave(), (Letter::A, 4));
+        }
+        let formatted: Vec<String> = pitches.iter().map(|p| format!("{:#.2}", p)).collect();
+        assert_eq!(formatted[0], "440.00 Hz");
+        assert_eq!(formatted[2], "69.00 st");
+        assert_eq!(format!("{:#.1}", AnyPitch::Perc(Perc(0.372))), "37.2 %");
+        assert_eq!(format!("{}", AnyPitch::Hz(Hz(440.0))), "440");
+        assert_eq!(format!("{:#}", AnyPitch::Mel(Mel(1125.0))), "1125 mel");
+        // The original representation is preserved.
+        assert!(matches!(pitches[1], AnyPitch::LetterOctave(_)));
+    }
+}
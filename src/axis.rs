@@ -0,0 +1,171 @@
+//! Tick generation for log-frequency axes, such as those of spectrograms and frequency response
+//! plots.
+
+use super::{
+    calc,
+    Hz,
+    Step,
+};
+
+/// The kind of label to give each tick.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TickLabels {
+    /// Ticks lie on notes and are labelled with their names (e.g. `A4`), starting with octaves
+    /// and adding notes as the axis is zoomed in.
+    Notes,
+    /// Ticks lie on round frequencies and are labelled in hz (e.g. `500 Hz`, `2 kHz`), starting
+    /// with decades and adding finer values as the axis is zoomed in.
+    Hz,
+}
+
+/// A single tick along a log-frequency axis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tick {
+    /// The frequency at which the tick lies.
+    pub hz: Hz,
+    /// The distance of the tick from the start of the axis, in the same unit as the axis width.
+    pub position: f32,
+    /// The label for the tick.
+    pub label: String,
+    /// Whether or not this is a major tick (a `C` for notes, or a power of ten for hz).
+    pub major: bool,
+}
+
+/// Nicely spaced ticks along a log-frequency axis spanning `min` to `max` hz and `width` units
+/// (e.g. pixels) in length.
+///
+/// The densest set of ticks is chosen for which no two ticks lie closer than `min_spacing`
+/// units. Returns an empty `Vec` unless `min`, `max` and `width` are finite and positive and
+/// `max` is greater than `min`.
+pub fn ticks(min: Hz, max: Hz, width: f32, min_spacing: f32, labels: TickLabels) -> Vec<Tick> {
+    let (min, max) = (min.hz() as f64, max.hz() as f64);
+    let finite = min.is_finite() && max.is_finite() && width.is_finite();
+    if !finite || min <= 0.0 || max <= min || width <= 0.0 {
+        return Vec::new();
+    }
+    let max_ticks = ((width / min_spacing.max(f32::EPSILON)) as usize).saturating_add(1);
+    let position = |hz: f64| ((hz / min).ln() / (max / min).ln()) as f32 * width;
+    let candidates = match labels {
+        TickLabels::Notes => note_candidates(min, max, max_ticks),
+        TickLabels::Hz => hz_candidates(min, max, max_ticks),
+    };
+    let fits = |set: &Vec<(f64, bool)>| set.windows(2)
+        .all(|pair| position(pair[1].0) - position(pair[0].0) >= min_spacing);
+    let best = candidates.into_iter()
+        .filter(|set| set.len() <= max_ticks && fits(set))
+        .max_by_key(|set| set.len())
+        .unwrap_or_default();
+    best.into_iter()
+        .map(|(hz, major)| Tick {
+            hz: Hz(hz as calc::Hz),
+            position: position(hz),
+            label: match labels {
+                TickLabels::Notes => Hz(hz as calc::Hz).to_letter_octave().to_string(),
+                TickLabels::Hz => hz_label(hz),
+            },
+            major,
+        })
+        .collect()
+}
+
+/// Candidate sets of note ticks, each every `interval` semitones from `C`.
+fn note_candidates(min: f64, max: f64, max_ticks: usize) -> Vec<Vec<(f64, bool)>> {
+    let (lowest, highest) = (Hz(min as calc::Hz).step(), Hz(max as calc::Hz).step());
+    let intervals = [96, 48, 24, 12, 6, 3, 1];
+    intervals.iter()
+        .filter(|&&interval| ((highest - lowest) / interval as calc::Step) as usize <= max_ticks)
+        .map(|&interval| {
+            let first = (lowest / interval as calc::Step).ceil() as i32 * interval;
+            (0..)
+                .map(|i| first + i * interval)
+                .take_while(|&step| step as calc::Step <= highest)
+                .map(|step| (Step(step as calc::Step).hz() as f64, step.rem_euclid(12) == 0))
+                .filter(|&(hz, _)| hz >= min && hz <= max)
+                .collect()
+        })
+        .collect()
+}
+
+/// Candidate sets of frequency ticks: powers of ten, the 1-2-5 series, every integer multiple
+/// of each power of ten and finally every fixed step of `1`, `2` or `5` times a power of ten.
+fn hz_candidates(min: f64, max: f64, max_ticks: usize) -> Vec<Vec<(f64, bool)>> {
+    let is_decade = |hz: f64| (hz.log10() - hz.log10().round()).abs() < 1e-9;
+    let within = |hz: &f64| *hz >= min * (1.0 - 1e-9) && *hz <= max * (1.0 + 1e-9);
+    let (first_decade, last_decade) = (min.log10().floor() as i32, max.log10().ceil() as i32);
+    let per_decade = |mantissas: &[f64]| -> Vec<(f64, bool)> {
+        (first_decade..last_decade + 1)
+            .flat_map(|e| mantissas.iter().map(move |m| m * 10f64.powi(e)))
+            .filter(within)
+            .map(|hz| (hz, is_decade(hz)))
+            .collect()
+    };
+    let mut candidates = vec![
+        per_decade(&[1.0]),
+        per_decade(&[1.0, 2.0, 5.0]),
+        per_decade(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]),
+    ];
+    for e in (first_decade - 6..last_decade + 1).rev() {
+        for &k in &[5.0, 2.0, 1.0] {
+            let interval = k * 10f64.powi(e);
+            let (first, last) = ((min / interval).ceil(), (max / interval).floor());
+            if last < first || (last - first) as usize > max_ticks {
+                continue;
+            }
+            candidates.push((first as i64..last as i64 + 1)
+                .map(|i| i as f64 * interval)
+                .map(|hz| (hz, is_decade(hz)))
+                .collect());
+        }
+    }
+    candidates
+}
+
+/// Format a frequency in hz or khz, dropping any insignificant trailing digits.
+fn hz_label(hz: f64) -> String {
+    let trim = |value: f64| {
+        let s = format!("{:.3}", value);
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    };
+    if hz >= 1000.0 {
+        format!("{} kHz", trim(hz / 1000.0))
+    } else {
+        format!("{} Hz", trim(hz))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn note_ticks_zoom() {
+        let wide = ticks(Hz(20.0), Hz(20_000.0), 400.0, 30.0, TickLabels::Notes);
+        assert!(wide.iter().all(|t| t.major));
+        assert!(wide.iter().any(|t| t.label == "C4"));
+        let narrow = ticks(Hz(220.0), Hz(880.0), 1200.0, 30.0, TickLabels::Notes);
+        assert!(narrow.iter().any(|t| t.label == "A4"));
+        assert!(narrow.iter().any(|t| !t.major));
+        assert!(narrow.windows(2).all(|w| w[1].position - w[0].position >= 30.0));
+    }
+
+    #[test]
+    fn hz_ticks_zoom() {
+        let wide = ticks(Hz(20.0), Hz(20_000.0), 300.0, 40.0, TickLabels::Hz);
+        let labels: Vec<&str> = wide.iter().map(|t| t.label.as_str()).collect();
+        assert_eq!(labels, vec!["100 Hz", "1 kHz", "10 kHz"]);
+        let narrow = ticks(Hz(430.0), Hz(450.0), 800.0, 40.0, TickLabels::Hz);
+        assert!(narrow.iter().any(|t| t.label == "440 Hz"));
+        assert!(narrow.len() > 3);
+        assert!(ticks(Hz(100.0), Hz(50.0), 300.0, 40.0, TickLabels::Hz).is_empty());
+        // Bounds that are not finite have no decades to tick.
+        let inf = calc::Hz::INFINITY;
+        for &labels in [TickLabels::Hz, TickLabels::Notes].iter() {
+            assert!(ticks(Hz(20.0), Hz(inf), 300.0, 40.0, labels).is_empty());
+            assert!(ticks(Hz(calc::Hz::NAN), Hz(20_000.0), 300.0, 40.0, labels).is_empty());
+            assert!(ticks(Hz(20.0), Hz(20_000.0), inf, 40.0, labels).is_empty());
+        }
+        let huge = ticks(Hz(20.0), Hz(calc::Hz::MAX), 300.0, 40.0, TickLabels::Hz);
+        assert!(huge.iter().all(|t| t.hz.hz().is_finite()));
+        assert!(!huge.is_empty());
+    }
+}
@@ -0,0 +1,358 @@
+use super::{
+    calc,
+    Error,
+    Hz,
+    LetterOctave,
+};
+
+/// The reference frequency from which the IEC 61260 base-ten band centers are derived.
+pub const REFERENCE_HZ: calc::Hz = 1_000.0;
+
+/// The nominal center frequencies of the audible octave bands (IEC 61260).
+pub const OCTAVE_BAND_CENTERS: [Hz; 10] = [
+    Hz(31.5), Hz(63.0), Hz(125.0), Hz(250.0), Hz(500.0),
+    Hz(1_000.0), Hz(2_000.0), Hz(4_000.0), Hz(8_000.0), Hz(16_000.0),
+];
+
+/// The nominal center frequencies of the audible one-third-octave bands (IEC 61260).
+pub const THIRD_OCTAVE_BAND_CENTERS: [Hz; 31] = [
+    Hz(20.0), Hz(25.0), Hz(31.5), Hz(40.0), Hz(50.0), Hz(63.0), Hz(80.0), Hz(100.0),
+    Hz(125.0), Hz(160.0), Hz(200.0), Hz(250.0), Hz(315.0), Hz(400.0), Hz(500.0), Hz(630.0),
+    Hz(800.0), Hz(1_000.0), Hz(1_250.0), Hz(1_600.0), Hz(2_000.0), Hz(2_500.0), Hz(3_150.0),
+    Hz(4_000.0), Hz(5_000.0), Hz(6_300.0), Hz(8_000.0), Hz(10_000.0), Hz(12_500.0),
+    Hz(16_000.0), Hz(20_000.0),
+];
+
+/// The width of a standardised band.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum BandSize {
+    Octave,
+    ThirdOctave,
+}
+
+impl BandSize {
+
+    /// The number of bands of this size that fit within a single octave.
+    #[inline]
+    pub fn bands_per_octave(&self) -> u32 {
+        match *self {
+            BandSize::Octave => 1,
+            BandSize::ThirdOctave => 3,
+        }
+    }
+
+    /// The nominal center frequencies for this band size.
+    #[inline]
+    pub fn nominal_centers(&self) -> &'static [Hz] {
+        match *self {
+            BandSize::Octave => &OCTAVE_BAND_CENTERS,
+            BandSize::ThirdOctave => &THIRD_OCTAVE_BAND_CENTERS,
+        }
+    }
+
+    /// The band index (relative to the 1 kHz band) of the lowest nominal center.
+    #[inline]
+    fn first_index(&self) -> i32 {
+        match *self {
+            BandSize::Octave => -5,
+            BandSize::ThirdOctave => -17,
+        }
+    }
+
+}
+
+/// A single frequency band.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Band {
+    /// The nominal (rounded) center frequency, as printed on measurement equipment.
+    pub nominal: Hz,
+    /// The exact mid-band frequency.
+    pub center: Hz,
+    /// The lower band-edge frequency.
+    pub lower: Hz,
+    /// The upper band-edge frequency.
+    pub upper: Hz,
+}
+
+impl Band {
+
+    /// The band of a filter with the given center frequency and Q, with edges at the -3 dB points.
+    ///
+    /// The edges lie symmetrically about the center in log-frequency space, and the nominal
+    /// frequency is the exact center. An infinite Q gives a band of zero width.
+    ///
+    /// Returns `Error::InvalidValue` if `q` is `NaN`, and `Error::OutOfRange` if it is not greater
+    /// than zero.
+    pub fn from_q(center: Hz, q: f32) -> Result<Band, Error> {
+        if q.is_nan() {
+            return Err(Error::InvalidValue);
+        }
+        if q <= 0.0 {
+            return Err(Error::OutOfRange);
+        }
+        let f0 = center.hz() as f64;
+        let half = 1.0 / (2.0 * q as f64);
+        let root = (1.0 + half * half).sqrt();
+        Ok(Band {
+            nominal: center,
+            center,
+            lower: Hz((f0 * (root - half)) as calc::Hz),
+            upper: Hz((f0 * (root + half)) as calc::Hz),
+        })
+    }
+
+    /// The band of a filter with the given center frequency and bandwidth in octaves.
+    #[inline]
+    pub fn from_octaves(center: Hz, octaves: calc::OctaveFraction) -> Band {
+        let half_width = (octaves as f64 / 2.0).exp2();
+        let f0 = center.hz() as f64;
+        Band {
+            nominal: center,
+            center,
+            lower: Hz((f0 / half_width) as calc::Hz),
+            upper: Hz((f0 * half_width) as calc::Hz),
+        }
+    }
+
+    /// The notes nearest to the lower and upper band edges, for labelling bands musically.
+    #[inline]
+    pub fn edge_notes(&self) -> (LetterOctave, LetterOctave) {
+        (self.lower.to_letter_octave(), self.upper.to_letter_octave())
+    }
+
+    /// Whether or not the given frequency lies within the band edges.
+    ///
+    /// The lower edge is inclusive and the upper edge exclusive so that adjacent bands do not
+    /// overlap.
+    #[inline]
+    pub fn contains(&self, hz: Hz) -> bool {
+        hz.hz() >= self.lower.hz() && hz.hz() < self.upper.hz()
+    }
+
+}
+
+/// The base-ten octave ratio `10^(3/10)` used by IEC 61260.
+#[inline]
+fn octave_ratio() -> f64 {
+    10.0f64.powf(0.3)
+}
+
+/// Construct the band with the given index relative to the 1 kHz band.
+fn band_at(index: i32, size: BandSize) -> Band {
+    let b = size.bands_per_octave() as f64;
+    let g = octave_ratio();
+    let center = REFERENCE_HZ as f64 * g.powf(index as f64 / b);
+    let half_width = g.powf(1.0 / (2.0 * b));
+    let nominal = size.nominal_centers()[(index - size.first_index()) as usize];
+    Band {
+        nominal,
+        center: Hz(center as calc::Hz),
+        lower: Hz((center / half_width) as calc::Hz),
+        upper: Hz((center * half_width) as calc::Hz),
+    }
+}
+
+/// All standard bands of the given size within the audible range, from lowest to highest.
+pub fn bands(size: BandSize) -> Vec<Band> {
+    let first = size.first_index();
+    (0..size.nominal_centers().len() as i32).map(|i| band_at(first + i, size)).collect()
+}
+
+/// Find the standard band of the given size that contains the given frequency.
+///
+/// Returns `None` if the frequency lies outside of every band in the table.
+pub fn band_for(hz: Hz, size: BandSize) -> Option<Band> {
+    if hz.hz().is_nan() || hz.hz() <= 0.0 {
+        return None;
+    }
+    let b = size.bands_per_octave() as f64;
+    let index = (b * (hz.hz() as f64 / REFERENCE_HZ as f64).log(octave_ratio())).round() as i32;
+    let first = size.first_index();
+    let last = first + size.nominal_centers().len() as i32 - 1;
+    if index < first || index > last {
+        return None;
+    }
+    Some(band_at(index, size))
+}
+
+/// The Q of a band-pass or peaking filter spanning the given bandwidth in octaves, as measured
+/// between its -3 dB points, e.g. `1.414` for one octave.
+///
+/// Returns infinity for a bandwidth of zero.
+#[inline]
+pub fn q_from_octaves(octaves: calc::OctaveFraction) -> f32 {
+    let ratio = (octaves as f64).exp2();
+    (ratio.sqrt() / (ratio - 1.0)) as f32
+}
+
+/// The bandwidth in octaves between the -3 dB points of a filter with the given Q.
+#[inline]
+pub fn octaves_from_q(q: f32) -> calc::OctaveFraction {
+    (2.0 / ::std::f64::consts::LN_2 * (1.0 / (2.0 * q as f64)).asinh()) as calc::OctaveFraction
+}
+
+/// The Q of a filter spanning the given bandwidth in (possibly fractional) semitones.
+#[inline]
+pub fn q_from_semitones(semitones: calc::Step) -> f32 {
+    q_from_octaves(semitones / 12.0)
+}
+
+/// The bandwidth in semitones of a filter with the given Q.
+#[inline]
+pub fn semitones_from_q(q: f32) -> calc::Step {
+    octaves_from_q(q) * 12.0
+}
+
+/// The Q of a filter spanning the given bandwidth in cents.
+#[inline]
+pub fn q_from_cents(cents: calc::Cents) -> f32 {
+    q_from_octaves(cents / 1_200.0)
+}
+
+/// The bandwidth in cents of a filter with the given Q.
+#[inline]
+pub fn cents_from_q(q: f32) -> calc::Cents {
+    octaves_from_q(q) * 1_200.0
+}
+
+/// Divide the range between `min` and `max` hz into bands of equal width in log-frequency space,
+/// `bands_per_octave` to each octave (e.g. `3` for third-octave bands), from lowest to highest.
+///
+/// Unlike `bands`, the bands are not standardised: the first band begins exactly at `min`, each
+/// center is the geometric mean of its edges and the nominal frequency is the exact center. If
+/// the range is not a whole number of bands wide the last band is narrowed to end at `max`.
+///
+/// Returns an empty `Vec` if `bands_per_octave` is zero, either bound is not finite and positive
+/// or the range is empty.
+pub fn split_range(min: Hz, max: Hz, bands_per_octave: u32) -> Vec<Band> {
+    let (min, max) = (min.hz() as f64, max.hz() as f64);
+    if bands_per_octave == 0 || !min.is_finite() || !max.is_finite() || min <= 0.0 || max <= min {
+        return Vec::new();
+    }
+    let b = bands_per_octave as f64;
+    // Allow a little rounding error so that a whole number of bands does not gain a sliver.
+    let count = ((max / min).log2() * b - 1e-9).ceil() as u32;
+    (0..count)
+        .map(|i| {
+            let lower = min * (i as f64 / b).exp2();
+            let upper = (min * ((i + 1) as f64 / b).exp2()).min(max);
+            let center = Hz((lower * upper).sqrt() as calc::Hz);
+            Band {
+                nominal: center,
+                center,
+                lower: Hz(lower as calc::Hz),
+                upper: Hz(upper as calc::Hz),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_bands() {
+        for &size in [BandSize::Octave, BandSize::ThirdOctave].iter() {
+            let bands = bands(size);
+            assert_eq!(bands.len(), size.nominal_centers().len());
+            let b = size.bands_per_octave() as f64;
+            for (i, band) in bands.iter().enumerate() {
+                // Each band is labelled with the nominal center at the same position in the table,
+                // which lies within a few percent of the exact base-ten center.
+                assert_eq!(band.nominal, size.nominal_centers()[i]);
+                let index = size.first_index() + i as i32;
+                let exact = 1_000.0 * octave_ratio().powf(index as f64 / b);
+                assert!((band.center.hz() as f64 - exact).abs() < exact * 1e-6);
+                assert!((band.center.hz() / band.nominal.hz() - 1.0).abs() < 0.03);
+                assert!(band.contains(band.center));
+            }
+            for pair in bands.windows(2) {
+                let (upper, lower) = (pair[0].upper.hz(), pair[1].lower.hz());
+                assert!((upper - lower).abs() < upper * 1e-5);
+            }
+        }
+        let octaves = bands(BandSize::Octave);
+        assert_eq!(octaves[5].nominal, Hz(1_000.0));
+        assert_eq!(octaves[5].center, Hz(1_000.0));
+        assert_eq!(bands(BandSize::ThirdOctave)[17].center, Hz(1_000.0));
+    }
+
+    #[test]
+    fn band_lookup() {
+        for &size in [BandSize::Octave, BandSize::ThirdOctave].iter() {
+            for &nominal in size.nominal_centers().iter() {
+                assert_eq!(band_for(nominal, size).map(|band| band.nominal), Some(nominal));
+            }
+            // Frequencies just within the edges of a band belong to it.
+            for band in bands(size) {
+                assert_eq!(band_for(Hz(band.lower.hz() * 1.000_1), size), Some(band));
+                assert_eq!(band_for(Hz(band.upper.hz() * 0.999_9), size), Some(band));
+            }
+        }
+        // Just outside the lowest and highest bands.
+        let octaves = bands(BandSize::Octave);
+        assert_eq!(band_for(Hz(octaves[0].lower.hz() * 0.999), BandSize::Octave), None);
+        assert_eq!(band_for(Hz(octaves[9].upper.hz() * 1.001), BandSize::Octave), None);
+        let thirds = bands(BandSize::ThirdOctave);
+        assert_eq!(band_for(Hz(thirds[0].lower.hz() * 0.999), BandSize::ThirdOctave), None);
+        assert_eq!(band_for(Hz(thirds[30].upper.hz() * 1.001), BandSize::ThirdOctave), None);
+        assert_eq!(band_for(Hz(0.0), BandSize::Octave), None);
+        assert_eq!(band_for(Hz(calc::Hz::NAN), BandSize::Octave), None);
+    }
+
+    #[test]
+    fn split_into_bands() {
+        let octaves = split_range(Hz(20.0), Hz(20_480.0), 1);
+        assert_eq!(octaves.len(), 10);
+        assert_eq!(octaves[0].lower, Hz(20.0));
+        assert!((octaves[0].center.hz() - 28.284).abs() < 0.001);
+        assert_eq!(octaves[9].upper, Hz(20_480.0));
+        for pair in octaves.windows(2) {
+            assert_eq!(pair[0].upper, pair[1].lower);
+        }
+        let thirds = split_range(Hz(100.0), Hz(300.0), 3);
+        assert_eq!(thirds.len(), 5);
+        assert_eq!(thirds[4].upper, Hz(300.0));
+        assert!(thirds[4].contains(Hz(299.0)));
+        assert!(split_range(Hz(100.0), Hz(50.0), 3).is_empty());
+        assert!(split_range(Hz(100.0), Hz(200.0), 0).is_empty());
+        assert!(split_range(Hz(100.0), Hz(calc::Hz::INFINITY), 3).is_empty());
+        assert!(split_range(Hz(0.0), Hz(200.0), 3).is_empty());
+        assert!(split_range(Hz(calc::Hz::NAN), Hz(200.0), 3).is_empty());
+        // The widest range of finite frequencies spans fewer than 300 octaves.
+        let widest = split_range(Hz(calc::Hz::MIN_POSITIVE), Hz(calc::Hz::MAX), 1);
+        assert!(!widest.is_empty() && widest.len() < 300);
+    }
+
+    #[test]
+    fn q_and_bandwidth() {
+        assert!((q_from_octaves(1.0) - ::std::f32::consts::SQRT_2).abs() < 0.0001);
+        assert!((q_from_octaves(1.0 / 3.0) - 4.3185).abs() < 0.0001);
+        assert!((octaves_from_q(::std::f32::consts::SQRT_2) - 1.0).abs() < 0.0001);
+        assert!((semitones_from_q(4.3185) - 4.0).abs() < 0.001);
+        assert!((cents_from_q(q_from_cents(50.0)) - 50.0).abs() < 0.01);
+        assert!((q_from_semitones(24.0) - q_from_octaves(2.0)).abs() < 0.0001);
+        assert!(q_from_octaves(0.0).is_infinite());
+    }
+
+    #[test]
+    fn band_edges_from_q() {
+        use super::super::Letter;
+        let band = Band::from_q(Hz(440.0), q_from_octaves(1.0)).unwrap();
+        assert!((band.lower.hz() - 311.127).abs() < 0.01);
+        assert!((band.upper.hz() - 622.254).abs() < 0.01);
+        assert_eq!(band.edge_notes(), (LetterOctave(Letter::Eb, 4), LetterOctave(Letter::Eb, 5)));
+        let octave = Band::from_octaves(Hz(440.0), 1.0);
+        assert!((octave.lower.hz() - band.lower.hz()).abs() < 0.01);
+        assert!((octave.upper.hz() - band.upper.hz()).abs() < 0.01);
+        assert!(band.contains(Hz(440.0)));
+        assert_eq!(Band::from_q(Hz(440.0), 0.0), Err(Error::OutOfRange));
+        assert_eq!(Band::from_q(Hz(440.0), -1.0), Err(Error::OutOfRange));
+        assert_eq!(Band::from_q(Hz(440.0), f32::NAN), Err(Error::InvalidValue));
+        let narrow = Band::from_q(Hz(440.0), f32::INFINITY).unwrap();
+        assert_eq!((narrow.lower, narrow.upper), (Hz(440.0), Hz(440.0)));
+    }
+}
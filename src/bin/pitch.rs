@@ -0,0 +1,159 @@
+//!
+//! A command line tool converting a pitch between each of its representations, e.g.
+//!
+//! ```text
+//! pitch C#4
+//! pitch 445hz --to step
+//! pitch 69 --a4 442
+//! ```
+//!
+//! Built when the `cli` feature is enabled.
+//!
+
+extern crate pitch_calc;
+
+use pitch_calc::{
+    AnyPitch,
+    Hz,
+    Step,
+    Tuning,
+    nearest_step,
+    parse_pitch,
+};
+use std::env;
+use std::process;
+
+const USAGE: &str = "\
+usage: pitch <PITCH> [--to <REPRESENTATION>] [--a4 <HZ>]
+
+PITCH may be a frequency (440hz, 1.2khz), mels (1000mel), a step or MIDI note
+number (69, 60.5st), or a pitch name (A4, Bb3, C#4 +14c).
+
+REPRESENTATION is one of: hz, step, letter, cents, mel, octave, perc.
+--a4 sets the frequency of A4 used to relate frequencies to steps (default 440).";
+
+/// The representations that may be requested with `--to`.
+const REPRESENTATIONS: &[&str] = &["hz", "step", "letter", "cents", "mel", "octave", "perc"];
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(lines) => for line in lines { println!("{}", line) },
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            process::exit(1);
+        },
+    }
+}
+
+/// Parse the arguments and produce the lines to print.
+fn run(args: &[String]) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut to = None;
+    let mut a4 = 440.0;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(vec![USAGE.to_string()]),
+            "--to" => {
+                let representation = args.next().ok_or("`--to` expects a representation")?;
+                if !REPRESENTATIONS.contains(&representation.as_str()) {
+                    return Err(format!("unknown representation `{}`", representation));
+                }
+                to = Some(representation.as_str());
+            },
+            "--a4" => {
+                let hz = args.next().ok_or("`--a4` expects a frequency")?;
+                // Accept the unit in any case, e.g. `442`, `442hz` or `442 Hz`.
+                a4 = hz.to_lowercase().trim_end_matches("hz").trim().parse()
+                    .ok()
+                    .filter(|a4: &f32| a4.is_finite() && *a4 > 0.0)
+                    .ok_or_else(|| format!("invalid frequency `{}`", hz))?;
+            },
+            // Allow the pitch to be given unquoted, e.g. `pitch A4 +14c`.
+            _ => words.push(arg.as_str()),
+        }
+    }
+    if words.is_empty() {
+        return Err("expected a pitch".to_string());
+    }
+    let pitch = words.join(" ");
+    let pitch = parse_pitch(&pitch).map_err(|err| format!("`{}`: {}", pitch, err))?;
+
+    // Frequencies are related to steps by the tuning, while names and steps are independent of it.
+    let tuning = Tuning::from_a4(a4);
+    let step = match pitch {
+        AnyPitch::Hz(hz) => tuning.step(hz),
+        AnyPitch::Mel(mel) => tuning.step(mel.to_hz()),
+        _ => pitch.to_step(),
+    };
+    let hz = tuning.hz(step);
+
+    let line = |representation: &str| format_representation(representation, step, hz);
+    Ok(match to {
+        Some(representation) => vec![line(representation)],
+        None => REPRESENTATIONS.iter()
+            .map(|representation| format!("{:<7} {}", representation, line(representation)))
+            .collect(),
+    })
+}
+
+/// Format the pitch in the given representation.
+fn format_representation(representation: &str, step: Step, hz: Hz) -> String {
+    let cents = (step.step() - nearest_step(step.step())) * 100.0;
+    match representation {
+        "hz" => format!("{:#.3}", hz),
+        "step" => format!("{:.3}", step),
+        "letter" => format!("{}", step.to_letter_octave()),
+        "cents" => format!("{:+.1}c", cents),
+        "mel" => format!("{:#.3}", hz.to_mel()),
+        "octave" => format!("{:.4}", step.to_octave_fraction()),
+        "perc" => format!("{:#.2}", hz.to_perc()),
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_with(args: &[&str]) -> Result<Vec<String>, String> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        run(&args)
+    }
+
+    #[test]
+    fn every_representation() {
+        let lines = run_with(&["C#4"]).unwrap();
+        assert_eq!(lines.len(), REPRESENTATIONS.len());
+        assert_eq!(lines[0], "hz      277.183 Hz");
+        assert_eq!(lines[2], "letter  C#4");
+        // The pitch may be given unquoted.
+        assert_eq!(run_with(&["A4", "+14c", "--to", "cents"]).unwrap(), ["+14.0c"]);
+    }
+
+    #[test]
+    fn to() {
+        assert_eq!(run_with(&["445hz", "--to", "step"]).unwrap(), ["69.196"]);
+        assert_eq!(run_with(&["445Hz", "--to", "step"]).unwrap(), ["69.196"]);
+        assert_eq!(run_with(&["69", "--to", "letter"]).unwrap(), ["A4"]);
+        assert!(run_with(&["69", "--to", "cycles"]).is_err());
+        assert!(run_with(&["69", "--to"]).is_err());
+    }
+
+    #[test]
+    fn a4() {
+        assert_eq!(run_with(&["69", "--a4", "442", "--to", "hz"]).unwrap(), ["442.000 Hz"]);
+        assert_eq!(run_with(&["69", "--a4", "442Hz", "--to", "hz"]).unwrap(), ["442.000 Hz"]);
+        assert_eq!(run_with(&["442hz", "--a4", "442 HZ", "--to", "step"]).unwrap(), ["69.000"]);
+        assert!(run_with(&["69", "--a4", "0"]).is_err());
+        assert!(run_with(&["69", "--a4", "fast"]).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(run_with(&[]).is_err());
+        assert!(run_with(&["H4"]).is_err());
+        assert_eq!(run_with(&["--help"]).unwrap(), [USAGE]);
+    }
+}
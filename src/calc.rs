@@ -1,6 +1,6 @@
 
+use std::f64::consts::LN_10;
 use num::{
-    Float,
     FromPrimitive,
     ToPrimitive,
 };
@@ -13,8 +13,8 @@ use super::{
 };
 use utils::modulo;
 
-/// Useful for conversions between Step and Hz.
-const TWELFTH_ROOT_OF_TWO: f32 = 1.059463094359;
+/// The number of steps within an octave, used for conversions between Step and Hz.
+const STEPS_PER_OCTAVE: f64 = 12.0;
 /// The pitch `A 4` represented in steps.
 const TUNING_PITCH_A4: f32 = 69.0;
 /// The pitch `A 4` represented in hz.
@@ -22,13 +22,21 @@ const PITCH_INDEX: f32 = 440.0;
 /// Octave offset to match MIDI step standard (i.e. A4 == 69).
 const MIDI_OCTAVE_OFFSET: Octave = 1;
 
+pub type Cents = f32;
 pub type Hz = f32;
 pub type Mel = f32;
+pub type OctaveFraction = f32;
 pub type Perc = f64;
 pub type Semitones = i32;
 pub type Step = f32;
 pub type Weight = f32;
 
+/// Calculate the size of the interval between two frequencies with the given ratio in cents.
+#[inline]
+pub fn cents_from_ratio(ratio: f64) -> Cents {
+    (ratio.log2() * 1200.0) as Cents
+}
+
 /// Find and return the smallest distance
 /// between two letters in semitones as an int.
 #[inline]
@@ -43,16 +51,40 @@ pub fn hz_from_letter_octave(letter: Letter, octave: Octave) -> Hz {
     hz_from_step(step_from_letter_octave(letter, octave))
 }
 
+/// Calculate hz from (Letter, Octave) where `A 4` is tuned to `a4` hz.
+#[inline]
+pub fn hz_from_letter_octave_with_a4(letter: Letter, octave: Octave, a4: Hz) -> Hz {
+    hz_from_step_with_a4(step_from_letter_octave(letter, octave), a4)
+}
+
+/// Calculate hz from a percentage of the human hearing range in log-frequency space.
+#[inline]
+pub fn hz_from_log_perc(log_perc: Perc) -> Hz {
+    (MIN_HZ as f64 * (MAX_HZ as f64 / MIN_HZ as f64).powf(log_perc)) as Hz
+}
+
 /// Calculate hz from mel.
 #[inline]
 pub fn hz_from_mel(mel: Mel) -> Hz {
-    (10.0.powf(mel / 2595.0) - 1.0) * 700.0
+    ((mel as f64 / 2595.0 * LN_10).exp_m1() * 700.0) as Hz
+}
+
+/// Calculate hz from a fractional octave number.
+#[inline]
+pub fn hz_from_octave_fraction(octave: OctaveFraction) -> Hz {
+    hz_from_step(step_from_octave_fraction(octave))
 }
 
 /// Calculate frequency in hz from percentage.
 #[inline]
 pub fn hz_from_perc(perc: Perc) -> Hz {
-    perc as Hz * (MAX_HZ - MIN_HZ) + MIN_HZ
+    hz_from_perc_with_range(perc, MIN_HZ, MAX_HZ)
+}
+
+/// Calculate frequency in hz from a percentage of the range between `min` and `max` hz.
+#[inline]
+pub fn hz_from_perc_with_range(perc: Perc, min: Hz, max: Hz) -> Hz {
+    (perc * (max as Perc - min as Perc) + min as Perc) as Hz
 }
 
 /// Calculate hz from scaled percentage.
@@ -64,7 +96,17 @@ pub fn hz_from_scaled_perc(scaled: Perc, weight: Weight) -> Hz {
 /// Calculate hz from pitch as `step`.
 #[inline]
 pub fn hz_from_step(step: Step) -> Hz {
-    PITCH_INDEX * TWELFTH_ROOT_OF_TWO.powf(step - TUNING_PITCH_A4)
+    hz_from_step_with_a4(step, PITCH_INDEX)
+}
+
+/// Calculate hz from pitch as `step` where `A 4` is tuned to `a4` hz.
+///
+/// The exponent is computed in `f64` (rather than by raising an `f32` twelfth root of two to the
+/// power of the step) so that whole steps convert to the correctly rounded frequency and back.
+#[inline]
+pub fn hz_from_step_with_a4(step: Step, a4: Hz) -> Hz {
+    let octaves = (step as f64 - TUNING_PITCH_A4 as f64) / STEPS_PER_OCTAVE;
+    (a4 as f64 * octaves.exp2()) as Hz
 }
 
 /// Calculate (Letter, Octave) from hz.
@@ -73,6 +115,12 @@ pub fn letter_octave_from_hz(hz: Hz) -> (Letter, Octave) {
     letter_octave_from_step(step_from_hz(hz))
 }
 
+/// Calculate (Letter, Octave) from hz where `A 4` is tuned to `a4` hz.
+#[inline]
+pub fn letter_octave_from_hz_with_a4(hz: Hz, a4: Hz) -> (Letter, Octave) {
+    letter_octave_from_step(step_from_hz_with_a4(hz, a4))
+}
+
 /// Calculate (Letter, Octave) from mel.
 #[inline]
 pub fn letter_octave_from_mel(mel: Mel) -> (Letter, Octave) {
@@ -92,18 +140,40 @@ pub fn letter_octave_from_scaled_perc(scaled: Perc, weight: Weight) -> (Letter,
 }
 
 /// Calculate pitch as (Letter, Octave) from pitch as step.
+///
+/// The step is rounded to the nearest whole step with `nearest_step` and the octave is found by
+/// flooring, so that the negative range behaves like the positive range (e.g. step `-1.0` is
+/// `B -2` and step `-12.5` is `C -2`).
 #[inline]
 pub fn letter_octave_from_step(step: Step) -> (Letter, Octave) {
-    let rounded = step.round() as Octave;
+    let rounded = nearest_step(step) as Octave;
     let letter_step = modulo(rounded, TOTAL_LETTERS as Octave);
-    (FromPrimitive::from_i32(letter_step).unwrap(), (rounded - letter_step) / 12 - MIDI_OCTAVE_OFFSET)
+    let octave = rounded.div_euclid(TOTAL_LETTERS as Octave) - MIDI_OCTAVE_OFFSET;
+    (FromPrimitive::from_i32(letter_step).unwrap(), octave)
+}
+
+/// Calculate the percentage of the human hearing range in log-frequency space from hz, i.e.
+/// the fraction of the octaves between `MIN_HZ` and `MAX_HZ` lying below the frequency.
+#[inline]
+pub fn log_perc_from_hz(hz: Hz) -> Perc {
+    (hz as f64 / MIN_HZ as f64).ln() / (MAX_HZ as f64 / MIN_HZ as f64).ln()
+}
+
+/// Calculate the percentage of the human hearing range in log-frequency space from `step`.
+#[inline]
+pub fn log_perc_from_step(step: Step) -> Perc {
+    log_perc_from_hz(hz_from_step(step))
 }
 
 /// Calculate mel from hz.
 /// Formula used from http://en.wikipedia.org/wiki/Mel_scale
+///
+/// As with the other logarithmic conversions, the math is performed in `f64` and rounded once
+/// to `f32`, so that chains of conversions (e.g. `Hz -> Mel -> Hz -> Step`) do not accumulate the
+/// rounding error of each intermediate step.
 #[inline]
 pub fn mel_from_hz(hz: Hz) -> Mel {
-    (1.0 + hz / 700.0).log10() * 2595.0
+    ((hz as f64 / 700.0).ln_1p() / LN_10 * 2595.0) as Mel
 }
 
 /// Calculate mel from (Letter, Octave).
@@ -130,10 +200,36 @@ pub fn mel_from_step(step: Step) -> Mel {
     mel_from_hz(hz_from_step(step))
 }
 
+/// Round a step to the nearest whole step, always rounding halves upwards (i.e. computing
+/// `floor(step + 0.5)`) so that rounding is consistent for both positive and negative steps.
+#[inline]
+pub fn nearest_step(step: Step) -> Step {
+    (step + 0.5).floor()
+}
+
+/// Calculate the fractional octave number from hz.
+#[inline]
+pub fn octave_fraction_from_hz(hz: Hz) -> OctaveFraction {
+    octave_fraction_from_step(step_from_hz(hz))
+}
+
+/// Calculate the fractional octave number from the pitch `step`, where each whole number is the
+/// start (i.e. `C`) of that octave, e.g. `4.5` lies halfway between `C 4` and `C 5` (`F#4`).
+#[inline]
+pub fn octave_fraction_from_step(step: Step) -> OctaveFraction {
+    step / 12.0 - MIDI_OCTAVE_OFFSET as OctaveFraction
+}
+
 /// Calculate percentage from hz.
 #[inline]
 pub fn perc_from_hz(hz: Hz) -> Perc {
-    (hz - MIN_HZ) as Perc / (MAX_HZ - MIN_HZ) as Perc
+    perc_from_hz_with_range(hz, MIN_HZ, MAX_HZ)
+}
+
+/// Calculate the percentage of the range between `min` and `max` hz from hz.
+#[inline]
+pub fn perc_from_hz_with_range(hz: Hz, min: Hz, max: Hz) -> Perc {
+    (hz as Perc - min as Perc) / (max as Perc - min as Perc)
 }
 
 /// Calculate percentage from letter octave.
@@ -149,9 +245,12 @@ pub fn perc_from_mel(mel: Mel) -> Perc {
 }
 
 /// Calculate percentage from scaled percentage.
+///
+/// Percentages below zero (i.e. frequencies below the range) are scaled symmetrically about
+/// zero so that the conversion remains invertible for every pitch.
 #[inline]
 pub fn perc_from_scaled_perc(scaled: Perc, weight: Weight) -> Perc {
-    scaled.powf(weight as Perc)
+    signed_powf(scaled, weight as Perc)
 }
 
 /// Calculate frequency percentage from pitch as `step`.
@@ -160,6 +259,12 @@ pub fn perc_from_step(step: Step) -> Perc {
     perc_from_hz(hz_from_step(step))
 }
 
+/// Calculate the frequency ratio of an interval given in cents.
+#[inline]
+pub fn ratio_from_cents(cents: Cents) -> f64 {
+    2.0f64.powf(cents as f64 / 1200.0)
+}
+
 /// Calculate scaled percentage from hz.
 #[inline]
 pub fn scaled_perc_from_hz(hz: Hz, weight: Weight) -> Perc {
@@ -179,9 +284,18 @@ pub fn scaled_perc_from_mel(mel: Mel, weight: Weight) -> Perc {
 }
 
 /// Calculate scaled percentage from percentage.
+///
+/// Percentages below zero (i.e. frequencies below the range) are scaled symmetrically about
+/// zero so that the conversion remains invertible for every pitch.
 #[inline]
 pub fn scaled_perc_from_perc(perc: Perc, weight: Weight) -> Perc {
-    perc.powf(1.0 / weight as Perc)
+    signed_powf(perc, 1.0 / weight as Perc)
+}
+
+/// Raise the magnitude of `value` to the power `exponent`, preserving its sign.
+#[inline]
+fn signed_powf(value: Perc, exponent: Perc) -> Perc {
+    value.abs().powf(exponent).copysign(value)
 }
 
 /// Calculate scaled frequency percentage from pitch as `step`.
@@ -193,7 +307,13 @@ pub fn scaled_perc_from_step(step: Step, weight: Weight) -> Perc {
 /// Calculate the pitch `step` from frequency in hz.
 #[inline]
 pub fn step_from_hz(hz: Hz) -> Step {
-    (hz / PITCH_INDEX).log2() / TWELFTH_ROOT_OF_TWO.log2() + TUNING_PITCH_A4
+    step_from_hz_with_a4(hz, PITCH_INDEX)
+}
+
+/// Calculate the pitch `step` from hz where `A 4` is tuned to `a4` hz.
+#[inline]
+pub fn step_from_hz_with_a4(hz: Hz, a4: Hz) -> Step {
+    ((hz as f64 / a4 as f64).log2() * STEPS_PER_OCTAVE + TUNING_PITCH_A4 as f64) as Step
 }
 
 /// Calculate the pitch `step` from (Letter, Octave).
@@ -202,12 +322,25 @@ pub fn step_from_letter_octave(letter: Letter, octave: Octave) -> Step {
     (MIDI_OCTAVE_OFFSET + octave) as Step * 12.0 + letter.to_f32().unwrap()
 }
 
+/// Calculate the pitch `step` from a percentage of the human hearing range in log-frequency
+/// space.
+#[inline]
+pub fn step_from_log_perc(log_perc: Perc) -> Step {
+    step_from_hz(hz_from_log_perc(log_perc))
+}
+
 /// Calculate the pitch `step` from mel.
 #[inline]
 pub fn step_from_mel(mel: Mel) -> Step {
     step_from_hz(hz_from_mel(mel))
 }
 
+/// Calculate the pitch `step` from a fractional octave number.
+#[inline]
+pub fn step_from_octave_fraction(octave: OctaveFraction) -> Step {
+    (octave + MIDI_OCTAVE_OFFSET as OctaveFraction) * 12.0
+}
+
 /// Calculate the pitch `step` from frequency precentage.
 #[inline]
 pub fn step_from_perc(perc: Perc) -> Step {
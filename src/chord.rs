@@ -0,0 +1,339 @@
+//! Chords described by their root and quality, along with their inversions and slash chords.
+
+use num::ToPrimitive;
+use std::fmt;
+use super::{
+    calc,
+    Key,
+    Letter,
+    LetterOctave,
+    Octave,
+    Step,
+};
+use key::Mode;
+use utils::modulo;
+
+/// The quality of a chord, determining the intervals of its tones above the root.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum ChordQuality {
+    Major,
+    Minor,
+    Diminished,
+    Augmented,
+    Suspended2,
+    Suspended4,
+    Major6,
+    Minor6,
+    Dominant7,
+    Major7,
+    Minor7,
+    MinorMajor7,
+    HalfDiminished7,
+    Diminished7,
+    Dominant9,
+    Major9,
+    Minor9,
+}
+
+/// Every ChordQuality variant, from the triads to the ninth chords.
+pub const ALL_CHORD_QUALITIES: [ChordQuality; 17] = [
+    ChordQuality::Major,
+    ChordQuality::Minor,
+    ChordQuality::Diminished,
+    ChordQuality::Augmented,
+    ChordQuality::Suspended2,
+    ChordQuality::Suspended4,
+    ChordQuality::Major6,
+    ChordQuality::Minor6,
+    ChordQuality::Dominant7,
+    ChordQuality::Major7,
+    ChordQuality::Minor7,
+    ChordQuality::MinorMajor7,
+    ChordQuality::HalfDiminished7,
+    ChordQuality::Diminished7,
+    ChordQuality::Dominant9,
+    ChordQuality::Major9,
+    ChordQuality::Minor9,
+];
+
+impl ChordQuality {
+
+    /// The tones of the chord, each as its distance above the root in semitones paired with its
+    /// diatonic number (e.g. `(3, 3)` for a minor third), from the root upwards.
+    pub fn tones(&self) -> &'static [(u8, u8)] {
+        match *self {
+            ChordQuality::Major => &[(0, 1), (4, 3), (7, 5)],
+            ChordQuality::Minor => &[(0, 1), (3, 3), (7, 5)],
+            ChordQuality::Diminished => &[(0, 1), (3, 3), (6, 5)],
+            ChordQuality::Augmented => &[(0, 1), (4, 3), (8, 5)],
+            ChordQuality::Suspended2 => &[(0, 1), (2, 2), (7, 5)],
+            ChordQuality::Suspended4 => &[(0, 1), (5, 4), (7, 5)],
+            ChordQuality::Major6 => &[(0, 1), (4, 3), (7, 5), (9, 6)],
+            ChordQuality::Minor6 => &[(0, 1), (3, 3), (7, 5), (9, 6)],
+            ChordQuality::Dominant7 => &[(0, 1), (4, 3), (7, 5), (10, 7)],
+            ChordQuality::Major7 => &[(0, 1), (4, 3), (7, 5), (11, 7)],
+            ChordQuality::Minor7 => &[(0, 1), (3, 3), (7, 5), (10, 7)],
+            ChordQuality::MinorMajor7 => &[(0, 1), (3, 3), (7, 5), (11, 7)],
+            ChordQuality::HalfDiminished7 => &[(0, 1), (3, 3), (6, 5), (10, 7)],
+            ChordQuality::Diminished7 => &[(0, 1), (3, 3), (6, 5), (9, 7)],
+            ChordQuality::Dominant9 => &[(0, 1), (4, 3), (7, 5), (10, 7), (14, 9)],
+            ChordQuality::Major9 => &[(0, 1), (4, 3), (7, 5), (11, 7), (14, 9)],
+            ChordQuality::Minor9 => &[(0, 1), (3, 3), (7, 5), (10, 7), (14, 9)],
+        }
+    }
+
+    /// The distance of each tone above the root in semitones, from the root upwards.
+    pub fn intervals(&self) -> Vec<u8> {
+        self.tones().iter().map(|&(semitones, _)| semitones).collect()
+    }
+
+    /// The suffix following the root in a chord symbol (e.g. `m7` for `Am7`).
+    pub fn suffix(&self) -> &'static str {
+        match *self {
+            ChordQuality::Major => "",
+            ChordQuality::Minor => "m",
+            ChordQuality::Diminished => "dim",
+            ChordQuality::Augmented => "aug",
+            ChordQuality::Suspended2 => "sus2",
+            ChordQuality::Suspended4 => "sus4",
+            ChordQuality::Major6 => "6",
+            ChordQuality::Minor6 => "m6",
+            ChordQuality::Dominant7 => "7",
+            ChordQuality::Major7 => "maj7",
+            ChordQuality::Minor7 => "m7",
+            ChordQuality::MinorMajor7 => "m(maj7)",
+            ChordQuality::HalfDiminished7 => "m7b5",
+            ChordQuality::Diminished7 => "dim7",
+            ChordQuality::Dominant9 => "9",
+            ChordQuality::Major9 => "maj9",
+            ChordQuality::Minor9 => "m9",
+        }
+    }
+
+}
+
+/// A chord described by its root and quality, optionally over a bass note other than its root
+/// (an inversion, or a slash chord such as `C/D`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Chord {
+    /// The root of the chord.
+    pub root: Letter,
+    /// The quality of the chord.
+    pub quality: ChordQuality,
+    /// The bass note, when it is not the root.
+    pub bass: Option<Letter>,
+}
+
+impl Chord {
+
+    /// A chord in root position.
+    #[inline]
+    pub fn new(root: Letter, quality: ChordQuality) -> Chord {
+        Chord { root, quality, bass: None }
+    }
+
+    /// The lowest note of the chord, which is the root unless another bass note is given.
+    #[inline]
+    pub fn bass(&self) -> Letter {
+        self.bass.unwrap_or(self.root)
+    }
+
+    /// The pitch classes of the chord tones (with `C` as `0`), from the root upwards.
+    pub fn pitch_classes(&self) -> Vec<u8> {
+        let root = self.root.to_u8().unwrap();
+        self.quality.intervals().iter().map(|&i| modulo(root + i, 12)).collect()
+    }
+
+    /// The chord tones from the root upwards, each spelled as the diatonic number it represents
+    /// above the root where possible (e.g. `Eb` rather than `Dsh` as the third of `C` minor).
+    pub fn letters(&self) -> Vec<Letter> {
+        let root = self.root.to_u8().unwrap();
+        self.quality.tones().iter().map(|&(semitones, number)| {
+            let pitch_class = modulo(root + semitones, 12);
+            let natural = Letter::from_diatonic_index(self.root.diatonic_index() + number - 1);
+            Letter::spelled_from(natural, pitch_class)
+        }).collect()
+    }
+
+    /// The same chord over the given bass note, e.g. `C` over `E` for its first inversion or `C`
+    /// over `D` for a slash chord whose bass is not a chord tone.
+    #[inline]
+    pub fn with_bass(self, bass: Letter) -> Chord {
+        Chord { bass: if bass == self.root { None } else { Some(bass) }, ..self }
+    }
+
+    /// The given inversion of the chord, in which the chord tone of that index (counting the
+    /// root as `0`) is in the bass, e.g. `1` for first inversion. Indices wrap around the number
+    /// of chord tones.
+    pub fn inverted(self, inversion: usize) -> Chord {
+        let letters = self.letters();
+        self.with_bass(letters[inversion % letters.len()])
+    }
+
+    /// The inversion of the chord: `0` in root position, `1` with the second chord tone in the
+    /// bass and so on, or `None` if the bass note is not a chord tone at all.
+    pub fn inversion(&self) -> Option<usize> {
+        let bass = self.bass();
+        self.letters().iter().position(|&letter| letter == bass)
+    }
+
+    /// The inversion of the chord that the given voicing is in, judged by its lowest pitch, or
+    /// `None` if the voicing is empty or its lowest pitch is not a chord tone.
+    pub fn inversion_of<I, P>(&self, voicing: I) -> Option<usize>
+        where I: IntoIterator<Item=P>,
+              P: Into<Step>,
+    {
+        let lowest = voicing.into_iter()
+            .map(|p| p.into().step())
+            .min_by(|a, b| a.total_cmp(b))?;
+        let pitch_class = modulo(calc::nearest_step(lowest) as i32, 12) as u8;
+        self.pitch_classes().iter().position(|&pc| pc == pitch_class)
+    }
+
+    /// A close voicing of the chord with its bass note in the given octave and each remaining
+    /// chord tone at the nearest pitch above the previous one, from lowest to highest.
+    pub fn voicing(&self, octave: Octave) -> Vec<Step> {
+        let bass = LetterOctave(self.bass(), octave).step();
+        let mut pitches = vec![Step(bass)];
+        let mut previous = bass;
+        // Stack the chord tones above the bass, continuing from it when it is a chord tone.
+        let mut tones = self.pitch_classes();
+        let bass_pitch_class = self.bass().to_u8().unwrap();
+        if let Some(start) = tones.iter().position(|&pc| pc == bass_pitch_class) {
+            tones.rotate_left(start);
+            tones.remove(0);
+        }
+        for pitch_class in tones {
+            let above = (pitch_class as calc::Step - previous).rem_euclid(12.0);
+            previous += if above == 0.0 { 12.0 } else { above };
+            pitches.push(Step(previous));
+        }
+        pitches
+    }
+
+}
+
+impl fmt::Display for Chord {
+    /// Formats the chord symbol with ASCII accidentals (e.g. `Bbmaj7` or `C/E`), or with Unicode
+    /// accidentals when the alternate flag is given (e.g. `{:#}` gives `B♭maj7`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{:#}{}", self.root, self.quality.suffix())?;
+        } else {
+            write!(f, "{}{}", self.root, self.quality.suffix())?;
+        }
+        match self.bass {
+            Some(bass) if f.alternate() => write!(f, "/{:#}", bass),
+            Some(bass) => write!(f, "/{}", bass),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Identify the chords that best describe an unordered collection of pitches, such as a voicing
+/// or an arpeggio, ranked from the best fit down.
+///
+/// Each chord is paired with how well its tones match the pitch classes of the pitches, from
+/// `1.0` for an exact match down towards `0.0`, penalising both missing and additional tones.
+/// The lowest pitch is taken to be the bass, so that a chord in inversion is reported with its
+/// bass note. Where chords fit equally well, those in root position are ranked first, followed
+/// by the simpler qualities (e.g. `C6` before `Am7/C`). Only chords whose root is one of the
+/// pitches are considered, and an empty collection has no chords.
+pub fn detect_chords<I, P>(pitches: I) -> Vec<(Chord, f32)>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let steps: Vec<calc::Step> = pitches.into_iter().map(|p| p.into().step()).collect();
+    let pitch_class = |step: calc::Step| modulo(calc::nearest_step(step) as i32, 12) as u8;
+    let mut present = [false; 12];
+    for &step in &steps {
+        present[pitch_class(step) as usize] = true;
+    }
+    let bass = match steps.iter().cloned().min_by(|a, b| a.total_cmp(b)) {
+        Some(lowest) => pitch_class(lowest),
+        None => return Vec::new(),
+    };
+    let count = present.iter().filter(|&&p| p).count();
+    let mut candidates = Vec::new();
+    for root in (0..12).filter(|&root| present[root as usize]) {
+        for (rank, &quality) in ALL_CHORD_QUALITIES.iter().enumerate() {
+            let mut tones = [false; 12];
+            for &interval in quality.intervals().iter() {
+                tones[modulo(root + interval, 12) as usize] = true;
+            }
+            let matched = (0..12).filter(|&pc| tones[pc] && present[pc]).count();
+            let size = tones.iter().filter(|&&t| t).count();
+            let fit = matched as f32 / (size + count - matched) as f32;
+            let chord = Chord::new(Key::from_pitch_class(root, Mode::Major).tonic(), quality);
+            let chord = match chord.pitch_classes().iter().position(|&pc| pc == bass) {
+                Some(index) => chord.inverted(index),
+                None => chord.with_bass(Key::from_pitch_class(bass, Mode::Major).tonic()),
+            };
+            candidates.push((chord, fit, root != bass, rank));
+        }
+    }
+    candidates.sort_by(|a, b| {
+        b.1.total_cmp(&a.1).then(a.2.cmp(&b.2)).then(a.3.cmp(&b.3))
+    });
+    candidates.into_iter().map(|(chord, fit, _, _)| (chord, fit)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chord_tones() {
+        let c_minor = Chord::new(Letter::C, ChordQuality::Minor);
+        assert_eq!(c_minor.pitch_classes(), vec![0, 3, 7]);
+        assert_eq!(c_minor.letters(), vec![Letter::C, Letter::Eb, Letter::G]);
+        let d7 = Chord::new(Letter::D, ChordQuality::Dominant7);
+        assert_eq!(d7.letters(), vec![Letter::D, Letter::Fsh, Letter::A, Letter::C]);
+        assert_eq!(format!("{}", Chord::new(Letter::Bb, ChordQuality::Major7)), "Bbmaj7");
+        let b_flat = Chord::new(Letter::Bb, ChordQuality::Major7);
+        assert_eq!(format!("{:#}", b_flat), "B\u{266D}maj7");
+    }
+
+    #[test]
+    fn inversions_and_slash_chords() {
+        let c = Chord::new(Letter::C, ChordQuality::Major);
+        assert_eq!(c.inversion(), Some(0));
+        let first = c.inverted(1);
+        assert_eq!(first.bass(), Letter::E);
+        assert_eq!(first.inversion(), Some(1));
+        assert_eq!(format!("{}", first), "C/E");
+        assert_eq!(c.inverted(3), c);
+        let slash = c.with_bass(Letter::D);
+        assert_eq!(slash.inversion(), None);
+        assert_eq!(format!("{}", slash), "C/D");
+        assert_eq!(first.voicing(3), vec![Step(52.0), Step(55.0), Step(60.0)]);
+        assert_eq!(slash.voicing(3), vec![Step(50.0), Step(60.0), Step(64.0), Step(67.0)]);
+        let voicing = vec![Step(67.0), Step(60.0), Step(55.0), Step(64.0)];
+        assert_eq!(c.inversion_of(voicing), Some(2));
+        assert_eq!(c.inversion_of(vec![Step(61.0)]), None);
+    }
+
+    #[test]
+    fn detection() {
+        let steps = |steps: &[calc::Step]| steps.iter().cloned().map(Step).collect::<Vec<_>>();
+        // E3 C4 G4: C major in first inversion.
+        let ranked = detect_chords(steps(&[64.0, 52.0, 67.0, 60.0]));
+        assert_eq!(format!("{}", ranked[0].0), "C/E");
+        assert_eq!(ranked[0].0.inversion(), Some(1));
+        assert_eq!(ranked[0].1, 1.0);
+        // C E G A is both C6 and Am7 over C, with the root position reading preferred.
+        let ranked = detect_chords(steps(&[48.0, 64.0, 67.0, 69.0]));
+        assert_eq!(format!("{} {}", ranked[0].0, ranked[1].0), "C6 Am7/C");
+        // Extensions: Bb D F Ab C is a dominant ninth.
+        let ranked = detect_chords(steps(&[46.0, 62.0, 65.0, 68.0, 72.0]));
+        assert_eq!(format!("{}", ranked[0].0), "Bb9");
+        // A dyad best fits the triads containing it, but not exactly.
+        let ranked = detect_chords(steps(&[60.0, 64.0]));
+        assert_eq!(ranked[0].0, Chord::new(Letter::C, ChordQuality::Major));
+        assert!(ranked[0].1 < 1.0);
+        assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert!(detect_chords(Vec::<Step>::new()).is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+//! Mapping of frequencies onto the pitch class bins of a chromagram.
+
+use super::{
+    calc,
+    Error,
+    Hz,
+};
+
+/// Maps frequencies onto the bins of a chromagram.
+///
+/// Bin `0` is centred on the pitch class `C`, and the bins divide the octave evenly. The
+/// reference tuning is given as the frequency of `A 4`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct ChromaMap {
+    /// The number of bins per octave (commonly 12, 24 or 36).
+    pub bins_per_octave: u32,
+    /// The frequency of `A 4` in the reference tuning.
+    pub a4: calc::Hz,
+}
+
+impl Default for ChromaMap {
+    /// Twelve bins with `A 4` tuned to 440hz.
+    fn default() -> ChromaMap {
+        ChromaMap { bins_per_octave: 12, a4: 440.0 }
+    }
+}
+
+impl ChromaMap {
+
+    /// Construct a ChromaMap with the given resolution and reference tuning.
+    ///
+    /// Returns `Error::OutOfRange` if there are no bins or `a4` is not greater than zero, and
+    /// `Error::InvalidValue` if `a4` is not finite.
+    pub fn new(bins_per_octave: u32, a4: calc::Hz) -> Result<ChromaMap, Error> {
+        if !a4.is_finite() {
+            return Err(Error::InvalidValue);
+        }
+        if bins_per_octave == 0 || a4 <= 0.0 {
+            return Err(Error::OutOfRange);
+        }
+        Ok(ChromaMap { bins_per_octave, a4 })
+    }
+
+    /// The position of the frequency within the octave measured in bins, in the range
+    /// `[0, bins_per_octave)`.
+    ///
+    /// Returns `None` for frequencies that are not finite and greater than zero, or if the map
+    /// itself is invalid (see `ChromaMap::new`).
+    pub fn fractional_bin(&self, hz: Hz) -> Option<f64> {
+        let hz = hz.hz() as f64;
+        if !hz.is_finite() || hz <= 0.0 || self.bins_per_octave == 0 {
+            return None;
+        }
+        let c = self.a4 as f64 * 2.0f64.powf(-9.0 / 12.0);
+        let octaves = (hz / c).log2();
+        let bins = self.bins_per_octave as f64;
+        let position = (octaves - octaves.floor()) * bins % bins;
+        if position.is_finite() { Some(position) } else { None }
+    }
+
+    /// The index of the nearest bin to the given frequency, along with a weight describing how
+    /// close the frequency is to the centre of that bin.
+    ///
+    /// The weight is `1.0` at the bin centre and falls linearly to `0.0` at the bin edges.
+    /// Returns `None` wherever `fractional_bin` does.
+    pub fn bin(&self, hz: Hz) -> Option<(usize, f32)> {
+        self.fractional_bin(hz).map(|position| {
+            let nearest = position.round();
+            let weight = 1.0 - 2.0 * (position - nearest).abs();
+            let index = nearest as u32 % self.bins_per_octave;
+            (index as usize, weight as f32)
+        })
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{Letter, LetterOctave, Step};
+
+    #[test]
+    fn new() {
+        assert_eq!(ChromaMap::new(12, 440.0), Ok(ChromaMap::default()));
+        assert_eq!(ChromaMap::new(0, 440.0), Err(Error::OutOfRange));
+        assert_eq!(ChromaMap::new(12, 0.0), Err(Error::OutOfRange));
+        assert_eq!(ChromaMap::new(12, calc::Hz::NAN), Err(Error::InvalidValue));
+        // Maps made invalid through the public fields produce no bins rather than panicking.
+        let empty = ChromaMap { bins_per_octave: 0, a4: 440.0 };
+        assert_eq!(empty.bin(Hz(440.0)), None);
+        assert_eq!(ChromaMap { bins_per_octave: 12, a4: -1.0 }.bin(Hz(440.0)), None);
+        assert_eq!(ChromaMap::default().bin(Hz(0.0)), None);
+        assert_eq!(ChromaMap::default().bin(Hz(calc::Hz::INFINITY)), None);
+    }
+
+    #[test]
+    fn resolutions() {
+        let a4 = LetterOctave(Letter::A, 4).to_hz();
+        for &(bins, a) in &[(12, 9), (24, 18), (36, 27)] {
+            let map = ChromaMap::new(bins, 440.0).unwrap();
+            assert_eq!(map.bin(a4).map(|(index, _)| index), Some(a));
+            assert_eq!(map.bin(Hz(220.0)).map(|(index, _)| index), Some(a));
+            assert_eq!(map.bin(LetterOctave(Letter::C, 2).to_hz()).map(|(i, _)| i), Some(0));
+        }
+        // A quarter tone above `C` lies on the second bin of a 24 bin map.
+        let map = ChromaMap::new(24, 440.0).unwrap();
+        assert_eq!(map.bin(Step(60.5).to_hz()).map(|(index, _)| index), Some(1));
+    }
+
+    #[test]
+    fn wrap_at_top_bin() {
+        // Just beneath `C` rounds up past the top bin, wrapping around to bin `0`.
+        let map = ChromaMap::default();
+        let (index, weight) = map.bin(Step(59.9).to_hz()).unwrap();
+        assert_eq!(index, 0);
+        assert!((weight - 0.8).abs() < 0.001);
+        let position = map.fractional_bin(Step(59.9).to_hz()).unwrap();
+        assert!((position - 11.9).abs() < 0.001);
+    }
+
+    #[test]
+    fn weights() {
+        let map = ChromaMap::default();
+        let (_, centre) = map.bin(Hz(440.0)).unwrap();
+        assert!((centre - 1.0).abs() < 0.001);
+        let (index, quarter) = map.bin(Step(69.25).to_hz()).unwrap();
+        assert_eq!(index, 9);
+        assert!((quarter - 0.5).abs() < 0.001);
+        let (_, edge) = map.bin(Step(69.4999).to_hz()).unwrap();
+        assert!(edge < 0.01);
+    }
+
+    #[test]
+    fn reference_tuning() {
+        // With `A 4` at 415hz, 415hz is an `A` and 440hz lies on the `A♯` bin.
+        let baroque = ChromaMap::new(12, 415.0).unwrap();
+        let (index, weight) = baroque.bin(Hz(415.0)).unwrap();
+        assert_eq!(index, 9);
+        assert!((weight - 1.0).abs() < 0.001);
+        assert_eq!(baroque.bin(Hz(440.0)).map(|(index, _)| index), Some(10));
+    }
+}
@@ -1,6 +1,8 @@
 use std::convert::From;
 use calc::Hz as CalcHz;
-use super::{LetterOctave, Step, Mel, Perc, ScaledPerc, Hz};
+use calc;
+use super::{AnyPitch, Letter, LetterOctave, Octave, Step, Mel, MpeNote, OctaveFraction, Perc};
+use super::{LogPerc, MidiNote, QuarterTone, ScaledPerc, Hz};
 
 /// Implement a single From<T> using the passed expression
 macro_rules! impl_from {
@@ -32,18 +34,67 @@ macro_rules! impl_all_pairs {
     }
 }
 
-/// Implement From<T> for all fully defined pitch types
+// Implement From<T> for all fully defined pitch types
 impl_all_pairs!(
     to_hz => Hz,
     to_mel => Mel,
+    to_octave_fraction => OctaveFraction,
     to_letter_octave => LetterOctave,
     to_scaled_perc => ScaledPerc,
     to_perc => Perc,
+    to_log_perc => LogPerc,
     to_step => Step
     );
 
-/// Additionally implement From for calc::Hz = f32
+// Additionally implement From for the unit values of each representation in both directions
 impl_from!(CalcHz, Hz, other => Hz(other));
+impl_from!(calc::Mel, Mel, other => Mel(other));
+impl_from!(calc::OctaveFraction, OctaveFraction, other => OctaveFraction(other));
+impl_from!(calc::Perc, Perc, other => Perc(other));
+impl_from!(calc::Step, Step, other => Step(other));
+impl_from!(Hz, CalcHz, hz);
+impl_from!(Mel, calc::Mel, mel);
+impl_from!(OctaveFraction, calc::OctaveFraction, octave_fraction);
+impl_from!(Perc, calc::Perc, perc);
+impl_from!(Step, calc::Step, step);
+
+// A LetterOctave may be built from its (Letter, Octave) pair
+impl_from!((Letter, Octave), LetterOctave, other => LetterOctave(other.0, other.1));
+impl_from!(LetterOctave, (Letter, Octave), letter_octave);
+
+// AnyPitch wraps each of the representations it may be given in
+impl_from!(Hz, AnyPitch, other => AnyPitch::Hz(other));
+impl_from!(LetterOctave, AnyPitch, other => AnyPitch::LetterOctave(other));
+impl_from!(LogPerc, AnyPitch, other => AnyPitch::LogPerc(other));
+impl_from!(Mel, AnyPitch, other => AnyPitch::Mel(other));
+impl_from!(OctaveFraction, AnyPitch, other => AnyPitch::OctaveFraction(other));
+impl_from!(Perc, AnyPitch, other => AnyPitch::Perc(other));
+impl_from!(QuarterTone, AnyPitch, other => AnyPitch::QuarterTone(other));
+impl_from!(ScaledPerc, AnyPitch, other => AnyPitch::ScaledPerc(other));
+impl_from!(Step, AnyPitch, other => AnyPitch::Step(other));
+impl_from!(AnyPitch, Step, to_step);
+impl_from!(AnyPitch, Hz, to_hz);
+impl_from!(AnyPitch, LetterOctave, to_letter_octave);
+impl_from!(AnyPitch, Mel, to_mel);
+impl_from!(AnyPitch, OctaveFraction, to_octave_fraction);
+impl_from!(AnyPitch, Perc, to_perc);
+impl_from!(AnyPitch, LogPerc, to_log_perc);
+impl_from!(AnyPitch, ScaledPerc, to_scaled_perc);
+
+// QuarterTone converts via Step, rounding to the nearest quarter tone
+impl_from!(QuarterTone, Step, to_step);
+impl_from!(QuarterTone, Hz, to_hz);
+impl_from!(Step, QuarterTone, other => QuarterTone::from_step(other));
+
+// MidiNote converts to the pitch of the note
+impl_from!(MidiNote, Step, to_step);
+impl_from!(MidiNote, Hz, to_hz);
+impl_from!(MidiNote, LetterOctave, to_letter_octave);
+impl_from!(MidiNote, u8, number);
+
+// MpeNote converts to its sounding pitch
+impl_from!(MpeNote, Step, to_step);
+impl_from!(MpeNote, Hz, to_hz);
 
 #[cfg(test)]
 mod tests {
@@ -65,4 +116,21 @@ mod tests {
         let lo = LetterOctave(Letter::A, 4);
         assert!(into_test_gen(lo) == Hz(440.0));
     }
+
+    #[test]
+    fn octave_fraction() {
+        let lo = LetterOctave(Letter::A, 4);
+        assert!(OctaveFraction::from(lo) == OctaveFraction(4.75));
+        assert!(LetterOctave::from(OctaveFraction(4.5)) == LetterOctave(Letter::Fsh, 4));
+    }
+
+    #[test]
+    fn unit_values() {
+        assert!(Step::from(69.0) == Step(69.0));
+        assert!(f32::from(Step(69.0)) == 69.0);
+        assert!(Perc::from(0.5) == Perc(0.5));
+        assert!(LetterOctave::from((Letter::C, 4)) == LetterOctave(Letter::C, 4));
+        let pitch: AnyPitch = Hz(440.0).into();
+        assert!(Step::from(pitch) == Step(69.0));
+    }
 }
@@ -0,0 +1,151 @@
+//! Response curves mapping control positions, such as knobs and sliders, onto frequencies.
+
+use super::{
+    calc,
+    hz_from_perc_with_range,
+    perc_from_hz_with_range,
+    perc_from_scaled_perc,
+    scaled_perc_from_perc,
+    MAX_HZ,
+    MIN_HZ,
+};
+
+/// A response curve mapping a control position (e.g. a knob or slider in the range `[0, 1]`)
+/// onto a frequency range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Curve {
+    /// Equal distances along the control cover equal distances in hz.
+    Linear,
+    /// Equal distances along the control cover equal frequency ratios (i.e. equal musical
+    /// intervals). The lower end of the range must be greater than zero.
+    Logarithmic,
+    /// The position raised to the given exponent, like a ScaledPerc with the same weight. An
+    /// exponent greater than `1` gives more of the control to the low end of the range.
+    Exponential(calc::Weight),
+    /// An s-shaped curve that is flatter at both ends of the control and steeper in the middle.
+    /// The knee controls the steepness: `1` is linear and larger values flatten the ends further.
+    SCurve(f32),
+}
+
+impl Curve {
+
+    /// Map a control position onto the linear percentage of the frequency range.
+    ///
+    /// `Logarithmic` is passed through unchanged here, as its shape depends on the range itself
+    /// and is handled by `hz_with_range` and `position_with_range`.
+    fn perc_from_position(&self, position: calc::Perc) -> calc::Perc {
+        match *self {
+            Curve::Linear | Curve::Logarithmic => position,
+            Curve::Exponential(exponent) => perc_from_scaled_perc(position, exponent),
+            Curve::SCurve(knee) => {
+                let knee = knee as calc::Perc;
+                let (a, b) = (position.powf(knee), (1.0 - position).powf(knee));
+                a / (a + b)
+            },
+        }
+    }
+
+    /// The inverse of `perc_from_position`.
+    fn position_from_perc(&self, perc: calc::Perc) -> calc::Perc {
+        match *self {
+            Curve::Linear | Curve::Logarithmic => perc,
+            Curve::Exponential(exponent) => scaled_perc_from_perc(perc, exponent),
+            Curve::SCurve(knee) => {
+                let ratio = (perc / (1.0 - perc)).powf(1.0 / knee as calc::Perc);
+                if ratio.is_infinite() { 1.0 } else { ratio / (1.0 + ratio) }
+            },
+        }
+    }
+
+    /// The frequency in hz at the given control position within the range between `min` and
+    /// `max` hz.
+    pub fn hz_with_range(&self, position: calc::Perc, min: calc::Hz, max: calc::Hz) -> calc::Hz {
+        match *self {
+            Curve::Logarithmic => {
+                let (min, max) = (min as f64, max as f64);
+                (min * (max / min).powf(position)) as calc::Hz
+            },
+            _ => hz_from_perc_with_range(self.perc_from_position(position), min, max),
+        }
+    }
+
+    /// The frequency in hz at the given control position within the human hearing range.
+    #[inline]
+    pub fn hz(&self, position: calc::Perc) -> calc::Hz {
+        self.hz_with_range(position, MIN_HZ, MAX_HZ)
+    }
+
+    /// The control position at which the given frequency lies within the range between `min` and
+    /// `max` hz.
+    pub fn position_with_range(&self, hz: calc::Hz, min: calc::Hz, max: calc::Hz) -> calc::Perc {
+        match *self {
+            Curve::Logarithmic => {
+                let (hz, min, max) = (hz as f64, min as f64, max as f64);
+                (hz / min).ln() / (max / min).ln()
+            },
+            _ => self.position_from_perc(perc_from_hz_with_range(hz, min, max)),
+        }
+    }
+
+    /// The control position at which the given frequency lies within the human hearing range.
+    #[inline]
+    pub fn position(&self, hz: calc::Hz) -> calc::Perc {
+        self.position_with_range(hz, MIN_HZ, MAX_HZ)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVES: [Curve; 5] = [
+        Curve::Linear,
+        Curve::Logarithmic,
+        Curve::Exponential(2.0),
+        Curve::SCurve(1.0),
+        Curve::SCurve(3.0),
+    ];
+
+    #[test]
+    fn endpoints() {
+        let (min, max) = (27.5, 4_186.0);
+        for curve in CURVES.iter() {
+            assert!((curve.hz_with_range(0.0, min, max) - min).abs() < 0.01, "{:?}", curve);
+            assert!((curve.hz_with_range(1.0, min, max) - max).abs() < 0.1, "{:?}", curve);
+            assert!((curve.hz(0.0) - MIN_HZ).abs() < 0.01, "{:?}", curve);
+            assert!((curve.hz(1.0) - MAX_HZ).abs() < 1.0, "{:?}", curve);
+            assert!(curve.position_with_range(min, min, max).abs() < 0.0001, "{:?}", curve);
+            assert!((curve.position_with_range(max, min, max) - 1.0).abs() < 0.0001, "{:?}", curve);
+        }
+    }
+
+    #[test]
+    fn monotonic() {
+        for curve in CURVES.iter() {
+            let mut previous = curve.hz(0.0);
+            for i in 1..=100 {
+                let position = i as calc::Perc / 100.0;
+                let hz = curve.hz(position);
+                assert!(hz > previous, "{:?} at {}", curve, position);
+                previous = hz;
+                let round_trip = curve.position(hz);
+                assert!((round_trip - position).abs() < 0.001, "{:?} at {}", curve, position);
+            }
+        }
+    }
+
+    #[test]
+    fn shapes() {
+        // The midpoint of a logarithmic curve is the geometric mean of the range.
+        assert!((Curve::Logarithmic.hz_with_range(0.5, 100.0, 10_000.0) - 1_000.0).abs() < 0.01);
+        assert!((Curve::Linear.hz_with_range(0.5, 100.0, 10_000.0) - 5_050.0).abs() < 0.01);
+        // An S-curve with a knee of `1` is linear, and every S-curve passes through the middle.
+        assert!((Curve::SCurve(1.0).hz(0.3) - Curve::Linear.hz(0.3)).abs() < 0.01);
+        assert!((Curve::SCurve(3.0).hz(0.5) - Curve::Linear.hz(0.5)).abs() < 0.01);
+        assert!(Curve::SCurve(3.0).hz(0.1) < Curve::Linear.hz(0.1));
+        // An exponent above `1` gives more of the control to the low end.
+        assert!(Curve::Exponential(2.0).hz(0.5) < Curve::Linear.hz(0.5));
+    }
+}
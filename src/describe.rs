@@ -0,0 +1,96 @@
+//! A summary of a pitch in each of the common representations at once, e.g. for debugging
+//! conversions or for a "pitch info" panel.
+
+use std::fmt;
+use super::{
+    calc,
+    FormatOptions,
+    FormatWith,
+    Hz,
+    LetterOctave,
+    Mel,
+    Perc,
+    Pitch,
+    Step,
+    format_note,
+    nearest_step,
+};
+
+/// A pitch in each of the common representations. See `Pitch::describe`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct PitchDescription {
+    /// The pitch in steps.
+    pub step: Step,
+    /// The frequency of the pitch.
+    pub hz: Hz,
+    /// The closest named pitch.
+    pub letter_octave: LetterOctave,
+    /// The offset of the pitch from `letter_octave` in cents.
+    pub cents: calc::Cents,
+    /// The pitch in mels.
+    pub mel: Mel,
+    /// The pitch as a percentage of the human hearing range.
+    pub perc: Perc,
+}
+
+impl PitchDescription {
+
+    /// Describe the given pitch, converting it to each representation with its own exact
+    /// conversions where available.
+    pub fn new<P: Pitch>(pitch: &P) -> PitchDescription {
+        let step = pitch.to_step();
+        let nearest = nearest_step(step.step());
+        PitchDescription {
+            step,
+            hz: pitch.to_hz(),
+            letter_octave: Step(nearest).to_letter_octave(),
+            cents: (step.step() - nearest) * 100.0,
+            mel: pitch.to_mel(),
+            perc: pitch.to_perc(),
+        }
+    }
+
+}
+
+impl fmt::Display for PitchDescription {
+    /// Formats each representation with its unit, separated by commas (e.g. `{:.1}` gives
+    /// `69.0 st, 440.0 Hz, A4 +0.0c, 549.6 mel, 2.1 %`). Any precision is applied to each number,
+    /// and the alternate flag gives Unicode accidentals.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let options = FormatOptions {
+            precision: f.precision(),
+            units: true,
+            unicode: f.alternate(),
+            cents: true,
+        };
+        write!(f, "{}, {}, {}, {}, {}",
+               self.step.format_with(&options),
+               self.hz.format_with(&options),
+               format_note(self.step, &options),
+               self.mel.format_with(&options),
+               self.perc.format_with(&options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Letter;
+
+    #[test]
+    fn describe() {
+        let a4 = Hz(440.0).describe();
+        assert_eq!(a4.step, Step(69.0));
+        assert_eq!(a4.hz, Hz(440.0));
+        assert_eq!(a4.letter_octave, LetterOctave(Letter::A, 4));
+        assert_eq!(a4.cents, 0.0);
+        assert_eq!(a4.mel, Hz(440.0).to_mel());
+        assert_eq!(a4, LetterOctave(Letter::A, 4).describe());
+        let sharp = Step(61.25).describe();
+        assert_eq!(sharp.letter_octave, LetterOctave(Letter::Csh, 4));
+        assert!((sharp.cents - 25.0).abs() < 0.001);
+        assert_eq!(format!("{:.1}", a4), "69.0 st, 440.0 Hz, A4 +0.0c, 549.6 mel, 2.1 %");
+        assert!(format!("{:#.0}", sharp).contains("C\u{266F}4 +25c"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Monophonic pitch detection from buffers of audio samples, using the YIN algorithm.
+//!
+//! Enabled by the `detection` feature.
+//!
+//! de Cheveigné, A. and Kawahara, H. (2002) "YIN, a fundamental frequency estimator for speech
+//! and music", The Journal of the Acoustical Society of America 111(4).
+
+use super::Hz;
+
+/// The settings of the YIN pitch detector.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Yin {
+    /// The threshold below which a dip in the normalised difference function is accepted as the
+    /// period. Lower values reject more noisy frames. `0.1` to `0.2` is typical.
+    pub threshold: f32,
+    /// The lowest detectable frequency. Detecting lower frequencies requires longer buffers, as
+    /// at least two periods must fit within the buffer.
+    pub min_hz: f32,
+    /// The highest detectable frequency.
+    pub max_hz: f32,
+}
+
+impl Default for Yin {
+    /// A threshold of `0.15` covering `40` to `4000` hz, from the lowest bass notes to the top of
+    /// the piano.
+    fn default() -> Yin {
+        Yin { threshold: 0.15, min_hz: 40.0, max_hz: 4_000.0 }
+    }
+}
+
+/// Detect the pitch of the given buffer of samples using the default `Yin` settings.
+///
+/// See `Yin::detect`.
+#[inline]
+pub fn detect_pitch(buffer: &[f32], sample_rate: f64) -> Option<(Hz, f32)> {
+    Yin::default().detect(buffer, sample_rate)
+}
+
+impl Yin {
+
+    /// Detect the fundamental frequency of the given buffer of mono samples recorded at the
+    /// given sample rate, along with a confidence from `0` to `1`.
+    ///
+    /// Returns `None` if no periodicity is found within the detectable range (e.g. for silence,
+    /// noise, or a buffer too short to contain two periods of `min_hz`).
+    pub fn detect(&self, buffer: &[f32], sample_rate: f64) -> Option<(Hz, f32)> {
+        let window = buffer.len() / 2;
+        let min_tau = ((sample_rate / self.max_hz as f64).floor() as usize).max(2);
+        let max_tau = ((sample_rate / self.min_hz as f64).ceil() as usize).min(window);
+        if min_tau + 1 >= max_tau {
+            return None;
+        }
+
+        // The difference function for each lag, and its cumulative mean normalisation.
+        let mut normalised = vec![1.0f64; max_tau + 1];
+        let mut running_sum = 0.0;
+        for tau in 1..max_tau + 1 {
+            let difference: f64 = (0..window)
+                .map(|i| {
+                    let delta = buffer[i] as f64 - buffer[i + tau] as f64;
+                    delta * delta
+                })
+                .sum();
+            running_sum += difference;
+            normalised[tau] = if running_sum > 0.0 {
+                difference * tau as f64 / running_sum
+            } else {
+                1.0
+            };
+        }
+
+        // The first dip below the threshold, followed down to its minimum.
+        let threshold = self.threshold as f64;
+        let mut tau = (min_tau..max_tau).find(|&tau| normalised[tau] < threshold)?;
+        while tau + 1 < max_tau && normalised[tau + 1] < normalised[tau] {
+            tau += 1;
+        }
+
+        // Refine the period between samples by fitting a parabola through the minimum.
+        let (a, b, c) = (normalised[tau - 1], normalised[tau], normalised[tau + 1]);
+        let denominator = a - 2.0 * b + c;
+        let offset = if denominator.abs() > 1e-12 { 0.5 * (a - c) / denominator } else { 0.0 };
+        let period = tau as f64 + offset.clamp(-1.0, 1.0);
+        let confidence = (1.0 - b).clamp(0.0, 1.0);
+        Some((Hz((sample_rate / period) as f32), confidence as f32))
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn sine(hz: f64, sample_rate: f64, len: usize) -> Vec<f32> {
+        (0..len).map(|i| (2.0 * PI * hz * i as f64 / sample_rate).sin() as f32).collect()
+    }
+
+    #[test]
+    fn detects_sines() {
+        for &hz in &[82.41, 220.0, 440.0, 1046.5] {
+            let buffer = sine(hz, 44_100.0, 4_096);
+            let (detected, confidence) = detect_pitch(&buffer, 44_100.0).unwrap();
+            assert!((detected.hz() as f64 - hz).abs() < hz * 0.001, "{} != {}", detected, hz);
+            assert!(confidence > 0.9);
+        }
+    }
+
+    #[test]
+    fn detects_harmonic_tones() {
+        // A sawtooth-like tone whose second harmonic is as loud as the fundamental.
+        let buffer: Vec<f32> = (0..4_096)
+            .map(|i| {
+                let t = i as f64 / 48_000.0;
+                ((2.0 * PI * 196.0 * t).sin() + (2.0 * PI * 392.0 * t).sin()) as f32
+            })
+            .collect();
+        let (detected, _) = detect_pitch(&buffer, 48_000.0).unwrap();
+        assert_eq!(detected.letter_octave(), (::Letter::G, 3));
+    }
+
+    #[test]
+    fn rejects_silence_and_short_buffers() {
+        assert_eq!(detect_pitch(&[0.0; 2_048], 44_100.0), None);
+        assert_eq!(detect_pitch(&sine(440.0, 44_100.0, 16), 44_100.0), None);
+        assert_eq!(detect_pitch(&[], 44_100.0), None);
+    }
+}
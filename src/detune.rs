@@ -0,0 +1,130 @@
+//! Detuned stacks of voices, as used by unison (e.g. "supersaw") oscillators and by chorus and
+//! stereo widening effects.
+
+use super::{
+    calc,
+    Hz,
+};
+
+/// How the voices of a unison stack are distributed across its spread.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Distribution {
+    /// Voices are spaced evenly across the spread, from the flattest to the sharpest.
+    Linear,
+    /// Voices are placed in mirrored pairs whose detune doubles with each pair outwards, so that
+    /// most voices lie close to the centre and only the outermost pair reaches the full spread.
+    Exponential,
+    /// Voices are placed in mirrored pairs at equal steps outwards from the centre, with a single
+    /// undetuned voice at the centre when the count is odd. Unlike `Linear`, an even count leaves
+    /// a gap of a single step either side of the centre rather than half a step.
+    Symmetric,
+}
+
+/// The detune of each voice of a unison stack in cents, from lowest to highest.
+///
+/// `spread` is the distance in cents between the flattest and sharpest voices, which lie at
+/// `-spread / 2` and `spread / 2`. A single voice is never detuned.
+pub fn unison_offsets(voices: usize, spread: calc::Cents, distribution: Distribution)
+    -> Vec<calc::Cents>
+{
+    if voices < 2 {
+        return vec![0.0; voices];
+    }
+    let half = spread / 2.0;
+    let pairs = voices / 2;
+    let mut offsets: Vec<calc::Cents> = match distribution {
+        Distribution::Linear => (0..voices)
+            .map(|i| -half + spread * i as calc::Cents / (voices - 1) as calc::Cents)
+            .collect(),
+        Distribution::Exponential | Distribution::Symmetric => {
+            let outward = (1..pairs + 1).map(|k| {
+                let (k, pairs) = (k as calc::Cents, pairs as calc::Cents);
+                match distribution {
+                    Distribution::Exponential => half * (k - pairs).exp2(),
+                    _ => half * k / pairs,
+                }
+            });
+            let mut offsets = Vec::with_capacity(voices);
+            for offset in outward {
+                offsets.push(-offset);
+                offsets.push(offset);
+            }
+            if voices % 2 == 1 {
+                offsets.push(0.0);
+            }
+            offsets
+        },
+    };
+    offsets.sort_by(|a, b| a.total_cmp(b));
+    offsets
+}
+
+/// The frequencies of a unison stack of the given number of voices around the given pitch,
+/// from lowest to highest. See `unison_offsets` for the detune of each voice.
+pub fn unison<P>(center: P, voices: usize, spread: calc::Cents, distribution: Distribution)
+    -> Vec<Hz>
+    where P: Into<Hz>,
+{
+    let center = center.into().hz() as f64;
+    unison_offsets(voices, spread, distribution)
+        .into_iter()
+        .map(|cents| detuned(center, cents))
+        .collect()
+}
+
+/// A pair of voices detuned either side of the given pitch for the left and right channels, the
+/// left flat and the right sharp by half of the given width in cents.
+///
+/// The pair is centred on the pitch in log-frequency space, so that their mean frequency (which
+/// is heard as the pitch of two closely detuned voices) lies slightly above it. See
+/// `stereo_pair_constant_center` to avoid this.
+pub fn stereo_pair<P: Into<Hz>>(center: P, width: calc::Cents) -> (Hz, Hz) {
+    let center = center.into().hz() as f64;
+    (detuned(center, -width / 2.0), detuned(center, width / 2.0))
+}
+
+/// As `stereo_pair`, but with both voices lowered together so that their mean frequency, and
+/// so the perceived pitch of the pair, remains at the given pitch however wide the pair is.
+pub fn stereo_pair_constant_center<P: Into<Hz>>(center: P, width: calc::Cents) -> (Hz, Hz) {
+    let center = center.into().hz() as f64;
+    let ratio = (width as f64 / 2_400.0).exp2();
+    let mean = (ratio + 1.0 / ratio) / 2.0;
+    (Hz((center / ratio / mean) as calc::Hz), Hz((center * ratio / mean) as calc::Hz))
+}
+
+/// The given frequency detuned by the given number of cents.
+#[inline]
+fn detuned(hz: f64, cents: calc::Cents) -> Hz {
+    Hz((hz * (cents as f64 / 1_200.0).exp2()) as calc::Hz)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distributions() {
+        assert_eq!(unison_offsets(0, 50.0, Distribution::Linear), Vec::<calc::Cents>::new());
+        assert_eq!(unison_offsets(1, 50.0, Distribution::Symmetric), vec![0.0]);
+        assert_eq!(unison_offsets(4, 60.0, Distribution::Linear), vec![-30.0, -10.0, 10.0, 30.0]);
+        assert_eq!(unison_offsets(4, 60.0, Distribution::Symmetric),
+                   vec![-30.0, -15.0, 15.0, 30.0]);
+        assert_eq!(unison_offsets(5, 60.0, Distribution::Symmetric),
+                   unison_offsets(5, 60.0, Distribution::Linear));
+        assert_eq!(unison_offsets(7, 80.0, Distribution::Exponential),
+                   vec![-40.0, -20.0, -10.0, 0.0, 10.0, 20.0, 40.0]);
+        let stack = unison(Hz(440.0), 3, 2_400.0, Distribution::Linear);
+        assert_eq!(stack, vec![Hz(220.0), Hz(440.0), Hz(880.0)]);
+    }
+
+    #[test]
+    fn stereo_pairs() {
+        let (left, right) = stereo_pair(Hz(440.0), 2_400.0);
+        assert_eq!((left, right), (Hz(220.0), Hz(880.0)));
+        let (left, right) = stereo_pair_constant_center(Hz(440.0), 20.0);
+        assert!(((left.hz() + right.hz()) / 2.0 - 440.0).abs() < 0.001);
+        assert!(((right.hz() / left.hz()).log2() * 1_200.0 - 20.0).abs() < 0.01);
+        assert_eq!(stereo_pair_constant_center(Hz(440.0), 0.0), (Hz(440.0), Hz(440.0)));
+    }
+}
@@ -0,0 +1,137 @@
+//! The error type shared by all fallible operations within the crate.
+
+use std::error;
+use std::fmt;
+
+/// An error produced by a fallible operation within the crate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Text could not be parsed as the expected kind of value.
+    ParseError(ParseError),
+    /// A value lay outside of the range supported by the operation (e.g. an interval number of
+    /// zero).
+    OutOfRange,
+    /// A value was not valid for the operation (e.g. a major fifth).
+    InvalidValue,
+    /// A tuning message was malformed.
+    TuningError(TuningError),
+}
+
+/// The reason that text could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// A pitch name did not begin with a letter between `A` and `G`.
+    InvalidLetter,
+    /// The accidental following the letter was not recognised.
+    InvalidAccidental,
+    /// The octave following the accidental was missing or not an integer.
+    InvalidOctave,
+    /// A number was missing or malformed.
+    InvalidNumber,
+    /// The text was not of the expected form (e.g. `P5` for an interval).
+    InvalidFormat,
+}
+
+/// The reason that a tuning message was malformed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TuningError {
+    /// The message did not begin with the expected header.
+    InvalidHeader,
+    /// The message was shorter or longer than its contents require.
+    InvalidLength,
+    /// The checksum of the message did not match its contents.
+    InvalidChecksum,
+    /// A data byte had its high bit set.
+    InvalidData,
+}
+
+impl From<ParseError> for Error {
+    #[inline]
+    fn from(err: ParseError) -> Error {
+        Error::ParseError(err)
+    }
+}
+
+impl From<TuningError> for Error {
+    #[inline]
+    fn from(err: TuningError) -> Error {
+        Error::TuningError(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::ParseError(ref err) => write!(f, "parse error: {}", err),
+            Error::OutOfRange => f.write_str("value out of range"),
+            Error::InvalidValue => f.write_str("invalid value"),
+            Error::TuningError(ref err) => write!(f, "tuning error: {}", err),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match *self {
+            ParseError::InvalidLetter => "expected a letter between A and G",
+            ParseError::InvalidAccidental => "unrecognised accidental",
+            ParseError::InvalidOctave => "expected an integer octave",
+            ParseError::InvalidNumber => "expected a number",
+            ParseError::InvalidFormat => "unrecognised format",
+        };
+        f.write_str(description)
+    }
+}
+
+impl fmt::Display for TuningError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match *self {
+            TuningError::InvalidHeader => "not a message of the expected type",
+            TuningError::InvalidLength => "the message length does not match its contents",
+            TuningError::InvalidChecksum => "the checksum does not match the message contents",
+            TuningError::InvalidData => "a data byte was greater than 0x7F",
+        };
+        f.write_str(description)
+    }
+}
+
+impl error::Error for Error {}
+impl error::Error for ParseError {}
+impl error::Error for TuningError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Error::OutOfRange.to_string(), "value out of range");
+        assert_eq!(Error::InvalidValue.to_string(), "invalid value");
+        assert_eq!(Error::ParseError(ParseError::InvalidOctave).to_string(),
+                   "parse error: expected an integer octave");
+        assert_eq!(Error::TuningError(TuningError::InvalidChecksum).to_string(),
+                   "tuning error: the checksum does not match the message contents");
+        assert_eq!(ParseError::InvalidLetter.to_string(), "expected a letter between A and G");
+        assert_eq!(TuningError::InvalidData.to_string(), "a data byte was greater than 0x7F");
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Error::from(ParseError::InvalidNumber),
+                   Error::ParseError(ParseError::InvalidNumber));
+        assert_eq!(Error::from(TuningError::InvalidHeader),
+                   Error::TuningError(TuningError::InvalidHeader));
+        let parse = || -> Result<(), Error> { Err(ParseError::InvalidFormat)? };
+        assert_eq!(parse(), Err(Error::ParseError(ParseError::InvalidFormat)));
+    }
+
+    #[test]
+    fn std_error() {
+        let boxed: Box<dyn error::Error> = Box::new(Error::from(ParseError::InvalidAccidental));
+        assert_eq!(boxed.to_string(), "parse error: unrecognised accidental");
+        assert!(boxed.source().is_none());
+        let errors: [&dyn error::Error; 2] =
+            [&ParseError::InvalidLetter, &TuningError::InvalidLength];
+        assert!(errors.iter().all(|err| !err.to_string().is_empty()));
+    }
+}
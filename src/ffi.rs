@@ -0,0 +1,145 @@
+//! `extern "C"` wrappers around the core conversion functions, allowing the same pitch math to be
+//! used from C, C++ or any other language with a C FFI.
+//!
+//! Enabled by the `ffi` feature. Build a shared library exposing them with:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! Letters are passed as pitch classes from `0` (`C`) to `11` (`B`), spelled with sharps when
+//! returned. Every function is prefixed with `pitch_calc_`.
+
+use num::{FromPrimitive, ToPrimitive};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use super::{
+    calc,
+    Letter,
+    Octave,
+    parse_pitch,
+};
+
+/// Wrap each of the given unary calc functions with an `extern "C"` function of the given name.
+macro_rules! extern_unary {
+    ($($name:ident => $function:ident($In:ty) -> $Out:ty),* $(,)*) => {
+        $(
+            #[no_mangle]
+            pub extern "C" fn $name(value: $In) -> $Out {
+                calc::$function(value)
+            }
+        )*
+    };
+}
+
+extern_unary! {
+    pitch_calc_hz_from_mel => hz_from_mel(calc::Mel) -> calc::Hz,
+    pitch_calc_hz_from_octave_fraction => hz_from_octave_fraction(calc::OctaveFraction) -> calc::Hz,
+    pitch_calc_hz_from_perc => hz_from_perc(calc::Perc) -> calc::Hz,
+    pitch_calc_hz_from_step => hz_from_step(calc::Step) -> calc::Hz,
+    pitch_calc_mel_from_hz => mel_from_hz(calc::Hz) -> calc::Mel,
+    pitch_calc_mel_from_step => mel_from_step(calc::Step) -> calc::Mel,
+    pitch_calc_octave_fraction_from_hz => octave_fraction_from_hz(calc::Hz) -> calc::OctaveFraction,
+    pitch_calc_perc_from_hz => perc_from_hz(calc::Hz) -> calc::Perc,
+    pitch_calc_step_from_hz => step_from_hz(calc::Hz) -> calc::Step,
+    pitch_calc_step_from_mel => step_from_mel(calc::Mel) -> calc::Step,
+}
+
+/// The frequency of the given step when `A 4` sounds at the given frequency.
+#[no_mangle]
+pub extern "C" fn pitch_calc_hz_from_step_with_a4(step: calc::Step, a4: calc::Hz) -> calc::Hz {
+    calc::hz_from_step_with_a4(step, a4)
+}
+
+/// The step of the given frequency when `A 4` sounds at the given frequency.
+#[no_mangle]
+pub extern "C" fn pitch_calc_step_from_hz_with_a4(hz: calc::Hz, a4: calc::Hz) -> calc::Step {
+    calc::step_from_hz_with_a4(hz, a4)
+}
+
+/// The frequency of the given pitch class (`0` to `11`, wrapping) within the given octave.
+#[no_mangle]
+pub extern "C" fn pitch_calc_hz_from_letter_octave(pitch_class: i32, octave: Octave) -> calc::Hz {
+    calc::hz_from_letter_octave(letter(pitch_class), octave)
+}
+
+/// The step of the given pitch class (`0` to `11`, wrapping) within the given octave.
+#[no_mangle]
+pub extern "C" fn pitch_calc_step_from_letter_octave(pitch_class: i32, octave: Octave)
+    -> calc::Step
+{
+    calc::step_from_letter_octave(letter(pitch_class), octave)
+}
+
+/// The pitch class of the letter closest to the given frequency, writing its octave to `octave`
+/// when it is non-null.
+///
+/// # Safety
+///
+/// `octave` must either be null or point to a writable `int32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn pitch_calc_letter_octave_from_hz(hz: calc::Hz, octave: *mut Octave)
+    -> i32
+{
+    let (letter, closest_octave) = calc::letter_octave_from_hz(hz);
+    if !octave.is_null() {
+        *octave = closest_octave;
+    }
+    letter.to_i32().unwrap()
+}
+
+/// Parse a pitch from the given nul-terminated UTF-8 string (see `parse_pitch`), writing the
+/// equivalent step to `step`. Returns `false`, leaving `step` untouched, if the string could not
+/// be parsed.
+///
+/// # Safety
+///
+/// `s` must be null or point to a nul-terminated string, and `step` must be null or point to a
+/// writable `float`.
+#[no_mangle]
+pub unsafe extern "C" fn pitch_calc_parse_step(s: *const c_char, step: *mut calc::Step) -> bool {
+    if s.is_null() || step.is_null() {
+        return false;
+    }
+    let pitch = CStr::from_ptr(s).to_str().ok().and_then(|s| parse_pitch(s).ok());
+    match pitch {
+        Some(pitch) => {
+            *step = pitch.step();
+            true
+        },
+        None => false,
+    }
+}
+
+/// The Letter of the given pitch class.
+fn letter(pitch_class: i32) -> Letter {
+    Letter::from_i32(pitch_class).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(pitch_calc_hz_from_step(69.0), 440.0);
+        assert_eq!(pitch_calc_hz_from_letter_octave(9, 4), 440.0);
+        assert_eq!(pitch_calc_step_from_letter_octave(-3, 4), 69.0);
+        assert!((pitch_calc_step_from_hz_with_a4(415.0, 415.0) - 69.0).abs() < 0.0001);
+        let mut octave = 0;
+        assert_eq!(unsafe { pitch_calc_letter_octave_from_hz(277.18, &mut octave) }, 1);
+        assert_eq!(octave, 4);
+    }
+
+    #[test]
+    fn parse() {
+        let mut step = 0.0;
+        let name = CString::new("A4 +14c").unwrap();
+        assert!(unsafe { pitch_calc_parse_step(name.as_ptr(), &mut step) });
+        assert!((step - 69.14).abs() < 0.0001);
+        let invalid = CString::new("H4").unwrap();
+        assert!(!unsafe { pitch_calc_parse_step(invalid.as_ptr(), &mut step) });
+        assert!(!unsafe { pitch_calc_parse_step(::std::ptr::null(), &mut step) });
+    }
+}
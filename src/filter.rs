@@ -0,0 +1,41 @@
+//! Frequency math for designing digital filters at musically meaningful frequencies.
+
+use std::f64::consts::PI;
+use super::Hz;
+
+/// Prewarp the given frequency for the bilinear transform at the given sample rate.
+///
+/// The bilinear transform compresses the whole analog frequency axis into the range below the
+/// Nyquist frequency, so an analog prototype designed at `hz` would respond at a lower digital
+/// frequency. Designing the prototype at the prewarped frequency `fs / π · tan(π · hz / fs)`
+/// instead places the response of the digital filter exactly at `hz`.
+///
+/// The result grows without bound as `hz` approaches the Nyquist frequency.
+#[inline]
+pub fn prewarp(hz: Hz, sample_rate: f64) -> Hz {
+    Hz((sample_rate / PI * (PI * hz.hz() as f64 / sample_rate).tan()) as f32)
+}
+
+/// The inverse of `prewarp`: the digital frequency at which an analog prototype designed at the
+/// given frequency responds after the bilinear transform at the given sample rate.
+#[inline]
+pub fn unprewarp(hz: Hz, sample_rate: f64) -> Hz {
+    Hz((sample_rate / PI * (PI * hz.hz() as f64 / sample_rate).atan()) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prewarp_round_trip() {
+        // Low frequencies are barely affected while those near Nyquist are pushed far higher.
+        assert!((prewarp(Hz(100.0), 48_000.0).hz() - 100.001).abs() < 0.001);
+        assert!((prewarp(Hz(12_000.0), 48_000.0).hz() - 15_278.87).abs() < 0.1);
+        for &hz in &[20.0, 440.0, 5_000.0, 20_000.0] {
+            let warped = prewarp(Hz(hz), 44_100.0);
+            assert!(warped.hz() >= hz);
+            assert!((unprewarp(warped, 44_100.0).hz() - hz).abs() < 0.01);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Formatting options shared by every pitch representation, so that user interfaces can produce
+//! consistent text without post-processing the output of `Display`.
+
+use super::{
+    Accidental,
+    AnyPitch,
+    Chord,
+    Hz,
+    Letter,
+    LetterOctave,
+    LogPerc,
+    Mel,
+    OctaveFraction,
+    Perc,
+    Pitch,
+    QuarterTone,
+    RomanNumeral,
+    ScaleDegree,
+    ScaledPerc,
+    Step,
+    nearest_step,
+};
+
+/// Options controlling how values are formatted by `FormatWith::format_with` and `format_note`.
+///
+/// The defaults match the plain `Display` of each type, so options are given by overriding only
+/// the fields of interest, e.g. `FormatOptions { precision: Some(2), ..FormatOptions::default() }`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct FormatOptions {
+    /// The number of decimal places of numeric values (and of cents offsets), or `None` for the
+    /// shortest exact representation.
+    pub precision: Option<usize>,
+    /// Whether numeric values are followed by their unit (e.g. `440 Hz` or `69 st`).
+    pub units: bool,
+    /// Whether accidentals are written with Unicode symbols (e.g. `♯` and `♭`) rather than
+    /// ASCII.
+    pub unicode: bool,
+    /// Whether pitch names are followed by their offset in cents from the named pitch (e.g.
+    /// `A4 +14c`). Only used by `format_note`.
+    pub cents: bool,
+}
+
+/// Types that may be formatted according to a set of `FormatOptions`.
+pub trait FormatWith {
+    /// Format the value according to the given options.
+    fn format_with(&self, options: &FormatOptions) -> String;
+}
+
+/// Format the name of the pitch nearest to the given pitch (e.g. `A4`), followed by its offset in
+/// cents from that pitch when `options.cents` is set (e.g. `A4 +14c`). The offset is rounded to
+/// `options.precision` decimal places, or to a whole cent by default.
+///
+/// The offset is always given with a sign, so that the text may be parsed back into a `Step`.
+pub fn format_note<P: Pitch>(pitch: P, options: &FormatOptions) -> String {
+    let step = pitch.to_step().step();
+    let name = Step(nearest_step(step)).to_letter_octave().format_with(options);
+    if !options.cents {
+        return name;
+    }
+    let cents = (step - nearest_step(step)) * 100.0;
+    format!("{} {:+.*}c", name, options.precision.unwrap_or(0), cents)
+}
+
+/// Implement FormatWith for numeric types, whose Display applies the precision to the number and
+/// appends the unit with the alternate flag.
+macro_rules! impl_format_with_numeric {
+    ($($T:ty),*) => {
+        $(
+            impl FormatWith for $T {
+                fn format_with(&self, options: &FormatOptions) -> String {
+                    match (options.precision, options.units) {
+                        (Some(precision), true) => format!("{:#.*}", precision, self),
+                        (Some(precision), false) => format!("{:.*}", precision, self),
+                        (None, true) => format!("{:#}", self),
+                        (None, false) => format!("{}", self),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Implement FormatWith for named types, whose Display uses Unicode accidentals with the
+/// alternate flag.
+macro_rules! impl_format_with_named {
+    ($($T:ty),*) => {
+        $(
+            impl FormatWith for $T {
+                fn format_with(&self, options: &FormatOptions) -> String {
+                    if options.unicode {
+                        format!("{:#}", self)
+                    } else {
+                        format!("{}", self)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_format_with_numeric!(Hz, LogPerc, Mel, OctaveFraction, Perc, ScaledPerc, Step);
+impl_format_with_named!(Accidental, Chord, Letter, LetterOctave, QuarterTone, RomanNumeral,
+                        ScaleDegree);
+
+impl FormatWith for AnyPitch {
+    /// Formats the wrapped representation.
+    fn format_with(&self, options: &FormatOptions) -> String {
+        match *self {
+            AnyPitch::Hz(ref hz) => hz.format_with(options),
+            AnyPitch::LetterOctave(ref letter_octave) => letter_octave.format_with(options),
+            AnyPitch::LogPerc(ref log_perc) => log_perc.format_with(options),
+            AnyPitch::Mel(ref mel) => mel.format_with(options),
+            AnyPitch::OctaveFraction(ref octave_fraction) => octave_fraction.format_with(options),
+            AnyPitch::Perc(ref perc) => perc.format_with(options),
+            AnyPitch::QuarterTone(ref quarter_tone) => quarter_tone.format_with(options),
+            AnyPitch::ScaledPerc(ref scaled_perc) => scaled_perc.format_with(options),
+            AnyPitch::Step(ref step) => step.format_with(options),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric() {
+        let options = FormatOptions { precision: Some(2), units: true, ..FormatOptions::default() };
+        assert_eq!(Hz(440.0).format_with(&options), "440.00 Hz");
+        assert_eq!(Step(69.0).format_with(&options), "69.00 st");
+        assert_eq!(Perc(0.372).format_with(&options), "37.20 %");
+        assert_eq!(Hz(440.0).format_with(&FormatOptions::default()), "440");
+        let plain = FormatOptions { precision: Some(1), ..FormatOptions::default() };
+        assert_eq!(Mel(1125.0).format_with(&plain), "1125.0");
+        assert_eq!(AnyPitch::Hz(Hz(440.0)).format_with(&options), "440.00 Hz");
+    }
+
+    #[test]
+    fn named() {
+        let unicode = FormatOptions { unicode: true, ..FormatOptions::default() };
+        assert_eq!(LetterOctave(Letter::Csh, 4).format_with(&unicode), "C\u{266F}4");
+        assert_eq!(LetterOctave(Letter::Csh, 4).format_with(&FormatOptions::default()), "C#4");
+        assert_eq!(Letter::Bb.format_with(&unicode), "B\u{266D}");
+        let any = AnyPitch::LetterOctave(LetterOctave(Letter::Eb, 3));
+        assert_eq!(any.format_with(&unicode), "E\u{266D}3");
+    }
+
+    #[test]
+    fn notes() {
+        let cents = FormatOptions { cents: true, ..FormatOptions::default() };
+        assert_eq!(format_note(Step(69.14), &cents), "A4 +14c");
+        assert_eq!(format_note(Step(69.14), &FormatOptions::default()), "A4");
+        assert_eq!(format_note(Step(60.75), &cents), "C#4 -25c");
+        assert_eq!(format_note(Hz(440.0), &cents), "A4 +0c");
+        let precise = FormatOptions { precision: Some(1), unicode: true, ..cents };
+        assert_eq!(format_note(Step(60.75), &precise), "C\u{266F}4 -25.0c");
+        // The offset may be parsed back into the pitch.
+        let text = format_note(Step(69.14), &cents);
+        assert!((text.parse::<Step>().unwrap().step() - 69.14).abs() < 0.001);
+    }
+}
@@ -0,0 +1,177 @@
+//! Fretted string instruments, mapping between (string, fret) positions and pitches.
+
+use super::{
+    calc,
+    Letter,
+    LetterOctave,
+    Step,
+};
+
+/// The number of frets used by the preset constructors.
+pub const DEFAULT_FRETS: u32 = 24;
+
+/// A fretted string instrument described by the open pitch of each of its strings.
+///
+/// Strings are indexed from `0`, starting with the lowest-pitched string (i.e. the low `E` on a
+/// guitar in standard tuning). Fret `0` is the open string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Fretboard {
+    /// The open pitch of each string, from the lowest to the highest string.
+    pub strings: Vec<LetterOctave>,
+    /// The number of frets available on every string.
+    pub frets: u32,
+}
+
+impl Fretboard {
+
+    /// Construct a Fretboard from the open pitch of each string and the number of frets.
+    pub fn new(strings: Vec<LetterOctave>, frets: u32) -> Fretboard {
+        Fretboard { strings, frets }
+    }
+
+    /// A six string guitar in standard tuning (E A D G B E).
+    pub fn guitar_standard() -> Fretboard {
+        Fretboard::new(vec![
+            LetterOctave(Letter::E, 2),
+            LetterOctave(Letter::A, 2),
+            LetterOctave(Letter::D, 3),
+            LetterOctave(Letter::G, 3),
+            LetterOctave(Letter::B, 3),
+            LetterOctave(Letter::E, 4),
+        ], DEFAULT_FRETS)
+    }
+
+    /// A six string guitar in drop-D tuning (D A D G B E).
+    pub fn guitar_drop_d() -> Fretboard {
+        let mut fretboard = Fretboard::guitar_standard();
+        fretboard.strings[0] = LetterOctave(Letter::D, 2);
+        fretboard
+    }
+
+    /// A six string guitar in DADGAD tuning.
+    pub fn guitar_dadgad() -> Fretboard {
+        Fretboard::new(vec![
+            LetterOctave(Letter::D, 2),
+            LetterOctave(Letter::A, 2),
+            LetterOctave(Letter::D, 3),
+            LetterOctave(Letter::G, 3),
+            LetterOctave(Letter::A, 3),
+            LetterOctave(Letter::D, 4),
+        ], DEFAULT_FRETS)
+    }
+
+    /// A four string bass guitar in standard tuning (E A D G).
+    pub fn bass_standard() -> Fretboard {
+        Fretboard::new(vec![
+            LetterOctave(Letter::E, 1),
+            LetterOctave(Letter::A, 1),
+            LetterOctave(Letter::D, 2),
+            LetterOctave(Letter::G, 2),
+        ], DEFAULT_FRETS)
+    }
+
+    /// A soprano/concert/tenor ukulele in standard re-entrant tuning (G C E A).
+    ///
+    /// Note that the re-entrant `G` string is higher than the `C` string beside it, so the
+    /// strings are not sorted by pitch for this preset.
+    pub fn ukulele_standard() -> Fretboard {
+        Fretboard::new(vec![
+            LetterOctave(Letter::G, 4),
+            LetterOctave(Letter::C, 4),
+            LetterOctave(Letter::E, 4),
+            LetterOctave(Letter::A, 4),
+        ], 18)
+    }
+
+    /// The number of strings.
+    #[inline]
+    pub fn num_strings(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// The pitch produced by the given string and fret as a Step.
+    ///
+    /// Returns `None` if either the string or the fret does not exist on this fretboard.
+    #[inline]
+    pub fn step(&self, string: usize, fret: u32) -> Option<Step> {
+        if fret > self.frets {
+            return None;
+        }
+        self.strings.get(string).map(|open| Step(open.step() + fret as calc::Step))
+    }
+
+    /// The pitch produced by the given string and fret as a LetterOctave.
+    ///
+    /// Returns `None` if either the string or the fret does not exist on this fretboard.
+    #[inline]
+    pub fn letter_octave(&self, string: usize, fret: u32) -> Option<LetterOctave> {
+        self.step(string, fret).map(|step| step.to_letter_octave())
+    }
+
+    /// All `(string, fret)` positions at which the given pitch can be played.
+    ///
+    /// The pitch is rounded to the nearest semitone. Positions are ordered by string index.
+    pub fn positions<P: Into<Step>>(&self, pitch: P) -> Vec<(usize, u32)> {
+        let target = pitch.into().step().round();
+        self.strings.iter().enumerate().filter_map(|(string, open)| {
+            let fret = target - open.step();
+            if fret >= 0.0 && fret <= self.frets as calc::Step {
+                Some((string, fret as u32))
+            } else {
+                None
+            }
+        }).collect()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets() {
+        let standard = Fretboard::guitar_standard();
+        assert_eq!(standard.num_strings(), 6);
+        assert_eq!(standard.frets, DEFAULT_FRETS);
+        assert_eq!(Fretboard::guitar_drop_d().strings[0], LetterOctave(Letter::D, 2));
+        assert_eq!(Fretboard::guitar_drop_d().strings[1..], standard.strings[1..]);
+        assert_eq!(Fretboard::guitar_dadgad().strings[4], LetterOctave(Letter::A, 3));
+        assert_eq!(Fretboard::bass_standard().strings[0], LetterOctave(Letter::E, 1));
+        let ukulele = Fretboard::ukulele_standard();
+        assert_eq!((ukulele.num_strings(), ukulele.frets), (4, 18));
+        assert!(ukulele.strings[0].step() > ukulele.strings[1].step());
+    }
+
+    #[test]
+    fn string_and_fret_to_pitch() {
+        let guitar = Fretboard::guitar_standard();
+        assert_eq!(guitar.step(0, 0), Some(Step(40.0)));
+        assert_eq!(guitar.letter_octave(0, 5), Some(LetterOctave(Letter::A, 2)));
+        assert_eq!(guitar.letter_octave(5, 12), Some(LetterOctave(Letter::E, 5)));
+        assert_eq!(guitar.letter_octave(1, 24), Some(LetterOctave(Letter::A, 4)));
+    }
+
+    #[test]
+    fn pitch_to_positions() {
+        let guitar = Fretboard::guitar_standard();
+        assert_eq!(guitar.positions(LetterOctave(Letter::E, 2)), vec![(0, 0)]);
+        assert_eq!(guitar.positions(LetterOctave(Letter::E, 4)),
+                   vec![(0, 24), (1, 19), (2, 14), (3, 9), (4, 5), (5, 0)]);
+        // Pitches are rounded to the nearest semitone.
+        assert_eq!(guitar.positions(Step(40.2)), vec![(0, 0)]);
+        let bass = Fretboard::bass_standard();
+        assert!(bass.positions(LetterOctave(Letter::E, 0)).is_empty());
+    }
+
+    #[test]
+    fn out_of_range() {
+        let guitar = Fretboard::guitar_standard();
+        assert_eq!(guitar.step(0, 25), None);
+        assert_eq!(guitar.step(6, 0), None);
+        assert_eq!(guitar.letter_octave(6, 3), None);
+        assert!(guitar.positions(LetterOctave(Letter::F, 6)).is_empty());
+        assert!(Fretboard::new(vec![], 12).positions(Step(60.0)).is_empty());
+    }
+}
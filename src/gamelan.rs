@@ -0,0 +1,149 @@
+use super::{
+    calc,
+    Hz,
+    Scale,
+    OCTAVE_CENTS,
+};
+
+/// A representative slendro tuning in cents above the first degree (after measurements of Central
+/// Javanese gamelan). Each ensemble is tuned differently, see `GamelanTuning::detune`.
+pub const SLENDRO_CENTS: [calc::Cents; 5] = [0.0, 231.0, 474.0, 717.0, 955.0];
+/// A representative pelog tuning in cents above the first degree (after measurements of Central
+/// Javanese gamelan). Each ensemble is tuned differently, see `GamelanTuning::detune`.
+pub const PELOG_CENTS: [calc::Cents; 7] = [0.0, 120.0, 258.0, 539.0, 675.0, 785.0, 943.0];
+
+/// The two tuning systems (laras) of the Javanese and Balinese gamelan.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Laras {
+    /// The five tone, roughly equidistant system.
+    Slendro,
+    /// The seven tone system of large and small intervals.
+    Pelog,
+}
+
+impl Laras {
+
+    /// The representative degrees of the laras in cents above its first degree.
+    pub fn cents(&self) -> &'static [calc::Cents] {
+        match *self {
+            Laras::Slendro => &SLENDRO_CENTS,
+            Laras::Pelog => &PELOG_CENTS,
+        }
+    }
+
+    /// The representative scale of the laras.
+    #[inline]
+    pub fn scale(&self) -> Scale {
+        Scale::normalize(self.cents().iter().cloned(), OCTAVE_CENTS)
+    }
+
+}
+
+/// The tuning of a particular gamelan ensemble.
+///
+/// No two gamelan are tuned alike, so the representative tuning of the laras may be adjusted per
+/// degree by `detune`. Pairs of instruments are also deliberately tuned apart by `ombak` so that
+/// they beat against each other when struck together.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct GamelanTuning {
+    /// The tuning system of the ensemble.
+    pub laras: Laras,
+    /// The frequency of the first degree.
+    pub tonic: Hz,
+    /// The deviation of each degree from the representative tuning in cents. Missing degrees
+    /// are not detuned.
+    pub detune: Vec<calc::Cents>,
+    /// The rate at which paired instruments beat against each other in hz.
+    pub ombak: calc::Hz,
+    /// The size of the "octave" in cents, which is often stretched slightly beyond `1200`.
+    pub period: calc::Cents,
+}
+
+impl GamelanTuning {
+
+    /// A gamelan tuned exactly to the representative tuning of the laras, without ombak.
+    pub fn new(laras: Laras, tonic: Hz) -> GamelanTuning {
+        GamelanTuning {
+            laras,
+            tonic,
+            detune: Vec::new(),
+            ombak: 0.0,
+            period: OCTAVE_CENTS,
+        }
+    }
+
+    /// The same gamelan with each degree detuned by the given amount in cents.
+    pub fn detuned<I>(self, detune: I) -> GamelanTuning
+        where I: IntoIterator<Item=calc::Cents>,
+    {
+        GamelanTuning { detune: detune.into_iter().collect(), ..self }
+    }
+
+    /// The same gamelan with paired instruments beating at the given rate in hz.
+    #[inline]
+    pub fn with_ombak(self, ombak: calc::Hz) -> GamelanTuning {
+        GamelanTuning { ombak, ..self }
+    }
+
+    /// The same gamelan repeating every `period` cents rather than every octave.
+    ///
+    /// The period must be finite and greater than zero for the frequencies to be meaningful.
+    #[inline]
+    pub fn with_period(self, period: calc::Cents) -> GamelanTuning {
+        GamelanTuning { period, ..self }
+    }
+
+    /// The scale of the ensemble, including its detuning and period.
+    pub fn scale(&self) -> Scale {
+        let degrees = self.laras.cents().iter().enumerate()
+            .map(|(i, cents)| cents + self.detune.get(i).cloned().unwrap_or(0.0));
+        Scale::normalize(degrees, self.period)
+    }
+
+    /// The frequency of the given degree. Degrees beyond the length of the laras continue into
+    /// the following periods, while negative degrees descend below the tonic.
+    pub fn hz(&self, degree: i32) -> Hz {
+        let cents = self.scale().cents(degree) as f64;
+        Hz((self.tonic.hz() as f64 * (cents / OCTAVE_CENTS as f64).exp2()) as calc::Hz)
+    }
+
+    /// The frequencies of the lower (pengumbang) and higher (pengisep) instrument of a pair at the
+    /// given degree, which lie `ombak` hz apart around the degree's frequency.
+    pub fn paired_hz(&self, degree: i32) -> (Hz, Hz) {
+        let hz = self.hz(degree).hz();
+        (Hz(hz - self.ombak / 2.0), Hz(hz + self.ombak / 2.0))
+    }
+
+    /// The frequencies of each degree within the given range, forming a tuning table.
+    pub fn table(&self, degrees: ::std::ops::Range<i32>) -> Vec<Hz> {
+        degrees.map(|degree| self.hz(degree)).collect()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detuned_table() {
+        let tuning = GamelanTuning::new(Laras::Slendro, Hz(280.0))
+            .detuned(vec![0.0, 10.0])
+            .with_ombak(5.0);
+        assert_eq!(tuning.scale().degrees[1], 241.0);
+        let table = tuning.table(0..6);
+        assert_eq!(table.len(), 6);
+        assert!((table[5].hz() - 560.0).abs() < 0.01);
+        let (low, high) = tuning.paired_hz(0);
+        assert_eq!((low, high), (Hz(277.5), Hz(282.5)));
+    }
+
+    #[test]
+    fn stretched_period() {
+        let tuning = GamelanTuning::new(Laras::Pelog, Hz(300.0)).with_period(1_220.0);
+        assert!(tuning.hz(7).hz() > 600.0);
+        assert!(tuning.hz(-7).hz() < 150.0);
+    }
+}
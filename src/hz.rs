@@ -1,19 +1,29 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
 use super::{
     calc,
+    Curve,
     DEFAULT_SCALE_WEIGHT,
     LetterOctave,
     Letter,
+    LogPerc,
     Mel,
+    NormalizedHz,
     Octave,
+    OctaveFraction,
     Perc,
     ScaledPerc,
     ScaleWeight,
+    Spelling,
     Step,
     letter_octave_from_hz,
+    log_perc_from_hz,
     mel_from_hz,
+    octave_fraction_from_hz,
     perc_from_hz,
+    perc_from_hz_with_range,
     scaled_perc_from_hz,
     step_from_hz,
 };
@@ -23,7 +33,7 @@ pub const MIN: calc::Hz = 20.0;
 
 /// Pitch representation in the form of a frequency (hz).
 #[derive(Debug, Copy, Clone)]
-#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize))]
 pub struct Hz(pub calc::Hz);
 
 impl Hz {
@@ -63,6 +73,12 @@ impl Hz {
         LetterOctave(letter, octave)
     }
 
+    /// Convert to the closest LetterOctave, spelling any black key with the given preference.
+    #[inline]
+    pub fn to_letter_octave_with_spelling(&self, spelling: Spelling) -> LetterOctave {
+        self.to_letter_octave().spelled(spelling)
+    }
+
     /// Convert to the unit value of a Mel.
     #[inline]
     pub fn mel(&self) -> calc::Mel {
@@ -88,6 +104,45 @@ impl Hz {
         Perc(self.perc())
     }
 
+    /// Convert to the unit value of a percentage of the human hearing range in log-frequency
+    /// space.
+    #[inline]
+    pub fn log_perc(&self) -> calc::Perc {
+        log_perc_from_hz(self.hz())
+    }
+
+    /// Convert to a percentage of the human hearing range in log-frequency space.
+    #[inline]
+    pub fn to_log_perc(&self) -> LogPerc {
+        LogPerc(self.log_perc())
+    }
+
+    /// Convert to the unit value of a percentage of the range between `min` and `max` hz.
+    #[inline]
+    pub fn perc_with_range(&self, min: calc::Hz, max: calc::Hz) -> calc::Perc {
+        perc_from_hz_with_range(self.hz(), min, max)
+    }
+
+    /// Convert to a percentage of the range between `min` and `max` hz.
+    #[inline]
+    pub fn to_perc_with_range(&self, min: calc::Hz, max: calc::Hz) -> Perc {
+        Perc(self.perc_with_range(min, max))
+    }
+
+    /// Convert to the unit value of the control position at which this frequency lies when the
+    /// human hearing range is mapped by the given response curve.
+    #[inline]
+    pub fn perc_with_curve(&self, curve: Curve) -> calc::Perc {
+        curve.position(self.hz())
+    }
+
+    /// Convert to the control position at which this frequency lies when the human hearing range
+    /// is mapped by the given response curve.
+    #[inline]
+    pub fn to_perc_with_curve(&self, curve: Curve) -> Perc {
+        Perc(self.perc_with_curve(curve))
+    }
+
     /// Convert to a scaled percentage of the human hearing range with a given weight.
     #[inline]
     pub fn scaled_perc_with_weight(&self, weight: ScaleWeight) -> calc::Perc {
@@ -126,6 +181,143 @@ impl Hz {
         Step(self.step())
     }
 
+    /// Convert to a fractional octave number.
+    #[inline]
+    pub fn octave_fraction(&self) -> calc::OctaveFraction {
+        let Hz(hz) = *self;
+        octave_fraction_from_hz(hz)
+    }
+
+    /// Convert to an OctaveFraction struct.
+    #[inline]
+    pub fn to_octave_fraction(&self) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction())
+    }
+
+    /// Convert to a fraction of the given sample rate.
+    #[inline]
+    pub fn to_normalized_hz(&self, sample_rate: f64) -> NormalizedHz {
+        NormalizedHz::from_hz(*self, sample_rate)
+    }
+
+    /// The frequency transposed by as few whole octaves as possible so that it lies within the
+    /// range of human hearing (`MIN` to `MAX` hz), along with the number of octaves it was moved
+    /// (negative when moved down).
+    ///
+    /// Returns `None` for frequencies that are not greater than zero.
+    #[inline]
+    pub fn fold_into_audible_range(&self) -> Option<(Hz, i32)> {
+        self.fold_into_hz_range(MIN, MAX)
+    }
+
+    /// The frequency transposed by as few whole octaves as possible so that it lies within the
+    /// range of `min` to `max` hz (inclusive), along with the number of octaves it was moved
+    /// (negative when moved down).
+    ///
+    /// Returns `None` for frequencies that are not greater than zero, or if the range is narrower
+    /// than an octave and no octave of the frequency lies within it.
+    pub fn fold_into_hz_range(&self, min: calc::Hz, max: calc::Hz) -> Option<(Hz, i32)> {
+        let (hz, min, max) = (self.hz() as f64, min as f64, max as f64);
+        if hz.is_nan() || hz <= 0.0 || hz.is_infinite() {
+            return None;
+        }
+        let octaves = if hz < min {
+            (min / hz).log2().ceil() as i32
+        } else if hz > max {
+            -((hz / max).log2().ceil() as i32)
+        } else {
+            0
+        };
+        let folded = hz * 2f64.powi(octaves);
+        if folded >= min && folded <= max {
+            Some((Hz(folded as calc::Hz), octaves))
+        } else {
+            None
+        }
+    }
+
+    /// The lower of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn min(self, other: Hz) -> Hz {
+        Hz(self.hz().min(other.hz()))
+    }
+
+    /// The higher of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn max(self, other: Hz) -> Hz {
+        Hz(self.hz().max(other.hz()))
+    }
+
+    /// The pitch limited to the range `min` to `max`. Never panics, even on a `NaN` pitch or
+    /// bound (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn clamp(self, min: Hz, max: Hz) -> Hz {
+        self.max(min).min(max)
+    }
+
+}
+
+/// Deserializes from either a number (e.g. `440.0`) or a string (e.g. `"440Hz"`, `"1.2 kHz"` or
+/// a pitch name such as `"A4"`) in human readable formats. Other formats, which cannot describe
+/// the type of each value, use the same newtype representation as `Serialize`.
+#[cfg(feature = "serde_serialization")]
+impl<'de> ::serde::Deserialize<'de> for Hz {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Hz, D::Error> {
+        use any_pitch::AnyPitch;
+        use serde::de::{self, Visitor};
+        use std::fmt;
+
+        if !deserializer.is_human_readable() {
+            #[derive(Deserialize)]
+            #[serde(rename = "Hz")]
+            struct Newtype(calc::Hz);
+            return <Newtype as ::serde::Deserialize>::deserialize(deserializer)
+                .map(|Newtype(hz)| Hz(hz));
+        }
+
+        struct HzVisitor;
+
+        impl<'de> Visitor<'de> for HzVisitor {
+            type Value = Hz;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a frequency as a number or a string such as \"440Hz\"")
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Hz, E> {
+                Ok(Hz(value as calc::Hz))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Hz, E> {
+                Ok(Hz(value as calc::Hz))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Hz, E> {
+                Ok(Hz(value as calc::Hz))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Hz, E> {
+                if let Ok(hz) = value.trim().parse::<calc::Hz>() {
+                    return Ok(Hz(hz));
+                }
+                value.parse::<AnyPitch>().map(|pitch| pitch.to_hz()).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(HzVisitor)
+    }
+}
+
+impl fmt::Display for Hz {
+    /// Formats the frequency in hz, appending the unit with the alternate flag (e.g. `440 Hz`). Any
+    /// precision or width is applied to the number.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&(self.hz()), f)?;
+        if f.alternate() {
+            f.write_str(" Hz")?;
+        }
+        Ok(())
+    }
 }
 
 impl Add for Hz {
@@ -179,22 +371,76 @@ impl Neg for Hz {
 impl PartialEq for Hz {
     #[inline]
     fn eq(&self, other: &Hz) -> bool {
-        self.hz() == other.hz()
+        self.cmp(other) == Ordering::Equal
     }
 }
 
 impl Eq for Hz {}
 
+impl Hash for Hz {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hz().to_bits().hash(state);
+    }
+}
+
 impl PartialOrd for Hz {
     #[inline]
     fn partial_cmp(&self, other: &Hz) -> Option<Ordering> {
-        self.hz().partial_cmp(&other.hz())
+        Some(self.cmp(other))
     }
 }
 
+/// Ordered by the [total order](crate#comparison) shared by the float pitch types.
 impl Ord for Hz {
     #[inline]
     fn cmp(&self, other: &Hz) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.hz().total_cmp(&other.hz())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chained_conversions() {
+        // Chaining through mels leaves the step within a hundredth of a cent.
+        for i in 0..2_000 {
+            let hz = Hz(1.0 + i as calc::Hz * 10.0);
+            let chained = hz.to_mel().to_hz().to_step().to_mel().to_hz().to_step();
+            assert!((chained.step() - hz.to_step().step()).abs() < 0.000_1, "{:?}", hz);
+        }
+        assert!((Hz(1_000.0).mel() - 1_000.0).abs() < 0.1);
+        assert!((Mel(0.001).hz() - 0.000_621_13).abs() < 0.000_000_1);
+    }
+
+    #[test]
+    fn fold_into_audible_range() {
+        assert_eq!(Hz(5.0).fold_into_audible_range(), Some((Hz(20.0), 2)));
+        assert_eq!(Hz(3.0).fold_into_audible_range(), Some((Hz(24.0), 3)));
+        assert_eq!(Hz(50_000.0).fold_into_audible_range(), Some((Hz(12_500.0), -2)));
+        assert_eq!(Hz(440.0).fold_into_audible_range(), Some((Hz(440.0), 0)));
+        assert_eq!(Hz(0.0).fold_into_audible_range(), None);
+        assert_eq!(Hz(100.0).fold_into_hz_range(300.0, 500.0), Some((Hz(400.0), 2)));
+        assert_eq!(Hz(100.0).fold_into_hz_range(300.0, 350.0), None);
+    }
+
+    #[test]
+    fn total_order() {
+        // Unlike `f32`, zeros of differing sign are distinct and `NaN` equals itself.
+        assert_ne!(Hz(0.0), Hz(-0.0));
+        assert!(Hz(-0.0) < Hz(0.0));
+        assert_eq!(Hz(calc::Hz::NAN), Hz(calc::Hz::NAN));
+        assert!(Hz(calc::Hz::NAN) > Hz(calc::Hz::INFINITY));
+        assert_eq!(Step(calc::Step::NAN), Step(calc::Step::NAN));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Hz(440.0)), "440");
+        assert_eq!(format!("{:#}", Hz(440.0)), "440 Hz");
+        assert_eq!(format!("{:#.2}", Hz(440.0)), "440.00 Hz");
+        assert_eq!(format!("{:>#8.1}", Hz(440.0)), "   440.0 Hz");
     }
 }
@@ -0,0 +1,232 @@
+//! Transposing instruments and the standard playable ranges of common instruments.
+
+use super::{
+    calc,
+    Letter,
+    LetterOctave,
+    Octave,
+    PitchRange,
+    Step,
+};
+
+/// An instrument whose written pitch differs from the pitch that actually sounds (concert pitch).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TransposingInstrument {
+    /// A human readable name for the instrument.
+    pub name: &'static str,
+    /// The number of semitones from written pitch to concert pitch. This is negative for
+    /// instruments that sound lower than written (e.g. `-2` for a B♭ clarinet).
+    pub transposition: calc::Semitones,
+}
+
+/// B♭ clarinet, sounding a major second lower than written.
+pub const B_FLAT_CLARINET: TransposingInstrument =
+    TransposingInstrument { name: "B♭ Clarinet", transposition: -2 };
+/// A clarinet, sounding a minor third lower than written.
+pub const A_CLARINET: TransposingInstrument =
+    TransposingInstrument { name: "A Clarinet", transposition: -3 };
+/// E♭ clarinet, sounding a minor third higher than written.
+pub const E_FLAT_CLARINET: TransposingInstrument =
+    TransposingInstrument { name: "E♭ Clarinet", transposition: 3 };
+/// B♭ bass clarinet, sounding a major ninth lower than written.
+pub const B_FLAT_BASS_CLARINET: TransposingInstrument =
+    TransposingInstrument { name: "B♭ Bass Clarinet", transposition: -14 };
+/// B♭ trumpet, sounding a major second lower than written.
+pub const B_FLAT_TRUMPET: TransposingInstrument =
+    TransposingInstrument { name: "B♭ Trumpet", transposition: -2 };
+/// B♭ soprano saxophone, sounding a major second lower than written.
+pub const B_FLAT_SOPRANO_SAX: TransposingInstrument =
+    TransposingInstrument { name: "B♭ Soprano Saxophone", transposition: -2 };
+/// E♭ alto saxophone, sounding a major sixth lower than written.
+pub const E_FLAT_ALTO_SAX: TransposingInstrument =
+    TransposingInstrument { name: "E♭ Alto Saxophone", transposition: -9 };
+/// B♭ tenor saxophone, sounding a major ninth lower than written.
+pub const B_FLAT_TENOR_SAX: TransposingInstrument =
+    TransposingInstrument { name: "B♭ Tenor Saxophone", transposition: -14 };
+/// E♭ baritone saxophone, sounding an octave and a major sixth lower than written.
+pub const E_FLAT_BARITONE_SAX: TransposingInstrument =
+    TransposingInstrument { name: "E♭ Baritone Saxophone", transposition: -21 };
+/// Horn in F, sounding a perfect fifth lower than written.
+pub const F_HORN: TransposingInstrument =
+    TransposingInstrument { name: "Horn in F", transposition: -7 };
+/// English horn (cor anglais), sounding a perfect fifth lower than written.
+pub const ENGLISH_HORN: TransposingInstrument =
+    TransposingInstrument { name: "English Horn", transposition: -7 };
+/// Piccolo, sounding an octave higher than written.
+pub const PICCOLO: TransposingInstrument =
+    TransposingInstrument { name: "Piccolo", transposition: 12 };
+/// Guitar, sounding an octave lower than written.
+pub const GUITAR: TransposingInstrument =
+    TransposingInstrument { name: "Guitar", transposition: -12 };
+/// Double bass, sounding an octave lower than written.
+pub const DOUBLE_BASS: TransposingInstrument =
+    TransposingInstrument { name: "Double Bass", transposition: -12 };
+/// Glockenspiel, sounding two octaves higher than written.
+pub const GLOCKENSPIEL: TransposingInstrument =
+    TransposingInstrument { name: "Glockenspiel", transposition: 24 };
+
+impl TransposingInstrument {
+
+    /// Construct a TransposingInstrument from a name and its written-to-concert transposition.
+    pub fn new(name: &'static str, transposition: calc::Semitones) -> TransposingInstrument {
+        TransposingInstrument { name, transposition }
+    }
+
+    /// Convert a written pitch to the concert pitch that sounds when it is played.
+    #[inline]
+    pub fn concert_from_written<P: Into<Step>>(&self, written: P) -> Step {
+        Step(written.into().step() + self.transposition as calc::Step)
+    }
+
+    /// Convert a concert pitch to the pitch that should be written for this instrument.
+    #[inline]
+    pub fn written_from_concert<P: Into<Step>>(&self, concert: P) -> Step {
+        Step(concert.into().step() - self.transposition as calc::Step)
+    }
+
+}
+
+/// Common instruments with a standard playable range.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Instrument {
+    Piano,
+    Harp,
+    Guitar,
+    BassGuitar,
+    Violin,
+    Viola,
+    Cello,
+    DoubleBass,
+    Piccolo,
+    Flute,
+    Oboe,
+    Clarinet,
+    Bassoon,
+    AltoSax,
+    TenorSax,
+    Trumpet,
+    Horn,
+    Trombone,
+    Tuba,
+}
+
+/// Every Instrument variant, in score order within each family.
+pub const ALL_INSTRUMENTS: [Instrument; 19] = [
+    Instrument::Piano,
+    Instrument::Harp,
+    Instrument::Guitar,
+    Instrument::BassGuitar,
+    Instrument::Violin,
+    Instrument::Viola,
+    Instrument::Cello,
+    Instrument::DoubleBass,
+    Instrument::Piccolo,
+    Instrument::Flute,
+    Instrument::Oboe,
+    Instrument::Clarinet,
+    Instrument::Bassoon,
+    Instrument::AltoSax,
+    Instrument::TenorSax,
+    Instrument::Trumpet,
+    Instrument::Horn,
+    Instrument::Trombone,
+    Instrument::Tuba,
+];
+
+impl Instrument {
+
+    /// The standard playable range of the instrument at concert (sounding) pitch.
+    pub fn range(&self) -> PitchRange {
+        use self::Instrument::*;
+        use letter::Letter::*;
+        let (lowest, highest) = match *self {
+            Piano      => ((A, 0), (C, 8)),
+            Harp       => ((B, 0), (Gsh, 7)),
+            Guitar     => ((E, 2), (B, 5)),
+            BassGuitar => ((E, 1), (G, 4)),
+            Violin     => ((G, 3), (A, 7)),
+            Viola      => ((C, 3), (E, 6)),
+            Cello      => ((C, 2), (A, 5)),
+            DoubleBass => ((E, 1), (G, 4)),
+            Piccolo    => ((D, 5), (C, 8)),
+            Flute      => ((C, 4), (D, 7)),
+            Oboe       => ((Bb, 3), (A, 6)),
+            Clarinet   => ((D, 3), (Bb, 6)),
+            Bassoon    => ((Bb, 1), (Eb, 5)),
+            AltoSax    => ((Db, 3), (Ab, 5)),
+            TenorSax   => ((Ab, 2), (E, 5)),
+            Trumpet    => ((E, 3), (Bb, 5)),
+            Horn       => ((B, 1), (F, 5)),
+            Trombone   => ((E, 2), (F, 5)),
+            Tuba       => ((D, 1), (F, 4)),
+        };
+        let to_step = |(letter, octave): (Letter, Octave)| LetterOctave(letter, octave).to_step();
+        PitchRange(to_step(lowest), to_step(highest))
+    }
+
+    /// The transposition used when writing parts for this instrument, if it is a transposing
+    /// instrument.
+    pub fn transposition(&self) -> Option<TransposingInstrument> {
+        use self::Instrument::*;
+        match *self {
+            Guitar | BassGuitar => Some(GUITAR),
+            DoubleBass          => Some(DOUBLE_BASS),
+            Piccolo             => Some(PICCOLO),
+            Clarinet            => Some(B_FLAT_CLARINET),
+            AltoSax             => Some(E_FLAT_ALTO_SAX),
+            TenorSax            => Some(B_FLAT_TENOR_SAX),
+            Trumpet             => Some(B_FLAT_TRUMPET),
+            Horn                => Some(F_HORN),
+            _                   => None,
+        }
+    }
+
+}
+
+/// Whether or not the given concert pitch lies within the standard range of the instrument.
+///
+/// The pitch is rounded to the nearest semitone before checking, so that tiny floating point
+/// errors (e.g. from converting `Hz`) at the edges of the range are tolerated.
+#[inline]
+pub fn is_playable<P: Into<Step>>(pitch: P, instrument: Instrument) -> bool {
+    instrument.range().contains(Step(pitch.into().step().round()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn written_and_concert() {
+        // Written `C 5` sounds as `B♭ 4`, `E♭ 4` and `F 4` respectively.
+        let written = LetterOctave(Letter::C, 5);
+        for &(instrument, concert) in &[
+            (B_FLAT_CLARINET, LetterOctave(Letter::Bb, 4)),
+            (E_FLAT_ALTO_SAX, LetterOctave(Letter::Eb, 4)),
+            (F_HORN, LetterOctave(Letter::F, 4)),
+        ] {
+            let sounding = instrument.concert_from_written(written);
+            assert_eq!(sounding.to_letter_octave(), concert);
+            assert_eq!(instrument.written_from_concert(sounding), written.to_step());
+            for step in 40..90 {
+                let concert = instrument.concert_from_written(Step(step as calc::Step));
+                assert_eq!(instrument.written_from_concert(concert), Step(step as calc::Step));
+            }
+        }
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(Instrument::Piano.range(), PitchRange(Step(21.0), Step(108.0)));
+        assert!(is_playable(LetterOctave(Letter::E, 2), Instrument::Guitar));
+        assert!(!is_playable(LetterOctave(Letter::Eb, 2), Instrument::Guitar));
+        // Tiny errors from converting frequencies are tolerated at the edges.
+        assert!(is_playable(::Hz(27.4999), Instrument::Piano));
+        for instrument in ALL_INSTRUMENTS.iter() {
+            assert!(instrument.range().span() > 0.0);
+        }
+        assert_eq!(Instrument::Horn.transposition(), Some(F_HORN));
+        assert_eq!(Instrument::Flute.transposition(), None);
+    }
+}
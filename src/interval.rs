@@ -0,0 +1,233 @@
+use num::ToPrimitive;
+use std::fmt;
+use std::str::FromStr;
+use super::{
+    calc,
+    Error,
+    LetterOctave,
+    ParseError,
+};
+
+/// The number of semitones above the tonic of each degree of the major scale.
+const MAJOR_SCALE_SEMITONES: [calc::Semitones; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// The quality of an Interval.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Quality {
+    DoublyDiminished,
+    Diminished,
+    Minor,
+    Perfect,
+    Major,
+    Augmented,
+    DoublyAugmented,
+}
+
+impl Quality {
+
+    /// The abbreviation used for the quality within interval names (e.g. the `m` in `m3`).
+    pub fn abbreviation(&self) -> &'static str {
+        match *self {
+            Quality::DoublyDiminished => "dd",
+            Quality::Diminished => "d",
+            Quality::Minor => "m",
+            Quality::Perfect => "P",
+            Quality::Major => "M",
+            Quality::Augmented => "A",
+            Quality::DoublyAugmented => "AA",
+        }
+    }
+
+    /// The quality with the given abbreviation.
+    pub fn from_abbreviation(abbreviation: &str) -> Option<Quality> {
+        match abbreviation {
+            "dd" => Some(Quality::DoublyDiminished),
+            "d" => Some(Quality::Diminished),
+            "m" => Some(Quality::Minor),
+            "P" => Some(Quality::Perfect),
+            "M" => Some(Quality::Major),
+            "A" => Some(Quality::Augmented),
+            "AA" => Some(Quality::DoublyAugmented),
+            _ => None,
+        }
+    }
+
+}
+
+/// A diatonic interval described by its quality and number (e.g. a minor third or a perfect
+/// fifth). Numbers greater than `8` describe compound intervals (e.g. `10` for a tenth).
+///
+/// `Interval::new` rejects a number of zero and qualities that cannot apply to the number. An
+/// Interval built from such fields directly never panics: a number of zero is treated as a
+/// unison, and an inapplicable quality as perfect or major.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Interval {
+    pub quality: Quality,
+    pub number: u32,
+}
+
+/// Whether the interval number belongs to the perfect class (unisons, fourths and fifths), rather
+/// than the major/minor class.
+#[inline]
+fn is_perfect_class(number: u32) -> bool {
+    matches!(number.saturating_sub(1) % 7, 0 | 3 | 4)
+}
+
+/// The offset in semitones a quality applies to the major or perfect interval with the given
+/// number.
+fn quality_offset(quality: Quality, number: u32) -> Option<calc::Semitones> {
+    let perfect = is_perfect_class(number);
+    match quality {
+        Quality::DoublyDiminished => Some(if perfect { -2 } else { -3 }),
+        Quality::Diminished => Some(if perfect { -1 } else { -2 }),
+        Quality::Minor if !perfect => Some(-1),
+        Quality::Perfect if perfect => Some(0),
+        Quality::Major if !perfect => Some(0),
+        Quality::Augmented => Some(1),
+        Quality::DoublyAugmented => Some(2),
+        _ => None,
+    }
+}
+
+/// The number of semitones spanned by the major or perfect interval with the given number.
+#[inline]
+fn natural_semitones(number: u32) -> calc::Semitones {
+    let degree = number.saturating_sub(1) as usize;
+    MAJOR_SCALE_SEMITONES[degree % 7] + (degree / 7) as calc::Semitones * 12
+}
+
+impl Interval {
+
+    /// Construct an Interval, checking that the quality can be applied to the number.
+    ///
+    /// Returns `Error::OutOfRange` for an interval number of zero and `Error::InvalidValue` for
+    /// a quality that cannot be applied to the number (e.g. a major fifth).
+    pub fn new(quality: Quality, number: u32) -> Result<Interval, Error> {
+        if number == 0 {
+            return Err(Error::OutOfRange);
+        }
+        match quality_offset(quality, number) {
+            Some(_) => Ok(Interval { quality, number }),
+            None => Err(Error::InvalidValue),
+        }
+    }
+
+    /// The number of semitones spanned by the interval.
+    pub fn semitones(&self) -> calc::Semitones {
+        let offset = quality_offset(self.quality, self.number).unwrap_or(0);
+        natural_semitones(self.number) + offset
+    }
+
+    /// Whether or not the interval spans more than an octave.
+    #[inline]
+    pub fn is_compound(&self) -> bool {
+        self.number > 8
+    }
+
+    /// The interval reduced to within a single octave (e.g. a major tenth becomes a major third).
+    /// Octaves themselves are left unchanged.
+    #[inline]
+    pub fn simple(&self) -> Interval {
+        let number = if self.number > 8 { (self.number - 2) % 7 + 2 } else { self.number };
+        Interval { quality: self.quality, number }
+    }
+
+    /// The interval between two spelled pitches, taking the spelling of each letter into account
+    /// (e.g. `C` to `Eb` is a minor third, while `C` to `Dsh` is an augmented second).
+    ///
+    /// The order of the pitches does not matter. Returns `None` if the interval would be more than
+    /// doubly diminished or doubly augmented.
+    pub fn between(a: LetterOctave, b: LetterOctave) -> Option<Interval> {
+        let position = |lo: &LetterOctave| lo.octave() * 7 + lo.letter().diatonic_index() as i32;
+        let semitones = |lo: &LetterOctave| lo.step().to_i32().unwrap();
+        let (a_position, b_position) = (position(&a), position(&b));
+        let ascending = b_position > a_position
+            || (b_position == a_position && semitones(&b) >= semitones(&a));
+        let (low, high) = if ascending { (a, b) } else { (b, a) };
+        let number = (position(&high) - position(&low)) as u32 + 1;
+        let offset = semitones(&high) - semitones(&low) - natural_semitones(number);
+        let perfect = is_perfect_class(number);
+        let quality = match (offset, perfect) {
+            (-2, true) | (-3, false) => Quality::DoublyDiminished,
+            (-1, true) | (-2, false) => Quality::Diminished,
+            (-1, false) => Quality::Minor,
+            (0, true) => Quality::Perfect,
+            (0, false) => Quality::Major,
+            (1, _) => Quality::Augmented,
+            (2, _) => Quality::DoublyAugmented,
+            _ => return None,
+        };
+        Some(Interval { quality, number })
+    }
+
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.quality.abbreviation(), self.number)
+    }
+}
+
+impl FromStr for Interval {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Interval, Error> {
+        let s = s.trim();
+        let split = s.find(|c: char| c.is_ascii_digit()).ok_or(ParseError::InvalidFormat)?;
+        let (quality, number) = s.split_at(split);
+        let quality = Quality::from_abbreviation(quality).ok_or(ParseError::InvalidFormat)?;
+        let number = number.parse().map_err(|_| ParseError::InvalidFormat)?;
+        Interval::new(quality, number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use letter::Letter;
+
+    #[test]
+    fn parse_and_format() {
+        for name in &["P1", "m3", "M3", "P5", "A4", "d7", "M10", "AA4", "dd5"] {
+            let interval: Interval = name.parse().unwrap();
+            assert_eq!(&interval.to_string(), name);
+        }
+        assert_eq!("P5".parse::<Interval>().unwrap().semitones(), 7);
+        assert_eq!("M10".parse::<Interval>().unwrap().semitones(), 16);
+        assert_eq!("P3".parse::<Interval>(), Err(Error::InvalidValue));
+        assert_eq!("P0".parse::<Interval>(), Err(Error::OutOfRange));
+        assert_eq!("X5".parse::<Interval>(), Err(ParseError::InvalidFormat.into()));
+    }
+
+    #[test]
+    fn between_spelled_pitches() {
+        let c4 = LetterOctave(Letter::C, 4);
+        let between = |b| Interval::between(c4, b).unwrap().to_string();
+        assert_eq!(between(LetterOctave(Letter::Eb, 4)), "m3");
+        assert_eq!(between(LetterOctave(Letter::Dsh, 4)), "A2");
+        assert_eq!(between(LetterOctave(Letter::Gb, 4)), "d5");
+        assert_eq!(between(LetterOctave(Letter::Fsh, 4)), "A4");
+        assert_eq!(between(LetterOctave(Letter::C, 5)), "P8");
+        assert_eq!(between(LetterOctave(Letter::E, 5)), "M10");
+        assert_eq!(between(LetterOctave(Letter::A, 3)), "m3");
+        assert_eq!(between(LetterOctave(Letter::Csh, 4)), "A1");
+    }
+
+    #[test]
+    fn simple_intervals() {
+        let tenth = Interval::new(Quality::Major, 10).unwrap();
+        assert!(tenth.is_compound());
+        assert_eq!(tenth.simple(), Interval::new(Quality::Major, 3).unwrap());
+        assert_eq!(Interval::new(Quality::Perfect, 15).unwrap().simple().number, 8);
+    }
+
+    #[test]
+    fn invalid_fields() {
+        // Intervals rejected by `Interval::new` may still be built from the fields.
+        let zero = Interval { quality: Quality::Perfect, number: 0 };
+        assert_eq!(zero.semitones(), 0);
+        assert_eq!(Interval { quality: Quality::Augmented, number: 0 }.semitones(), 1);
+        assert_eq!(Interval { quality: Quality::Major, number: 5 }.semitones(), 7);
+    }
+}
@@ -0,0 +1,208 @@
+use num::{FromPrimitive, ToPrimitive};
+use super::{
+    calc,
+    Letter,
+    Step,
+    TOTAL_LETTERS,
+};
+use utils::modulo;
+
+/// The Krumhansl-Kessler probe-tone profile for major keys, starting from the tonic.
+pub const MAJOR_PROFILE: [f32; 12] =
+    [6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88];
+/// The Krumhansl-Kessler probe-tone profile for minor keys, starting from the tonic.
+pub const MINOR_PROFILE: [f32; 12] =
+    [6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17];
+
+/// The mode of a Key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Mode {
+    Major,
+    Minor,
+}
+
+/// A musical key, described by its tonic and mode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Key(pub Letter, pub Mode);
+
+impl Key {
+
+    /// Construct a Key from a tonic pitch class, spelling the tonic the way that key is
+    /// conventionally written (e.g. `Bb` major rather than `Ash` major, but `Fsh` minor).
+    pub fn from_pitch_class(pitch_class: u8, mode: Mode) -> Key {
+        use letter::Letter::*;
+        let tonic = match (modulo(pitch_class, TOTAL_LETTERS), mode) {
+            (1, Mode::Major) => Db,
+            (3, _) => Eb,
+            (6, _) => Fsh,
+            (8, Mode::Major) => Ab,
+            (10, _) => Bb,
+            (pc, _) => FromPrimitive::from_u8(pc).unwrap(),
+        };
+        Key(tonic, mode)
+    }
+
+    /// Return the tonic of the key.
+    #[inline]
+    pub fn tonic(&self) -> Letter {
+        let Key(tonic, _) = *self;
+        tonic
+    }
+
+    /// Return the mode of the key.
+    #[inline]
+    pub fn mode(&self) -> Mode {
+        let Key(_, mode) = *self;
+        mode
+    }
+
+    /// The relative key, sharing the same key signature in the other mode (e.g. `A` minor for
+    /// `C` major).
+    pub fn relative(&self) -> Key {
+        let tonic = self.tonic_pitch_class();
+        match self.mode() {
+            Mode::Major => Key::from_pitch_class(modulo(tonic + 9, 12), Mode::Minor),
+            Mode::Minor => Key::from_pitch_class(modulo(tonic + 3, 12), Mode::Major),
+        }
+    }
+
+    /// The parallel key, sharing the same tonic in the other mode (e.g. `C` minor for `C`
+    /// major).
+    #[inline]
+    pub fn parallel(&self) -> Key {
+        let mode = match self.mode() {
+            Mode::Major => Mode::Minor,
+            Mode::Minor => Mode::Major,
+        };
+        Key(self.tonic(), mode)
+    }
+
+    /// The key of the same mode a fifth above (e.g. `G` major for `C` major).
+    #[inline]
+    pub fn dominant(&self) -> Key {
+        Key::from_pitch_class(modulo(self.tonic_pitch_class() + 7, 12), self.mode())
+    }
+
+    /// The key of the same mode a fifth below (e.g. `F` major for `C` major).
+    #[inline]
+    pub fn subdominant(&self) -> Key {
+        Key::from_pitch_class(modulo(self.tonic_pitch_class() + 5, 12), self.mode())
+    }
+
+    /// The pitch class of the tonic.
+    #[inline]
+    fn tonic_pitch_class(&self) -> u8 {
+        self.tonic().to_u8().unwrap()
+    }
+
+    /// The probe-tone profile for this key, indexed by pitch class (with `C` at index `0`).
+    pub fn profile(&self) -> [f32; 12] {
+        let base = match self.mode() {
+            Mode::Major => &MAJOR_PROFILE,
+            Mode::Minor => &MINOR_PROFILE,
+        };
+        let tonic = self.tonic().to_usize().unwrap();
+        let mut profile = [0.0; 12];
+        for (i, weight) in base.iter().enumerate() {
+            profile[(tonic + i) % 12] = *weight;
+        }
+        profile
+    }
+
+}
+
+/// Sum the weights of a collection of pitches into a pitch class profile indexed by pitch class
+/// (with `C` at index `0`). Each pitch is rounded to the nearest semitone.
+pub fn pitch_class_profile<I, P>(pitches: I) -> [f32; 12]
+    where I: IntoIterator<Item=(P, calc::Weight)>,
+          P: Into<Step>,
+{
+    let mut profile = [0.0; 12];
+    for (pitch, weight) in pitches {
+        let pitch_class = modulo(calc::nearest_step(pitch.into().step()) as i32, 12);
+        profile[pitch_class as usize] += weight;
+    }
+    profile
+}
+
+/// The Pearson correlation coefficient between two profiles.
+fn correlation(a: &[f32; 12], b: &[f32; 12]) -> f32 {
+    let mean = |p: &[f32; 12]| p.iter().sum::<f32>() / 12.0;
+    let (mean_a, mean_b) = (mean(a), mean(b));
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b.iter()) {
+        let (dx, dy) = (x - mean_a, y - mean_b);
+        cov += dx * dy;
+        var_a += dx * dx;
+        var_b += dy * dy;
+    }
+    let denominator = (var_a * var_b).sqrt();
+    if denominator == 0.0 { 0.0 } else { cov / denominator }
+}
+
+/// Estimate the key of a pitch class profile using the Krumhansl-Schmuckler algorithm.
+///
+/// Returns all 24 major and minor keys paired with the correlation between their probe-tone
+/// profile and the given profile, ordered from the most to the least likely key. The keys are
+/// sorted by the total order of their correlations, so a profile containing `NaN` never panics.
+pub fn estimate_key_from_profile(profile: &[f32; 12]) -> Vec<(Key, f32)> {
+    let mut candidates: Vec<(Key, f32)> = Vec::with_capacity(24);
+    for &mode in &[Mode::Major, Mode::Minor] {
+        for pitch_class in 0..TOTAL_LETTERS {
+            let key = Key::from_pitch_class(pitch_class, mode);
+            candidates.push((key, correlation(&key.profile(), profile)));
+        }
+    }
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+    candidates
+}
+
+/// Estimate the key of a weighted collection of pitches (e.g. note durations or spectral
+/// magnitudes) using the Krumhansl-Schmuckler algorithm.
+///
+/// See `estimate_key_from_profile` for the returned ranking.
+pub fn estimate_key<I, P>(pitches: I) -> Vec<(Key, f32)>
+    where I: IntoIterator<Item=(P, calc::Weight)>,
+          P: Into<Step>,
+{
+    estimate_key_from_profile(&pitch_class_profile(pitches))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use letter::Letter;
+    use step::Step;
+
+    #[test]
+    fn c_major_scale() {
+        let scale = [60.0, 62.0, 64.0, 65.0, 67.0, 69.0, 71.0, 72.0];
+        let weights = [2.0, 1.0, 1.5, 1.0, 2.0, 1.0, 1.0, 2.0];
+        let pitches = scale.iter().zip(weights.iter()).map(|(&s, &w)| (Step(s), w));
+        let ranked = estimate_key(pitches);
+        assert_eq!(ranked.len(), 24);
+        assert_eq!(ranked[0].0, Key(Letter::C, Mode::Major));
+    }
+
+    #[test]
+    fn conventional_spelling() {
+        assert_eq!(Key::from_pitch_class(10, Mode::Major), Key(Letter::Bb, Mode::Major));
+        assert_eq!(Key::from_pitch_class(1, Mode::Minor).tonic(), Letter::Csh);
+    }
+
+    #[test]
+    fn related_keys() {
+        let c_major = Key(Letter::C, Mode::Major);
+        assert_eq!(c_major.relative(), Key(Letter::A, Mode::Minor));
+        assert_eq!(c_major.parallel(), Key(Letter::C, Mode::Minor));
+        assert_eq!(c_major.dominant(), Key(Letter::G, Mode::Major));
+        assert_eq!(c_major.subdominant(), Key(Letter::F, Mode::Major));
+        let f_sharp_minor = Key(Letter::Fsh, Mode::Minor);
+        assert_eq!(f_sharp_minor.relative(), Key(Letter::A, Mode::Major));
+        assert_eq!(f_sharp_minor.dominant(), Key(Letter::Csh, Mode::Minor));
+        assert_eq!(c_major.subdominant().subdominant().tonic().alteration(), -1);
+        assert_eq!(c_major.relative().relative(), c_major);
+    }
+}
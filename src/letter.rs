@@ -4,12 +4,27 @@ use self::Letter::{
 use num::{FromPrimitive, ToPrimitive};
 use num::PrimInt as Int;
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use accidental::split_note_name;
+use error::{Error, ParseError};
+use std::hash::{Hash, Hasher};
 use utils::modulo;
 
 pub const TOTAL_LETTERS: u8 = 12;
 
+/// A preference for spelling the black keys with either sharps or flats.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Spelling {
+    /// Spell the black keys as `Csh`, `Dsh`, `Fsh`, `Gsh` and `Ash`.
+    Sharps,
+    /// Spell the black keys as `Db`, `Eb`, `Gb`, `Ab` and `Bb`.
+    Flats,
+}
+
 /// The letter representation for each step in the 12-tone, equal temperament, chromatic scale.
-#[derive(Copy, Clone, Debug, Hash)]
+#[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum Letter {
     C, Csh, Db, D, Dsh, Eb, E, F, Fsh, Gb, G, Gsh, Ab, A, Ash, Bb, B
@@ -18,8 +33,7 @@ pub enum Letter {
 
 impl PartialOrd for Letter {
     fn partial_cmp(&self, other: &Letter) -> Option<Ordering> {
-        let other_u8 = other.to_u8().unwrap();
-        self.to_u8().unwrap().partial_cmp(&other_u8)
+        Some(self.cmp(other))
     }
 }
 
@@ -38,6 +52,12 @@ impl PartialEq for Letter {
 
 impl Eq for Letter {}
 
+impl Hash for Letter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_u8().unwrap().hash(state)
+    }
+}
+
 
 impl Letter {
 
@@ -50,6 +70,128 @@ impl Letter {
         }
     }
 
+    /// The natural letter that this letter is spelled with (e.g. `D` for both `Db` and `Dsh`).
+    pub fn natural(&self) -> Letter {
+        match *self {
+            C | Csh => C,
+            Db | D | Dsh => D,
+            Eb | E => E,
+            F | Fsh => F,
+            Gb | G | Gsh => G,
+            Ab | A | Ash => A,
+            Bb | B => B,
+        }
+    }
+
+    /// The alteration applied to the natural letter in semitones (`1` for sharps, `-1` for flats).
+    pub fn alteration(&self) -> i8 {
+        match *self {
+            Csh | Dsh | Fsh | Gsh | Ash => 1,
+            Db | Eb | Gb | Ab | Bb => -1,
+            C | D | E | F | G | A | B => 0,
+        }
+    }
+
+    /// The letter spelled with the given natural letter and alteration in semitones (`1` for a
+    /// sharp and `-1` for a flat), if there is one (i.e. there is no `Cb` or `Esh`).
+    pub fn from_spelling(natural: Letter, alteration: i8) -> Option<Letter> {
+        match (natural.natural(), alteration) {
+            (natural, 0) => Some(natural),
+            (C, 1) => Some(Csh),
+            (D, 1) => Some(Dsh),
+            (F, 1) => Some(Fsh),
+            (G, 1) => Some(Gsh),
+            (A, 1) => Some(Ash),
+            (D, -1) => Some(Db),
+            (E, -1) => Some(Eb),
+            (G, -1) => Some(Gb),
+            (A, -1) => Some(Ab),
+            (B, -1) => Some(Bb),
+            _ => None,
+        }
+    }
+
+    /// The same letter respelled with the given preference (e.g. `Dsh` becomes `Eb` when
+    /// preferring flats). Natural letters are left unchanged.
+    pub fn spelled(&self, spelling: Spelling) -> Letter {
+        match (*self, spelling) {
+            (Db, Spelling::Sharps) => Csh,
+            (Eb, Spelling::Sharps) => Dsh,
+            (Gb, Spelling::Sharps) => Fsh,
+            (Ab, Spelling::Sharps) => Gsh,
+            (Bb, Spelling::Sharps) => Ash,
+            (Csh, Spelling::Flats) => Db,
+            (Dsh, Spelling::Flats) => Eb,
+            (Fsh, Spelling::Flats) => Gb,
+            (Gsh, Spelling::Flats) => Ab,
+            (Ash, Spelling::Flats) => Bb,
+            (letter, _) => letter,
+        }
+    }
+
+    /// The position of the natural letter within the diatonic scale starting from `C` (i.e. `C`
+    /// is `0` and `B` is `6`).
+    pub fn diatonic_index(&self) -> u8 {
+        match self.natural() {
+            C => 0,
+            D => 1,
+            E => 2,
+            F => 3,
+            G => 4,
+            A => 5,
+            _ => 6,
+        }
+    }
+
+    /// The natural letter at the given position within the diatonic scale starting from `C`,
+    /// wrapping around every seven positions (i.e. `0` is `C` and `6` is `B`).
+    pub fn from_diatonic_index(index: u8) -> Letter {
+        [C, D, E, F, G, A, B][(index % 7) as usize]
+    }
+
+    /// The letter of the given pitch class (with `C` as `0`) spelled with the given natural
+    /// letter (e.g. `Eb` for pitch class `3` spelled with `E`), falling back to the default
+    /// spelling of the pitch class when no single sharp or flat would do (e.g. for `Cb`).
+    pub fn spelled_from(natural: Letter, pitch_class: u8) -> Letter {
+        let natural_pitch_class = natural.natural().to_u8().unwrap() as i8;
+        let alteration = modulo(pitch_class as i8 - natural_pitch_class + 6, 12) - 6;
+        Letter::from_spelling(natural, alteration)
+            .unwrap_or_else(|| FromPrimitive::from_u8(modulo(pitch_class, 12)).unwrap())
+    }
+
+}
+
+
+impl fmt::Display for Letter {
+    /// Formats the letter with an ASCII accidental (e.g. `C#`), or with a Unicode accidental when
+    /// the alternate flag is given (e.g. `{:#}` gives `C♯`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = ["C", "D", "E", "F", "G", "A", "B"][self.diatonic_index() as usize];
+        let accidental = match (self.alteration(), f.alternate()) {
+            (1, false) => "#",
+            (-1, false) => "b",
+            (1, true) => "\u{266F}",
+            (-1, true) => "\u{266D}",
+            _ => "",
+        };
+        write!(f, "{}{}", name, accidental)
+    }
+}
+
+impl FromStr for Letter {
+    type Err = Error;
+    /// Parse a letter name such as `C`, `F#`, `Bb`, `F♯` or `B♭`.
+    fn from_str(s: &str) -> Result<Letter, Error> {
+        let (natural, accidental, rest) = split_note_name(s.trim())?;
+        if !rest.is_empty() {
+            return Err(ParseError::InvalidAccidental.into());
+        }
+        let alteration = accidental.step_offset() as i8;
+        if alteration as f32 != accidental.step_offset() {
+            return Err(ParseError::InvalidAccidental.into());
+        }
+        Letter::from_spelling(natural, alteration).ok_or(ParseError::InvalidAccidental.into())
+    }
 }
 
 
@@ -1,17 +1,27 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use error::{Error, ParseError};
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
 use super::{
     calc,
     DEFAULT_SCALE_WEIGHT,
     Letter,
     Hz,
+    LogPerc,
     Mel,
+    OctaveFraction,
     Perc,
+    PitchRange,
     ScaledPerc,
     ScaleWeight,
+    Spelling,
     Step,
     hz_from_letter_octave,
+    log_perc_from_step,
     mel_from_letter_octave,
+    octave_fraction_from_step,
     perc_from_letter_octave,
     scaled_perc_from_letter_octave,
     step_from_letter_octave,
@@ -20,8 +30,8 @@ use super::{
 pub type Octave = i32;
 
 /// Pitch representation in the form of a frequency (hz).
-#[derive(Debug, Copy, Clone, Hash)]
-#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize))]
 pub struct LetterOctave(pub Letter, pub Octave);
 
 impl LetterOctave {
@@ -47,6 +57,30 @@ impl LetterOctave {
         octave
     }
 
+    /// The same letter within the given octave.
+    #[inline]
+    pub fn fold_into_octave(&self, octave: Octave) -> LetterOctave {
+        LetterOctave(self.letter(), octave)
+    }
+
+    /// The same letter moved by as few whole octaves as possible so that it lies within the given
+    /// range, or `None` if no octave of the letter lies within it.
+    #[inline]
+    pub fn fold_into_range(&self, range: PitchRange) -> Option<LetterOctave> {
+        let LetterOctave(letter, octave) = *self;
+        self.to_step().fold_into_range(range).map(|step| {
+            LetterOctave(letter, octave + ((step.step() - self.step()) / 12.0).round() as Octave)
+        })
+    }
+
+    /// The same pitch respelled with the given preference (e.g. `Dsh 4` becomes `Eb 4` when
+    /// preferring flats).
+    #[inline]
+    pub fn spelled(&self, spelling: Spelling) -> LetterOctave {
+        let LetterOctave(letter, octave) = *self;
+        LetterOctave(letter.spelled(spelling), octave)
+    }
+
     /// Convert to the unit value of Hz with the equivalent pitch.
     #[inline]
     pub fn hz(&self) -> calc::Hz {
@@ -86,6 +120,19 @@ impl LetterOctave {
         Perc(self.perc())
     }
 
+    /// Convert to the unit value of a percentage of the human hearing range in log-frequency
+    /// space.
+    #[inline]
+    pub fn log_perc(&self) -> calc::Perc {
+        log_perc_from_step(self.step())
+    }
+
+    /// Convert to a percentage of the human hearing range in log-frequency space.
+    #[inline]
+    pub fn to_log_perc(&self) -> LogPerc {
+        LogPerc(self.log_perc())
+    }
+
     /// Convert to a scaled percentage of the human hearing range with a given weight.
     #[inline]
     pub fn scaled_perc_with_weight(&self, weight: ScaleWeight) -> calc::Perc {
@@ -124,6 +171,104 @@ impl LetterOctave {
         Step(self.step())
     }
 
+    /// Convert to a fractional octave number.
+    #[inline]
+    pub fn octave_fraction(&self) -> calc::OctaveFraction {
+        octave_fraction_from_step(self.step())
+    }
+
+    /// Convert to an OctaveFraction struct.
+    #[inline]
+    pub fn to_octave_fraction(&self) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction())
+    }
+
+}
+
+impl fmt::Display for LetterOctave {
+    /// Formats the pitch with an ASCII accidental (e.g. `C#4`), or with a Unicode accidental when
+    /// the alternate flag is given (e.g. `{:#}` gives `C♯4`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let LetterOctave(letter, octave) = *self;
+        if f.alternate() {
+            write!(f, "{:#}{}", letter, octave)
+        } else {
+            write!(f, "{}{}", letter, octave)
+        }
+    }
+}
+
+impl FromStr for LetterOctave {
+    type Err = Error;
+    /// Parse a pitch name such as `A4`, `C#-1`, `Bb3` or `F♯5`.
+    fn from_str(s: &str) -> Result<LetterOctave, Error> {
+        let s = s.trim();
+        let split = s.char_indices().skip(1)
+            .find(|&(_, c)| c.is_ascii_digit() || c == '-')
+            .map_or(s.len(), |(i, _)| i);
+        let (letter, octave) = s.split_at(split);
+        let letter = letter.parse()?;
+        let octave = octave.parse().map_err(|_| ParseError::InvalidOctave)?;
+        Ok(LetterOctave(letter, octave))
+    }
+}
+
+/// Deserializes from a sequence (e.g. `["C", 4]`), a map (e.g. `{"letter": "C", "octave": 4}`)
+/// or a pitch name (e.g. `"C4"`) in human readable formats. Other formats, which cannot describe
+/// the type of each value, use the same tuple representation as `Serialize`.
+#[cfg(feature = "serde_serialization")]
+impl<'de> ::serde::Deserialize<'de> for LetterOctave {
+    fn deserialize<D>(deserializer: D) -> Result<LetterOctave, D::Error>
+        where D: ::serde::Deserializer<'de>,
+    {
+        use serde::de::{self, MapAccess, SeqAccess, Visitor};
+
+        if !deserializer.is_human_readable() {
+            #[derive(Deserialize)]
+            #[serde(rename = "LetterOctave")]
+            struct Tuple(Letter, Octave);
+            return <Tuple as ::serde::Deserialize>::deserialize(deserializer)
+                .map(|Tuple(letter, octave)| LetterOctave(letter, octave));
+        }
+
+        struct LetterOctaveVisitor;
+
+        impl<'de> Visitor<'de> for LetterOctaveVisitor {
+            type Value = LetterOctave;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a [letter, octave] pair, a {letter, octave} map or a pitch name")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<LetterOctave, A::Error> {
+                let letter = seq.next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let octave = seq.next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Ok(LetterOctave(letter, octave))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<LetterOctave, A::Error> {
+                let (mut letter, mut octave) = (None, None);
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "letter" => letter = Some(map.next_value()?),
+                        "octave" => octave = Some(map.next_value()?),
+                        _ => return Err(de::Error::unknown_field(&key, &["letter", "octave"])),
+                    }
+                }
+                let letter = letter.ok_or_else(|| de::Error::missing_field("letter"))?;
+                let octave = octave.ok_or_else(|| de::Error::missing_field("octave"))?;
+                Ok(LetterOctave(letter, octave))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<LetterOctave, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(LetterOctaveVisitor)
+    }
 }
 
 impl Add for LetterOctave {
@@ -185,22 +330,84 @@ impl PartialEq for LetterOctave {
 
 impl Eq for LetterOctave {}
 
+impl Hash for LetterOctave {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let LetterOctave(letter, octave) = *self;
+        letter.hash(state);
+        octave.hash(state);
+    }
+}
+
 impl PartialOrd for LetterOctave {
     #[inline]
     fn partial_cmp(&self, other: &LetterOctave) -> Option<Ordering> {
-        let LetterOctave(letter, octave) = *self;
-        let LetterOctave(other_letter, other_octave) = *other;
-        match octave.partial_cmp(&other_octave) {
-            Some(Ordering::Equal) => letter.partial_cmp(&other_letter),
-            ordering => ordering,
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for LetterOctave {
     #[inline]
     fn cmp(&self, other: &LetterOctave) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        let LetterOctave(letter, octave) = *self;
+        let LetterOctave(other_letter, other_octave) = *other;
+        match octave.cmp(&other_octave) {
+            Ordering::Equal => letter.cmp(&other_letter),
+            ordering => ordering,
+        }
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_octaves() {
+        let names = |steps: &[calc::Step]| -> Vec<String> {
+            steps.iter().map(|&s| Step(s).to_letter_octave().to_string()).collect()
+        };
+        assert_eq!(names(&[0.0, -1.0, -11.0, -12.0, -13.0, -24.0]),
+                   vec!["C-1", "B-2", "C#-2", "C-2", "B-3", "C-3"]);
+        // Halves round upwards on both sides of zero.
+        assert_eq!(names(&[-0.5, 0.5, -12.5, 59.5]), vec!["C-1", "C#-1", "C-2", "C4"]);
+        assert_eq!(names(&[-0.6, -12.4]), vec!["B-2", "C-2"]);
+        for step in -48..12 {
+            let letter_octave = Step(step as calc::Step).to_letter_octave();
+            assert_eq!(letter_octave.step(), step as calc::Step);
+        }
+    }
+
+    #[test]
+    fn octave_folding() {
+        let bb = LetterOctave(Letter::Bb, 1);
+        assert_eq!(bb.fold_into_octave(3), LetterOctave(Letter::Bb, 3));
+        let range = PitchRange(LetterOctave(Letter::C, 4).to_step(), Step(71.0));
+        assert_eq!(bb.fold_into_range(range), Some(LetterOctave(Letter::Bb, 4)));
+    }
+
+    #[test]
+    fn spelling_preference() {
+        let d_sharp = Step(63.0).to_letter_octave();
+        assert_eq!(d_sharp.spelled(Spelling::Flats).to_string(), "Eb4");
+        assert_eq!(Hz(466.16).to_letter_octave_with_spelling(Spelling::Flats).to_string(), "Bb4");
+        assert_eq!(LetterOctave(Letter::Gb, 2).spelled(Spelling::Sharps).to_string(), "F#2");
+        assert_eq!(LetterOctave(Letter::C, 2).spelled(Spelling::Flats).letter(), Letter::C);
+    }
+
+    #[test]
+    fn format_and_parse() {
+        let lo = LetterOctave(Letter::Fsh, 5);
+        assert_eq!(lo.to_string(), "F#5");
+        assert_eq!(format!("{:#}", lo), "F\u{266F}5");
+        assert_eq!(format!("{:#}", LetterOctave(Letter::Bb, -1)), "B\u{266D}-1");
+        assert_eq!("F#5".parse::<LetterOctave>(), Ok(lo));
+        assert_eq!("F\u{266F}5".parse::<LetterOctave>(), Ok(lo));
+        assert_eq!("B\u{266D}-1".parse::<LetterOctave>().unwrap().letter(), Letter::Bb);
+        assert_eq!("Cb4".parse::<LetterOctave>(), Err(ParseError::InvalidAccidental.into()));
+        assert_eq!("C+4".parse::<LetterOctave>(), Err(ParseError::InvalidAccidental.into()));
+        assert_eq!("A".parse::<LetterOctave>(), Err(ParseError::InvalidOctave.into()));
+        assert_eq!("Eb".parse::<Letter>(), Ok(Letter::Eb));
+    }
+}
@@ -4,6 +4,19 @@
 //!
 //!  Created by Mitchell Nordine at 11:26PM on November 02, 2014.
 //!
+//! # Comparison
+//!
+//! The float pitch types (`Hz`, `LogPerc`, `Mel`, `OctaveFraction`, `Perc`, `ScaledPerc` and
+//! `Step`) implement `Eq`, `Ord` and `Hash` with the IEEE 754 total order of the underlying
+//! float (see `f32::total_cmp`), so that they may be sorted and used as keys. Unlike the `==` and
+//! `<` of the floats themselves, `NaN` equals itself and sorts above every other value, while
+//! `-0.0` sorts below, and is not equal to, `0.0`.
+//!
+//! The inherent `min`, `max` and `clamp` methods of these types, which take precedence over those
+//! of `Ord`, never panic and treat `NaN` as a missing value instead. `min` and `max` ignore a `NaN`
+//! pitch in favour of the other, as the float methods of the same names do. `clamp` gives `min`
+//! for a `NaN` pitch, ignores a `NaN` bound and gives `max` when `min` is greater than `max`.
+//!
 
 extern crate num;
 extern crate rand;
@@ -14,67 +27,286 @@ extern crate serde;
 #[macro_use] extern crate serde_derive;
 
 pub use self::calc::{
+    cents_from_ratio,
     difference_in_semitones,
     hz_from_letter_octave,
+    hz_from_letter_octave_with_a4,
+    hz_from_log_perc,
     hz_from_mel,
+    hz_from_octave_fraction,
     hz_from_perc,
+    hz_from_perc_with_range,
     hz_from_scaled_perc,
     hz_from_step,
+    hz_from_step_with_a4,
     letter_octave_from_hz,
+    letter_octave_from_hz_with_a4,
     letter_octave_from_mel,
     letter_octave_from_perc,
     letter_octave_from_scaled_perc,
     letter_octave_from_step,
+    log_perc_from_hz,
+    log_perc_from_step,
     mel_from_hz,
     mel_from_letter_octave,
     mel_from_perc,
     mel_from_scaled_perc,
     mel_from_step,
+    nearest_step,
+    octave_fraction_from_hz,
+    octave_fraction_from_step,
     perc_from_hz,
+    perc_from_hz_with_range,
     perc_from_letter_octave,
     perc_from_mel,
     perc_from_scaled_perc,
     perc_from_step,
+    ratio_from_cents,
     scaled_perc_from_hz,
     scaled_perc_from_letter_octave,
     scaled_perc_from_mel,
     scaled_perc_from_perc,
     scaled_perc_from_step,
     step_from_hz,
+    step_from_hz_with_a4,
     step_from_letter_octave,
+    step_from_log_perc,
     step_from_mel,
+    step_from_octave_fraction,
     step_from_perc,
     step_from_scaled_perc,
 };
+pub use self::accidental::Accidental;
+pub use self::any_pitch::{
+    AnyPitch,
+    parse_pitch,
+};
+pub use self::band::{
+    Band,
+    BandSize,
+    band_for,
+    cents_from_q,
+    octaves_from_q,
+    q_from_cents,
+    q_from_octaves,
+    q_from_semitones,
+    semitones_from_q,
+    split_range,
+};
+pub use self::chord::{
+    Chord,
+    ChordQuality,
+    detect_chords,
+};
+pub use self::chroma::ChromaMap;
+pub use self::curve::Curve;
+pub use self::describe::PitchDescription;
+pub use self::detune::{
+    Distribution,
+    stereo_pair,
+    stereo_pair_constant_center,
+    unison,
+    unison_offsets,
+};
+pub use self::error::{
+    Error,
+    ParseError,
+    TuningError,
+};
+pub use self::filter::{
+    prewarp,
+    unprewarp,
+};
+pub use self::format::{
+    FormatOptions,
+    FormatWith,
+    format_note,
+};
+pub use self::fretboard::Fretboard;
+pub use self::gamelan::{
+    GamelanTuning,
+    Laras,
+};
 pub use self::hz::Hz;
 pub use self::hz::MAX as MAX_HZ;
 pub use self::hz::MIN as MIN_HZ;
+pub use self::instrument::{
+    Instrument,
+    TransposingInstrument,
+    is_playable,
+};
+pub use self::interval::Interval;
+pub use self::key::{
+    Key,
+    Mode,
+    estimate_key,
+};
 pub use self::letter::{
     Letter,
+    Spelling,
     TOTAL_LETTERS,
 };
 pub use self::letter_octave::{
     Octave,
     LetterOctave,
 };
+pub use self::log_perc::LogPerc;
+pub use self::maqam::Maqam;
 pub use self::mel::Mel;
+pub use self::midi::{
+    MidiNote,
+    MpeNote,
+};
+pub use self::normalized_hz::NormalizedHz;
+pub use self::octave_fraction::OctaveFraction;
 pub use self::perc::Perc;
+pub use self::pitch::{
+    OrderedPitch,
+    Pitch,
+    cents_between,
+    octaves_between,
+    steps_between,
+};
+pub use self::scale::{
+    Comparison,
+    DiatonicMode,
+    OCTAVE_CENTS,
+    Scale,
+};
+pub use self::scale_degree::ScaleDegree;
 pub use self::scaled_perc::{
     DEFAULT_SCALE_WEIGHT,
+    ROUND_TRIP_TOLERANCE,
     ScaledPerc,
     ScaleWeight,
 };
+pub use self::quarter_tone::QuarterTone;
+pub use self::raga::{
+    Raga,
+    Swara,
+};
+pub use self::range::PitchRange;
+pub use self::ratio::Ratio;
+pub use self::roman::RomanNumeral;
+pub use self::segmentation::{
+    Segment,
+    SegmentKind,
+    Segmenter,
+};
+pub use self::set_theory::{
+    SetTransform,
+    interval_class_content,
+    interval_class_vector,
+    inversions_between,
+    is_tn_equivalent,
+    is_tni_equivalent,
+    set_relation,
+    transpositions_between,
+};
+pub use self::smoothing::{
+    PitchSmoother,
+    Seconds,
+    median_filter,
+};
+pub use self::staff::Clef;
 pub use self::step::Step;
+pub use self::temperament::{
+    PYTHAGOREAN,
+    QUARTER_COMMA_MEANTONE,
+    SIXTH_COMMA_MEANTONE,
+    Temperament,
+};
+pub use self::tone_row::ToneRow;
+pub use self::track::{
+    PitchTrack,
+    TrackPoint,
+};
+pub use self::transform::{
+    invert,
+    invert_around,
+    negative_harmony,
+    negative_harmony_spelled,
+    retrograde,
+    retrograde_inversion,
+    rotate,
+    transpose,
+};
+pub use self::tuning::{
+    Tuning,
+    TuningBuilder,
+    CONCERT_PITCH,
+    SCIENTIFIC_PITCH,
+};
+pub use self::vibrato::Vibrato;
+pub use self::voice::VoiceType;
+pub use self::voice_leading::{
+    VoiceLeading,
+    pitch_class_voice_leading,
+    voice_leading,
+};
+pub use self::weighted::WeightedPitch;
 
+pub mod accidental;
+pub mod acoustics;
+pub mod any_pitch;
+pub mod axis;
+pub mod band;
 pub mod calc;
+pub mod chord;
+pub mod chroma;
+pub mod curve;
+pub mod describe;
+#[cfg(feature="detection")]
+pub mod detection;
+pub mod detune;
+pub mod error;
+#[cfg(feature="ffi")]
+pub mod ffi;
+pub mod filter;
+pub mod format;
+pub mod fretboard;
+pub mod gamelan;
 pub mod hz;
+pub mod instrument;
+pub mod interval;
+pub mod key;
 pub mod letter;
 pub mod letter_octave;
+pub mod log_perc;
+pub mod maqam;
 pub mod mel;
+pub mod midi;
+pub mod mts;
+pub mod normalized_hz;
+pub mod octave_fraction;
 pub mod perc;
+pub mod perception;
+pub mod pitch;
+pub mod quarter_tone;
+pub mod raga;
+pub mod range;
+pub mod ratio;
+pub mod roman;
+pub mod scala;
+pub mod scale;
+pub mod scale_degree;
 pub mod scaled_perc;
+pub mod segmentation;
+pub mod set_theory;
+pub mod smoothing;
+pub mod staff;
+pub mod stats;
 pub mod step;
+pub mod temperament;
+pub mod tone_row;
+pub mod track;
+pub mod transform;
+pub mod tuning;
 pub mod utils;
+pub mod vibrato;
+pub mod voice;
+pub mod voice_leading;
+pub mod weighted;
 pub mod convert;
 
 #[cfg(feature="serde_serialization")]
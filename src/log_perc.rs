@@ -0,0 +1,302 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
+use super::{
+    calc,
+    DEFAULT_SCALE_WEIGHT,
+    Hz,
+    LetterOctave,
+    Letter,
+    Mel,
+    Octave,
+    OctaveFraction,
+    Perc,
+    ScaledPerc,
+    ScaleWeight,
+    Spelling,
+    Step,
+    hz_from_log_perc,
+    octave_fraction_from_step,
+    perc_from_hz,
+    scaled_perc_from_hz,
+    step_from_log_perc,
+};
+
+/// Pitch representation in the form of a percentage of the human hearing range in log-frequency
+/// space, i.e. the fraction of the octaves between the min and max hz lying below the pitch.
+///
+/// Unlike `Perc`, in which the lowest octaves are squeezed into the first fraction of a percent,
+/// equal distances cover equal musical intervals. This is usually what a frequency knob or slider
+/// wants, as `0.5` lies at roughly 632hz rather than 10khz.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct LogPerc(pub calc::Perc);
+
+impl LogPerc {
+
+    /// Return the value as a percentage.
+    #[inline]
+    pub fn log_perc(&self) -> calc::Perc { let LogPerc(log_perc) = *self; log_perc }
+
+    /// Convert to unit value of the equivalent frequency in Hz.
+    #[inline]
+    pub fn hz(&self) -> calc::Hz {
+        hz_from_log_perc(self.log_perc())
+    }
+
+    /// Convert to the equivalent frequency in Hz.
+    #[inline]
+    pub fn to_hz(&self) -> Hz {
+        Hz(self.hz())
+    }
+
+    /// Convert to a (Letter, Octave).
+    #[inline]
+    pub fn letter_octave(&self) -> (Letter, Octave) {
+        self.to_step().letter_octave()
+    }
+
+    /// Convert to Letter.
+    #[inline]
+    pub fn letter(&self) -> Letter {
+        let (letter, _) = self.letter_octave();
+        letter
+    }
+
+    /// Convert to Octave.
+    #[inline]
+    pub fn octave(&self) -> Octave {
+        let (_, octave) = self.letter_octave();
+        octave
+    }
+
+    /// Convert to LetterOctave.
+    #[inline]
+    pub fn to_letter_octave(&self) -> LetterOctave {
+        let (letter, octave) = self.letter_octave();
+        LetterOctave(letter, octave)
+    }
+
+    /// Convert to the closest LetterOctave, spelling any black key with the given preference.
+    #[inline]
+    pub fn to_letter_octave_with_spelling(&self, spelling: Spelling) -> LetterOctave {
+        self.to_letter_octave().spelled(spelling)
+    }
+
+    /// Convert to the unit value of a Mel.
+    #[inline]
+    pub fn mel(&self) -> calc::Mel {
+        self.to_hz().mel()
+    }
+
+    /// Convert to a Mel struct.
+    #[inline]
+    pub fn to_mel(&self) -> Mel {
+        Mel(self.mel())
+    }
+
+    /// Convert to the unit value of a linear percentage of the human hearing range.
+    #[inline]
+    pub fn perc(&self) -> calc::Perc {
+        perc_from_hz(self.hz())
+    }
+
+    /// Convert to a linear percentage of the human hearing range.
+    #[inline]
+    pub fn to_perc(&self) -> Perc {
+        Perc(self.perc())
+    }
+
+    /// Convert to a scaled percentage of the human hearing range with a given weight.
+    #[inline]
+    pub fn scaled_perc_with_weight(&self, weight: ScaleWeight) -> calc::Perc {
+        scaled_perc_from_hz(self.hz(), weight)
+    }
+
+    /// Convert to a scaled percentage of the human hearing range.
+    #[inline]
+    pub fn scaled_perc(&self) -> calc::Perc {
+        self.scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
+    }
+
+    /// Convert to a scaled percentage of the human hearing range with a given weight.
+    #[inline]
+    pub fn to_scaled_perc_with_weight(&self, weight: ScaleWeight) -> ScaledPerc {
+        ScaledPerc(self.scaled_perc_with_weight(weight), weight)
+    }
+
+    /// Convert to a scaled percentage of the human hearing range.
+    #[inline]
+    pub fn to_scaled_perc(&self) -> ScaledPerc {
+        self.to_scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
+    }
+
+    /// Convert to the unit value of a Step.
+    #[inline]
+    pub fn step(&self) -> calc::Step {
+        step_from_log_perc(self.log_perc())
+    }
+
+    /// Convert to a floating point MIDI-esque Step.
+    #[inline]
+    pub fn to_step(&self) -> Step {
+        Step(self.step())
+    }
+
+    /// Convert to a fractional octave number.
+    #[inline]
+    pub fn octave_fraction(&self) -> calc::OctaveFraction {
+        octave_fraction_from_step(self.step())
+    }
+
+    /// Convert to an OctaveFraction struct.
+    #[inline]
+    pub fn to_octave_fraction(&self) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction())
+    }
+
+    /// The lower of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn min(self, other: LogPerc) -> LogPerc {
+        LogPerc(self.log_perc().min(other.log_perc()))
+    }
+
+    /// The higher of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn max(self, other: LogPerc) -> LogPerc {
+        LogPerc(self.log_perc().max(other.log_perc()))
+    }
+
+    /// The pitch limited to the range `min` to `max`. Never panics, even on a `NaN` pitch or
+    /// bound (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn clamp(self, min: LogPerc, max: LogPerc) -> LogPerc {
+        self.max(min).min(max)
+    }
+
+}
+
+impl fmt::Display for LogPerc {
+    /// Formats the value as a percentage (i.e. multiplied by `100`), appending the unit with the
+    /// alternate flag (e.g. `50 %`). Any precision or width is applied to the number.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&(self.log_perc() * 100.0), f)?;
+        if f.alternate() {
+            f.write_str(" %")?;
+        }
+        Ok(())
+    }
+}
+
+impl Add for LogPerc {
+    type Output = LogPerc;
+    #[inline]
+    fn add(self, rhs: LogPerc) -> LogPerc {
+        LogPerc(self.log_perc() + rhs.log_perc())
+    }
+}
+
+impl Sub for LogPerc {
+    type Output = LogPerc;
+    #[inline]
+    fn sub(self, rhs: LogPerc) -> LogPerc {
+        LogPerc(self.log_perc() - rhs.log_perc())
+    }
+}
+
+impl Mul for LogPerc {
+    type Output = LogPerc;
+    #[inline]
+    fn mul(self, rhs: LogPerc) -> LogPerc {
+        LogPerc(self.log_perc() * rhs.log_perc())
+    }
+}
+
+impl Div for LogPerc {
+    type Output = LogPerc;
+    #[inline]
+    fn div(self, rhs: LogPerc) -> LogPerc {
+        LogPerc(self.log_perc() / rhs.log_perc())
+    }
+}
+
+impl Rem for LogPerc {
+    type Output = LogPerc;
+    #[inline]
+    fn rem(self, rhs: LogPerc) -> LogPerc {
+        LogPerc(self.log_perc() % rhs.log_perc())
+    }
+}
+
+impl Neg for LogPerc {
+    type Output = LogPerc;
+    #[inline]
+    fn neg(self) -> LogPerc {
+        LogPerc(-self.log_perc())
+    }
+}
+
+impl PartialEq for LogPerc {
+    #[inline]
+    fn eq(&self, other: &LogPerc) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for LogPerc {}
+
+impl Hash for LogPerc {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.log_perc().to_bits().hash(state);
+    }
+}
+
+impl PartialOrd for LogPerc {
+    #[inline]
+    fn partial_cmp(&self, other: &LogPerc) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Ordered by the [total order](crate#comparison) shared by the float pitch types.
+impl Ord for LogPerc {
+    #[inline]
+    fn cmp(&self, other: &LogPerc) -> Ordering {
+        self.log_perc().total_cmp(&other.log_perc())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_frequency_space() {
+        assert!((LogPerc(0.0).hz() - 20.0).abs() < 0.001);
+        assert!((LogPerc(1.0).hz() - 20_000.0).abs() < 0.01);
+        // The midpoint lies at the geometric mean of the range.
+        assert!((LogPerc(0.5).hz() - 632.456).abs() < 0.001);
+        assert!((Hz(632.456).log_perc() - 0.5).abs() < 0.000_001);
+        // Each octave covers the same distance.
+        let octave = Hz(880.0).log_perc() - Hz(440.0).log_perc();
+        assert!((Hz(110.0).log_perc() - Hz(55.0).log_perc() - octave).abs() < 0.000_001);
+        for i in 0..128 {
+            let step = Step(i as calc::Step);
+            assert!((step.to_log_perc().to_step().step() - step.step()).abs() < 0.001);
+        }
+        assert_eq!(LetterOctave(Letter::A, 4).to_log_perc().to_letter_octave(),
+                   LetterOctave(Letter::A, 4));
+        assert_eq!(format!("{:#.0}", LogPerc(0.5)), "50 %");
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", LogPerc(0.5)), "50");
+        assert_eq!(format!("{:#}", LogPerc(0.5)), "50 %");
+        assert_eq!(format!("{:#.1}", LogPerc(0.372)), "37.2 %");
+        assert_eq!(format!("{:>#6.1}", LogPerc(0.5)), "  50.0 %");
+    }
+}
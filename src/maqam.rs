@@ -0,0 +1,109 @@
+use super::{
+    calc,
+    Letter,
+    Scale,
+    OCTAVE_CENTS,
+};
+
+/// A selection of common Arabic maqamat, each described by the lower and upper ajnas of its
+/// ascending scale.
+///
+/// The characteristic neutral intervals are approximated by quarter tones, as is common within
+/// modern Arabic theory and notation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Maqam {
+    Rast,
+    Bayati,
+    Hijaz,
+    Saba,
+    Nahawand,
+    Kurd,
+}
+
+/// Every Maqam.
+pub const ALL_MAQAMAT: [Maqam; 6] = [
+    Maqam::Rast,
+    Maqam::Bayati,
+    Maqam::Hijaz,
+    Maqam::Saba,
+    Maqam::Nahawand,
+    Maqam::Kurd,
+];
+
+impl Maqam {
+
+    /// The degrees of the ascending scale in cents above the tonic.
+    pub fn degrees(&self) -> [calc::Cents; 7] {
+        match *self {
+            Maqam::Rast => [0.0, 200.0, 350.0, 500.0, 700.0, 900.0, 1050.0],
+            Maqam::Bayati => [0.0, 150.0, 300.0, 500.0, 700.0, 800.0, 1000.0],
+            Maqam::Hijaz => [0.0, 100.0, 400.0, 500.0, 700.0, 800.0, 1000.0],
+            Maqam::Saba => [0.0, 150.0, 300.0, 400.0, 700.0, 800.0, 1000.0],
+            Maqam::Nahawand => [0.0, 200.0, 300.0, 500.0, 700.0, 800.0, 1100.0],
+            Maqam::Kurd => [0.0, 100.0, 300.0, 500.0, 700.0, 800.0, 1000.0],
+        }
+    }
+
+    /// The ascending scale of the maqam.
+    #[inline]
+    pub fn scale(&self) -> Scale {
+        Scale::normalize(self.degrees().iter().cloned(), OCTAVE_CENTS)
+    }
+
+    /// The tonic on which the maqam is conventionally played and notated (e.g. `C` for Rast and
+    /// `D` for Bayati).
+    pub fn tonic(&self) -> Letter {
+        match *self {
+            Maqam::Rast | Maqam::Nahawand => Letter::C,
+            Maqam::Bayati | Maqam::Hijaz | Maqam::Saba | Maqam::Kurd => Letter::D,
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Step;
+
+    /// The intervals in cents between successive degrees, including the step back to the octave.
+    fn intervals(maqam: Maqam) -> Vec<calc::Cents> {
+        let scale = maqam.scale();
+        (0..scale.len() as i32)
+            .map(|degree| scale.cents(degree + 1) - scale.cents(degree))
+            .collect()
+    }
+
+    #[test]
+    fn interval_structure() {
+        // Rast is a jins rast on the tonic and another on the fifth, with neutral thirds and
+        // sevenths.
+        assert_eq!(intervals(Maqam::Rast), vec![200.0, 150.0, 150.0, 200.0, 200.0, 150.0, 150.0]);
+        // Bayati begins with the two three-quarter tones of jins bayati.
+        assert_eq!(intervals(Maqam::Bayati),
+                   vec![150.0, 150.0, 200.0, 200.0, 100.0, 200.0, 200.0]);
+        // Hijaz has the augmented second of jins hijaz and no quarter tones.
+        assert_eq!(intervals(Maqam::Hijaz), vec![100.0, 300.0, 100.0, 200.0, 100.0, 200.0, 200.0]);
+        for &maqam in ALL_MAQAMAT.iter() {
+            assert_eq!(maqam.scale().degrees, maqam.degrees().to_vec());
+            assert_eq!(intervals(maqam).iter().sum::<calc::Cents>(), OCTAVE_CENTS);
+        }
+    }
+
+    #[test]
+    fn quarter_tones() {
+        let has_quarter_tone = |maqam: Maqam| {
+            maqam.degrees().iter().any(|cents| cents % 100.0 != 0.0)
+        };
+        assert!(has_quarter_tone(Maqam::Rast));
+        assert!(has_quarter_tone(Maqam::Bayati));
+        assert!(has_quarter_tone(Maqam::Saba));
+        assert!(!has_quarter_tone(Maqam::Hijaz));
+        // The neutral third of Rast on C lies halfway between E flat and E.
+        let rast = Maqam::Rast.scale();
+        assert_eq!(rast.quantize(Step(60.0), Step(63.4)), Step(63.5));
+        assert_eq!(Maqam::Rast.tonic(), Letter::C);
+        assert_eq!(Maqam::Bayati.tonic(), Letter::D);
+    }
+}
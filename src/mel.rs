@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
 use super::{
     calc,
@@ -6,13 +8,18 @@ use super::{
     Hz,
     LetterOctave,
     Letter,
+    LogPerc,
     Octave,
+    OctaveFraction,
     Perc,
     ScaledPerc,
     ScaleWeight,
+    Spelling,
     Step,
     hz_from_mel,
     letter_octave_from_mel,
+    log_perc_from_hz,
+    octave_fraction_from_step,
     perc_from_mel,
     scaled_perc_from_mel,
     step_from_mel,
@@ -72,6 +79,12 @@ impl Mel {
         LetterOctave(letter, octave)
     }
 
+    /// Convert to the closest LetterOctave, spelling any black key with the given preference.
+    #[inline]
+    pub fn to_letter_octave_with_spelling(&self, spelling: Spelling) -> LetterOctave {
+        self.to_letter_octave().spelled(spelling)
+    }
+
     /// Convert to a percentage of the human hearing range.
     #[inline]
     pub fn perc(&self) -> calc::Perc {
@@ -84,6 +97,19 @@ impl Mel {
         Perc(self.perc())
     }
 
+    /// Convert to the unit value of a percentage of the human hearing range in log-frequency
+    /// space.
+    #[inline]
+    pub fn log_perc(&self) -> calc::Perc {
+        log_perc_from_hz(self.hz())
+    }
+
+    /// Convert to a percentage of the human hearing range in log-frequency space.
+    #[inline]
+    pub fn to_log_perc(&self) -> LogPerc {
+        LogPerc(self.log_perc())
+    }
+
     /// Convert to a scaled percentage of the human hearing range with a given weight.
     #[inline]
     pub fn scaled_perc_with_weight(&self, weight: ScaleWeight) -> calc::Perc {
@@ -120,6 +146,49 @@ impl Mel {
         Step(self.step())
     }
 
+    /// Convert to a fractional octave number.
+    #[inline]
+    pub fn octave_fraction(&self) -> calc::OctaveFraction {
+        octave_fraction_from_step(self.step())
+    }
+
+    /// Convert to an OctaveFraction struct.
+    #[inline]
+    pub fn to_octave_fraction(&self) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction())
+    }
+
+    /// The lower of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn min(self, other: Mel) -> Mel {
+        Mel(self.mel().min(other.mel()))
+    }
+
+    /// The higher of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn max(self, other: Mel) -> Mel {
+        Mel(self.mel().max(other.mel()))
+    }
+
+    /// The pitch limited to the range `min` to `max`. Never panics, even on a `NaN` pitch or
+    /// bound (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn clamp(self, min: Mel, max: Mel) -> Mel {
+        self.max(min).min(max)
+    }
+
+}
+
+impl fmt::Display for Mel {
+    /// Formats the value in mels, appending the unit with the alternate flag (e.g. `1125 mel`). Any
+    /// precision or width is applied to the number.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&(self.mel()), f)?;
+        if f.alternate() {
+            f.write_str(" mel")?;
+        }
+        Ok(())
+    }
 }
 
 impl Add for Mel {
@@ -173,23 +242,43 @@ impl Neg for Mel {
 impl PartialEq for Mel {
     #[inline]
     fn eq(&self, other: &Mel) -> bool {
-        self.mel() == other.mel()
+        self.cmp(other) == Ordering::Equal
     }
 }
 
 impl Eq for Mel {}
 
+impl Hash for Mel {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.mel().to_bits().hash(state);
+    }
+}
+
 impl PartialOrd for Mel {
     #[inline]
     fn partial_cmp(&self, other: &Mel) -> Option<Ordering> {
-        self.mel().partial_cmp(&other.mel())
+        Some(self.cmp(other))
     }
 }
 
+/// Ordered by the [total order](crate#comparison) shared by the float pitch types.
 impl Ord for Mel {
     #[inline]
     fn cmp(&self, other: &Mel) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.mel().total_cmp(&other.mel())
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Mel(1125.0)), "1125");
+        assert_eq!(format!("{:#}", Mel(1125.0)), "1125 mel");
+        assert_eq!(format!("{:#.1}", Mel(1125.0)), "1125.0 mel");
+        assert_eq!(format!("{:>#6}", Mel(1125.0)), "  1125 mel");
+    }
+}
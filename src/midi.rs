@@ -0,0 +1,330 @@
+//! Conversions between pitches and MIDI 1.0 notes with pitch bend, as used to play microtonal
+//! music over MIDI.
+
+use std::convert::TryFrom;
+use super::{
+    calc,
+    Error,
+    Hz,
+    LetterOctave,
+    Step,
+    nearest_step,
+};
+
+/// A 14-bit MIDI pitch bend value.
+pub type PitchBend = u16;
+
+/// The pitch bend value at which the pitch is left unchanged.
+pub const BEND_CENTER: PitchBend = 8_192;
+/// The largest pitch bend value.
+pub const BEND_MAX: PitchBend = 16_383;
+/// The pitch bend range most synthesizers default to, in semitones.
+pub const DEFAULT_BEND_RANGE: calc::Step = 2.0;
+/// The default per-note pitch bend range of MPE member channels, in semitones.
+pub const MPE_BEND_RANGE: calc::Step = 48.0;
+
+/// A MIDI note number, guaranteed to lie within the range `0` to `127`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_serialization", serde(try_from = "u8", into = "u8"))]
+pub struct MidiNote(u8);
+
+impl MidiNote {
+
+    /// The lowest MIDI note, `C -1`.
+    pub const MIN: MidiNote = MidiNote(0);
+    /// The highest MIDI note, `G 9`.
+    pub const MAX: MidiNote = MidiNote(127);
+
+    /// The MidiNote with the given number, or `None` if the number is greater than `127`.
+    #[inline]
+    pub fn new(note: u8) -> Option<MidiNote> {
+        if note <= MidiNote::MAX.0 { Some(MidiNote(note)) } else { None }
+    }
+
+    /// The MidiNote with the given number, clamped to `127`.
+    #[inline]
+    pub fn saturating(note: u8) -> MidiNote {
+        MidiNote(note.min(MidiNote::MAX.0))
+    }
+
+    /// The closest MidiNote to the given pitch, or `None` if the pitch lies outside of the MIDI
+    /// note range or is `NaN`.
+    pub fn checked_from_step<P: Into<Step>>(pitch: P) -> Option<MidiNote> {
+        let note = nearest_step(pitch.into().step());
+        if note >= 0.0 && note <= MidiNote::MAX.0 as calc::Step {
+            Some(MidiNote(note as u8))
+        } else {
+            None
+        }
+    }
+
+    /// The closest MidiNote to the given pitch, clamping pitches outside of the MIDI note range
+    /// to the nearest end. `NaN` becomes the lowest note.
+    pub fn saturating_from_step<P: Into<Step>>(pitch: P) -> MidiNote {
+        let note = nearest_step(pitch.into().step());
+        MidiNote(note.clamp(0.0, MidiNote::MAX.0 as calc::Step) as u8)
+    }
+
+    /// Return the MIDI note number.
+    #[inline]
+    pub fn number(&self) -> u8 {
+        let MidiNote(note) = *self;
+        note
+    }
+
+    /// Return the unit value of the equivalent Step.
+    #[inline]
+    pub fn step(&self) -> calc::Step {
+        self.number() as calc::Step
+    }
+
+    /// Convert to the equivalent Step.
+    #[inline]
+    pub fn to_step(&self) -> Step {
+        Step(self.step())
+    }
+
+    /// Return the unit value of the equivalent frequency.
+    #[inline]
+    pub fn hz(&self) -> calc::Hz {
+        self.to_step().hz()
+    }
+
+    /// Convert to the equivalent frequency.
+    #[inline]
+    pub fn to_hz(&self) -> Hz {
+        Hz(self.hz())
+    }
+
+    /// Convert to the equivalent LetterOctave.
+    #[inline]
+    pub fn to_letter_octave(&self) -> LetterOctave {
+        self.to_step().to_letter_octave()
+    }
+
+}
+
+impl TryFrom<u8> for MidiNote {
+    type Error = Error;
+    /// Fails with `Error::OutOfRange` for numbers greater than `127`.
+    #[inline]
+    fn try_from(note: u8) -> Result<MidiNote, Error> {
+        MidiNote::new(note).ok_or(Error::OutOfRange)
+    }
+}
+
+impl TryFrom<Step> for MidiNote {
+    type Error = Error;
+    /// Rounds to the closest MidiNote, failing with `Error::OutOfRange` for pitches outside of
+    /// the MIDI note range and `Error::InvalidValue` for `NaN`.
+    fn try_from(step: Step) -> Result<MidiNote, Error> {
+        if step.step().is_nan() {
+            return Err(Error::InvalidValue);
+        }
+        MidiNote::checked_from_step(step).ok_or(Error::OutOfRange)
+    }
+}
+
+/// The MIDI note and pitch bend that best reproduce the given pitch, where the synthesizer bends
+/// by `bend_range` semitones in either direction.
+///
+/// The note is the closest MIDI note to the pitch, leaving the smallest possible bend. Pitches
+/// outside of the MIDI note range are clamped to the nearest note that bends as far towards them
+/// as possible.
+pub fn note_and_bend<P: Into<Step>>(pitch: P, bend_range: calc::Step) -> (u8, PitchBend) {
+    let step = pitch.into().step();
+    let note = step.round().clamp(0.0, 127.0);
+    let bend = BEND_CENTER as calc::Step + (step - note) / bend_range * BEND_CENTER as calc::Step;
+    (note as u8, bend.round().clamp(0.0, BEND_MAX as calc::Step) as PitchBend)
+}
+
+/// The pitch sounded by the given MIDI note bent by the given pitch bend value, where the
+/// synthesizer bends by `bend_range` semitones in either direction.
+#[inline]
+pub fn step_from_note_and_bend(note: u8, bend: PitchBend, bend_range: calc::Step) -> Step {
+    let offset = (bend as calc::Step - BEND_CENTER as calc::Step) / BEND_CENTER as calc::Step;
+    Step(note as calc::Step + offset * bend_range)
+}
+
+/// The frequency sounded by the given MIDI note bent by the given pitch bend value.
+#[inline]
+pub fn hz_from_note_and_bend(note: u8, bend: PitchBend, bend_range: calc::Step) -> Hz {
+    step_from_note_and_bend(note, bend, bend_range).to_hz()
+}
+
+/// A pitch prepared for playback over MIDI, along with the channel it should be played on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ChannelNote {
+    pub note: u8,
+    pub bend: PitchBend,
+    pub channel: u8,
+}
+
+/// Prepare a group of simultaneous pitches for playback using the given channels (e.g. `1..16`
+/// to leave channel `0` free as an MPE-style master channel).
+///
+/// As pitch bend applies to an entire channel, pitches are only placed on the same channel when
+/// they require exactly the same bend. When there are more distinct bends than channels, the
+/// remaining pitches share the channel whose bend is closest to their own.
+///
+/// Returns `Error::OutOfRange` if the range of channels is empty or extends beyond the sixteen
+/// MIDI channels, as no pitch could be placed.
+pub fn assign_channels<I, P>(pitches: I, bend_range: calc::Step, channels: ::std::ops::Range<u8>)
+    -> Result<Vec<ChannelNote>, Error>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    if channels.start >= channels.end || channels.end > 16 {
+        return Err(Error::OutOfRange);
+    }
+    let channels: Vec<u8> = channels.collect();
+    let mut bends: Vec<(u8, PitchBend)> = Vec::with_capacity(channels.len());
+    let mut assigned = Vec::new();
+    for pitch in pitches {
+        let (note, bend) = note_and_bend(pitch, bend_range);
+        let channel = match bends.iter().find(|&&(_, b)| b == bend) {
+            Some(&(channel, _)) => Some(channel),
+            None if bends.len() < channels.len() => {
+                let channel = channels[bends.len()];
+                bends.push((channel, bend));
+                Some(channel)
+            },
+            None => bends.iter()
+                .min_by_key(|&&(_, b)| (b as i32 - bend as i32).abs())
+                .map(|&(channel, _)| channel),
+        };
+        if let Some(channel) = channel {
+            let bend = bends.iter().find(|&&(c, _)| c == channel).map(|&(_, b)| b).unwrap();
+            assigned.push(ChannelNote { note, bend, channel });
+        }
+    }
+    Ok(assigned)
+}
+
+/// The pitch of a single note played by an MPE (MIDI Polyphonic Expression) controller or
+/// synthesizer: a base MIDI note along with its own per-note pitch bend and bend range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct MpeNote {
+    /// The MIDI note number sent with the note-on message.
+    pub note: u8,
+    /// The per-note 14-bit pitch bend.
+    pub bend: PitchBend,
+    /// The distance that a full pitch bend bends the note in either direction, in semitones.
+    pub bend_range: calc::Step,
+}
+
+impl MpeNote {
+
+    /// The MpeNote that best reproduces the given pitch with the given bend range.
+    pub fn from_step<P: Into<Step>>(pitch: P, bend_range: calc::Step) -> MpeNote {
+        let (note, bend) = note_and_bend(pitch, bend_range);
+        MpeNote { note, bend, bend_range }
+    }
+
+    /// The MpeNote that best reproduces the given pitch with the default MPE bend range.
+    #[inline]
+    pub fn from_hz<H: Into<Hz>>(hz: H) -> MpeNote {
+        MpeNote::from_step(hz.into(), MPE_BEND_RANGE)
+    }
+
+    /// The same note bent towards the given pitch, keeping the base note (e.g. when the player
+    /// slides their finger after the note-on).
+    pub fn bent_to<P: Into<Step>>(&self, pitch: P) -> MpeNote {
+        let offset = (pitch.into().step() - self.note as calc::Step) / self.bend_range;
+        let bend = BEND_CENTER as calc::Step * (1.0 + offset);
+        MpeNote { bend: bend.round().clamp(0.0, BEND_MAX as calc::Step) as PitchBend, ..*self }
+    }
+
+    /// The current bend of the note in semitones.
+    #[inline]
+    pub fn bend_semitones(&self) -> calc::Step {
+        self.step() - self.note as calc::Step
+    }
+
+    /// Return the unit value of the sounding Step.
+    #[inline]
+    pub fn step(&self) -> calc::Step {
+        step_from_note_and_bend(self.note, self.bend, self.bend_range).step()
+    }
+
+    /// Convert to the sounding Step.
+    #[inline]
+    pub fn to_step(&self) -> Step {
+        Step(self.step())
+    }
+
+    /// Return the unit value of the sounding frequency.
+    #[inline]
+    pub fn hz(&self) -> calc::Hz {
+        self.to_step().hz()
+    }
+
+    /// Convert to the sounding frequency.
+    #[inline]
+    pub fn to_hz(&self) -> Hz {
+        Hz(self.hz())
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn midi_note_range() {
+        assert_eq!(MidiNote::new(60).map(|n| n.number()), Some(60));
+        assert_eq!(MidiNote::new(128), None);
+        assert_eq!(MidiNote::saturating(200), MidiNote::MAX);
+        assert_eq!(MidiNote::try_from(Step(60.4)), Ok(MidiNote(60)));
+        assert_eq!(MidiNote::try_from(Step(127.6)), Err(Error::OutOfRange));
+        assert_eq!(MidiNote::try_from(Step(-0.6)), Err(Error::OutOfRange));
+        assert_eq!(MidiNote::try_from(Step(calc::Step::NAN)), Err(Error::InvalidValue));
+        assert_eq!(MidiNote::try_from(130u8), Err(Error::OutOfRange));
+        assert_eq!(MidiNote::saturating_from_step(Hz(20_000.0)), MidiNote::MAX);
+        assert_eq!(MidiNote::saturating_from_step(Hz(1.0)), MidiNote::MIN);
+        assert_eq!(Step::from(MidiNote(69)), Step(69.0));
+        assert_eq!(MidiNote(69).to_hz(), Hz(440.0));
+    }
+
+    #[test]
+    fn note_and_bend_round_trip() {
+        assert_eq!(note_and_bend(Hz(440.0), DEFAULT_BEND_RANGE), (69, BEND_CENTER));
+        let (note, bend) = note_and_bend(Step(60.5), DEFAULT_BEND_RANGE);
+        assert_eq!((note, bend), (61, 6_144));
+        let step = step_from_note_and_bend(note, bend, DEFAULT_BEND_RANGE);
+        assert!((step.step() - 60.5).abs() < 0.001);
+        assert_eq!(note_and_bend(Step(-5.0), DEFAULT_BEND_RANGE), (0, 0));
+        assert_eq!(note_and_bend(Step(140.0), 48.0).0, 127);
+    }
+
+    #[test]
+    fn channel_assignment() {
+        let pitches = vec![Step(60.0), Step(64.0), Step(60.5), Step(67.25)];
+        let assigned = assign_channels(pitches.clone(), DEFAULT_BEND_RANGE, 1..3).unwrap();
+        assert_eq!(assigned[0].channel, assigned[1].channel);
+        assert!(assigned[2].channel != assigned[0].channel);
+        // Out of channels, so the slightly sharp G shares the closest bend.
+        assert_eq!(assigned[3].channel, assigned[0].channel);
+        assert!(assigned.iter().all(|n| n.channel >= 1 && n.channel < 3));
+        assert_eq!(assign_channels(pitches.clone(), DEFAULT_BEND_RANGE, 0..16).unwrap().len(), 4);
+        // Without channels no pitch could be placed.
+        assert_eq!(assign_channels(pitches.clone(), DEFAULT_BEND_RANGE, 3..3),
+                   Err(Error::OutOfRange));
+        assert_eq!(assign_channels(pitches, DEFAULT_BEND_RANGE, 1..17), Err(Error::OutOfRange));
+        let none: Vec<Step> = vec![];
+        assert_eq!(assign_channels(none, DEFAULT_BEND_RANGE, 0..1), Ok(vec![]));
+    }
+
+    #[test]
+    fn mpe_note() {
+        let note = MpeNote::from_hz(Hz(440.0));
+        assert_eq!((note.note, note.bend), (69, BEND_CENTER));
+        let slid = note.bent_to(Step(71.5));
+        assert_eq!(slid.note, 69);
+        assert!((slid.bend_semitones() - 2.5).abs() < 0.01);
+        assert!((MpeNote::from_step(Step(30.3), MPE_BEND_RANGE).step() - 30.3).abs() < 0.01);
+    }
+}
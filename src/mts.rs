@@ -0,0 +1,282 @@
+//! Generation and parsing of MIDI Tuning Standard (MTS) system exclusive messages.
+//!
+//! Both the bulk tuning dump (which retunes all 128 notes of a tuning program) and the real-time
+//! single note tuning change (which retunes selected notes) are supported, allowing tunings to be
+//! round-tripped with hardware.
+
+use super::{
+    calc,
+    Error,
+    Step,
+    TuningError,
+};
+
+/// A tuning table mapping each of the 128 MIDI notes to the pitch it should sound.
+pub type TuningTable = [calc::Step; 128];
+
+/// The start of a system exclusive message.
+const SYSEX_START: u8 = 0xF0;
+/// The end of a system exclusive message.
+const SYSEX_END: u8 = 0xF7;
+/// The universal non-real-time sysex ID.
+const NON_REAL_TIME: u8 = 0x7E;
+/// The universal real-time sysex ID.
+const REAL_TIME: u8 = 0x7F;
+/// The sub-ID #1 of MIDI tuning messages.
+const MIDI_TUNING: u8 = 0x08;
+/// The sub-ID #2 of the bulk tuning dump.
+const BULK_DUMP: u8 = 0x01;
+/// The sub-ID #2 of the single note tuning change.
+const SINGLE_NOTE_CHANGE: u8 = 0x02;
+/// The number of characters within a tuning program name.
+const NAME_LEN: usize = 16;
+/// The frequency data reserved to mean "no change".
+const NO_CHANGE: [u8; 3] = [0x7F, 0x7F, 0x7F];
+/// The resolution of the fraction of a semitone within the frequency data.
+const FRACTION_STEPS: f32 = 16_384.0;
+
+/// The 12-TET tuning table, in which every note sounds its own step.
+pub fn equal_tuning_table() -> TuningTable {
+    let mut table = [0.0; 128];
+    for (note, step) in table.iter_mut().enumerate() {
+        *step = note as calc::Step;
+    }
+    table
+}
+
+/// Encode a pitch as the three bytes of MTS frequency data: the semitone below the pitch followed
+/// by the remaining fraction of a semitone in 14 bits.
+///
+/// Pitches are clamped to the range representable by MTS (MIDI notes `0` to `127`).
+pub fn encode_frequency<P: Into<Step>>(pitch: P) -> [u8; 3] {
+    let highest = 127.0 + (FRACTION_STEPS - 2.0) / FRACTION_STEPS;
+    let step = pitch.into().step().clamp(0.0, highest);
+    let mut semitone = step.floor() as u32;
+    let mut fraction = ((step - semitone as calc::Step) * FRACTION_STEPS).round() as u32;
+    if fraction == FRACTION_STEPS as u32 {
+        semitone += 1;
+        fraction = 0;
+    }
+    [semitone as u8, (fraction >> 7) as u8, (fraction & 0x7F) as u8]
+}
+
+/// Decode three bytes of MTS frequency data into a pitch, or `None` if the data means that the
+/// note should not be changed.
+pub fn decode_frequency(data: [u8; 3]) -> Option<Step> {
+    if data == NO_CHANGE {
+        return None;
+    }
+    let fraction = ((data[1] as u32) << 7) | data[2] as u32;
+    Some(Step(data[0] as calc::Step + fraction as calc::Step / FRACTION_STEPS))
+}
+
+/// Ensure that every byte is a valid 7-bit data byte.
+fn check_data(bytes: &[u8]) -> Result<(), Error> {
+    if bytes.iter().any(|&b| b > 0x7F) { Err(TuningError::InvalidData.into()) } else { Ok(()) }
+}
+
+/// The checksum of a bulk tuning dump: the XOR of every byte between the sysex start and the
+/// checksum itself.
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |sum, b| sum ^ b) & 0x7F
+}
+
+/// A bulk tuning dump, retuning all 128 notes of a tuning program.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BulkDump {
+    /// The sysex device ID (`0x7F` to address all devices).
+    pub device_id: u8,
+    /// The tuning program number.
+    pub program: u8,
+    /// The name of the tuning, at most 16 ASCII characters.
+    pub name: String,
+    /// The pitch of each note, or `None` where the note should not be changed.
+    pub notes: Vec<Option<Step>>,
+}
+
+impl BulkDump {
+
+    /// A bulk tuning dump retuning every note to the given tuning table.
+    pub fn new(device_id: u8, program: u8, name: &str, table: &TuningTable) -> BulkDump {
+        BulkDump {
+            device_id,
+            program,
+            name: name.to_string(),
+            notes: table.iter().map(|&step| Some(Step(step))).collect(),
+        }
+    }
+
+    /// The tuning table described by the dump. Notes that are not changed sound their 12-TET
+    /// pitch.
+    pub fn tuning_table(&self) -> TuningTable {
+        let mut table = equal_tuning_table();
+        for (entry, note) in table.iter_mut().zip(self.notes.iter()) {
+            if let Some(step) = *note {
+                *entry = step.step();
+            }
+        }
+        table
+    }
+
+    /// Encode the dump as a complete sysex message.
+    ///
+    /// The name is truncated or padded with spaces to 16 characters, and any non-ASCII
+    /// characters are replaced by `?`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![SYSEX_START, NON_REAL_TIME, self.device_id & 0x7F, MIDI_TUNING,
+                             BULK_DUMP, self.program & 0x7F];
+        let name = self.name.chars()
+            .map(|c| if c.is_ascii() && !c.is_ascii_control() { c as u8 } else { b'?' })
+            .chain(::std::iter::repeat(b' '))
+            .take(NAME_LEN);
+        bytes.extend(name);
+        for note in 0..128 {
+            let data = match self.notes.get(note) {
+                Some(&Some(step)) => encode_frequency(step),
+                _ => NO_CHANGE,
+            };
+            bytes.extend_from_slice(&data);
+        }
+        let sum = checksum(&bytes[1..]);
+        bytes.push(sum);
+        bytes.push(SYSEX_END);
+        bytes
+    }
+
+    /// Parse a bulk tuning dump sysex message.
+    pub fn from_bytes(bytes: &[u8]) -> Result<BulkDump, Error> {
+        const LEN: usize = 6 + NAME_LEN + 128 * 3 + 2;
+        if bytes.len() < 6 || bytes[0] != SYSEX_START || bytes[1] != NON_REAL_TIME
+            || bytes[3] != MIDI_TUNING || bytes[4] != BULK_DUMP {
+            return Err(TuningError::InvalidHeader.into());
+        }
+        if bytes.len() != LEN || bytes[LEN - 1] != SYSEX_END {
+            return Err(TuningError::InvalidLength.into());
+        }
+        check_data(&bytes[1..LEN - 1])?;
+        if checksum(&bytes[1..LEN - 2]) != bytes[LEN - 2] {
+            return Err(TuningError::InvalidChecksum.into());
+        }
+        let name = bytes[6..6 + NAME_LEN].iter().map(|&b| b as char).collect::<String>();
+        let notes = bytes[6 + NAME_LEN..LEN - 2].chunks(3)
+            .map(|data| decode_frequency([data[0], data[1], data[2]]))
+            .collect();
+        Ok(BulkDump {
+            device_id: bytes[2],
+            program: bytes[5],
+            name: name.trim_end().to_string(),
+            notes,
+        })
+    }
+
+}
+
+/// A real-time single note tuning change, retuning selected notes of a tuning program.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SingleNoteChange {
+    /// The sysex device ID (`0x7F` to address all devices).
+    pub device_id: u8,
+    /// The tuning program number.
+    pub program: u8,
+    /// Each retuned MIDI note along with its new pitch, or `None` where the note should not be
+    /// changed.
+    pub changes: Vec<(u8, Option<Step>)>,
+}
+
+impl SingleNoteChange {
+
+    /// Apply the changes to the given tuning table.
+    pub fn apply(&self, table: &mut TuningTable) {
+        for &(note, step) in &self.changes {
+            if let (Some(entry), Some(step)) = (table.get_mut(note as usize), step) {
+                *entry = step.step();
+            }
+        }
+    }
+
+    /// Encode the change as a complete sysex message.
+    ///
+    /// Panics if there are more than 127 changes, the most a single message can hold.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        assert!(self.changes.len() <= 0x7F, "too many changes for a single MTS message");
+        let mut bytes = vec![SYSEX_START, REAL_TIME, self.device_id & 0x7F, MIDI_TUNING,
+                             SINGLE_NOTE_CHANGE, self.program & 0x7F, self.changes.len() as u8];
+        for &(note, step) in &self.changes {
+            bytes.push(note & 0x7F);
+            bytes.extend_from_slice(&step.map_or(NO_CHANGE, encode_frequency));
+        }
+        bytes.push(SYSEX_END);
+        bytes
+    }
+
+    /// Parse a single note tuning change sysex message.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SingleNoteChange, Error> {
+        if bytes.len() < 7 || bytes[0] != SYSEX_START || bytes[1] != REAL_TIME
+            || bytes[3] != MIDI_TUNING || bytes[4] != SINGLE_NOTE_CHANGE {
+            return Err(TuningError::InvalidHeader.into());
+        }
+        let count = bytes[6] as usize;
+        let len = 7 + count * 4 + 1;
+        if bytes.len() != len || bytes[len - 1] != SYSEX_END {
+            return Err(TuningError::InvalidLength.into());
+        }
+        check_data(&bytes[1..len - 1])?;
+        let changes = bytes[7..len - 1].chunks(4)
+            .map(|change| (change[0], decode_frequency([change[1], change[2], change[3]])))
+            .collect();
+        Ok(SingleNoteChange { device_id: bytes[2], program: bytes[5], changes })
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequency_data() {
+        assert_eq!(encode_frequency(Step(69.0)), [69, 0, 0]);
+        assert_eq!(encode_frequency(Step(60.5)), [60, 0x40, 0]);
+        assert_eq!(decode_frequency([60, 0x40, 0]), Some(Step(60.5)));
+        assert_eq!(decode_frequency(NO_CHANGE), None);
+        assert!(encode_frequency(Step(200.0)) != NO_CHANGE);
+    }
+
+    #[test]
+    fn bulk_dump_round_trip() {
+        let mut table = equal_tuning_table();
+        for (note, step) in table.iter_mut().enumerate() {
+            *step += (note % 3) as calc::Step * 0.25;
+        }
+        table[127] = 127.0;
+        let dump = BulkDump::new(0x7F, 3, "Quarter tones", &table);
+        let bytes = dump.to_bytes();
+        assert_eq!(bytes.len(), 408);
+        let parsed = BulkDump::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.name, "Quarter tones");
+        assert_eq!(parsed.program, 3);
+        for (a, b) in parsed.tuning_table().iter().zip(table.iter()) {
+            assert!((a - b).abs() < 0.0001);
+        }
+        let mut corrupt = bytes.clone();
+        corrupt[30] ^= 1;
+        assert_eq!(BulkDump::from_bytes(&corrupt), Err(TuningError::InvalidChecksum.into()));
+        assert_eq!(BulkDump::from_bytes(&bytes[..100]), Err(TuningError::InvalidLength.into()));
+        assert_eq!(SingleNoteChange::from_bytes(&bytes), Err(TuningError::InvalidHeader.into()));
+    }
+
+    #[test]
+    fn single_note_change_round_trip() {
+        let change = SingleNoteChange {
+            device_id: 0x7F,
+            program: 0,
+            changes: vec![(60, Some(Step(60.5))), (64, Some(Step(63.86))), (67, None)],
+        };
+        let parsed = SingleNoteChange::from_bytes(&change.to_bytes()).unwrap();
+        let mut table = equal_tuning_table();
+        parsed.apply(&mut table);
+        assert_eq!(table[60], 60.5);
+        assert!((table[64] - 63.86).abs() < 0.0001);
+        assert_eq!(table[67], 67.0);
+    }
+}
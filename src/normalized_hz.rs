@@ -0,0 +1,94 @@
+//! Frequencies normalized to a sample rate, as used throughout digital signal processing.
+
+use std::f64::consts::PI;
+use super::{
+    Hz,
+    Step,
+};
+
+/// A frequency as a fraction of the sample rate in cycles per sample, lying between `0.0` and
+/// `0.5` (the Nyquist frequency) for frequencies that can be represented.
+///
+/// Unlike the other representations a NormalizedHz only describes a pitch alongside a sample
+/// rate, so it is not a `Pitch` and each conversion takes the sample rate explicitly.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct NormalizedHz(pub f64);
+
+/// The Nyquist frequency, above which frequencies alias.
+pub const NYQUIST: NormalizedHz = NormalizedHz(0.5);
+
+impl NormalizedHz {
+
+    /// Return the frequency in cycles per sample.
+    #[inline]
+    pub fn normalized_hz(&self) -> f64 {
+        let NormalizedHz(normalized_hz) = *self;
+        normalized_hz
+    }
+
+    /// The normalized frequency of the given frequency at the given sample rate.
+    #[inline]
+    pub fn from_hz(hz: Hz, sample_rate: f64) -> NormalizedHz {
+        NormalizedHz(hz.hz() as f64 / sample_rate)
+    }
+
+    /// The normalized frequency of the given angular frequency in radians per sample, lying
+    /// between `0.0` and `π` for frequencies below Nyquist.
+    #[inline]
+    pub fn from_radians(radians: f64) -> NormalizedHz {
+        NormalizedHz(radians / (2.0 * PI))
+    }
+
+    /// Convert to the angular frequency in radians per sample.
+    #[inline]
+    pub fn radians(&self) -> f64 {
+        self.normalized_hz() * 2.0 * PI
+    }
+
+    /// Convert to hz at the given sample rate.
+    #[inline]
+    pub fn hz(&self, sample_rate: f64) -> f32 {
+        (self.normalized_hz() * sample_rate) as f32
+    }
+
+    /// Convert to a Hz struct at the given sample rate.
+    #[inline]
+    pub fn to_hz(&self, sample_rate: f64) -> Hz {
+        Hz(self.hz(sample_rate))
+    }
+
+    /// Convert to a Step at the given sample rate.
+    #[inline]
+    pub fn to_step(&self, sample_rate: f64) -> Step {
+        self.to_hz(sample_rate).to_step()
+    }
+
+    /// Whether or not the frequency lies between zero and the Nyquist frequency, inclusive.
+    #[inline]
+    pub fn is_below_nyquist(&self) -> bool {
+        self.normalized_hz() >= 0.0 && self.normalized_hz() <= NYQUIST.normalized_hz()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{Letter, LetterOctave};
+
+    #[test]
+    fn sample_rate_conversions() {
+        let a4 = NormalizedHz::from_hz(Hz(440.0), 44_100.0);
+        assert!((a4.normalized_hz() - 0.009977).abs() < 0.000001);
+        assert_eq!(a4.to_hz(44_100.0), Hz(440.0));
+        assert_eq!(a4.to_step(44_100.0).to_letter_octave(), LetterOctave(Letter::A, 4));
+        // The same normalized frequency an octave up at twice the sample rate.
+        assert_eq!(a4.to_hz(88_200.0), Hz(880.0));
+        assert!((NormalizedHz::from_radians(PI).normalized_hz() - 0.5).abs() < 1e-12);
+        assert!((NYQUIST.radians() - PI).abs() < 1e-12);
+        assert_eq!(NYQUIST.to_hz(48_000.0), Hz(24_000.0));
+        assert!(a4.is_below_nyquist());
+        assert!(!NormalizedHz::from_hz(Hz(30_000.0), 48_000.0).is_below_nyquist());
+    }
+}
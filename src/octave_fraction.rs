@@ -0,0 +1,288 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
+use super::{
+    calc,
+    DEFAULT_SCALE_WEIGHT,
+    Hz,
+    LetterOctave,
+    Letter,
+    LogPerc,
+    Mel,
+    Octave,
+    Perc,
+    ScaledPerc,
+    ScaleWeight,
+    Spelling,
+    Step,
+    hz_from_octave_fraction,
+    letter_octave_from_step,
+    log_perc_from_step,
+    mel_from_step,
+    perc_from_step,
+    scaled_perc_from_step,
+    step_from_octave_fraction,
+};
+
+/// Pitch representation in the form of a fractional octave number, where each whole number is
+/// the `C` that begins that octave in scientific pitch notation (e.g. `4.0` is `C 4` and `4.75` is
+/// `A 4`).
+///
+/// As octaves are evenly spaced in log-frequency, this is a natural coordinate for log-frequency
+/// plots and spectrogram axes.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct OctaveFraction(pub calc::OctaveFraction);
+
+impl OctaveFraction {
+
+    /// Return the unit value of the OctaveFraction struct.
+    #[inline]
+    pub fn octave_fraction(&self) -> calc::OctaveFraction {
+        let OctaveFraction(octave) = *self;
+        octave
+    }
+
+    /// Convert to hz.
+    #[inline]
+    pub fn hz(&self) -> calc::Hz {
+        hz_from_octave_fraction(self.octave_fraction())
+    }
+
+    /// Convert to a Hz struct.
+    #[inline]
+    pub fn to_hz(&self) -> Hz {
+        Hz(self.hz())
+    }
+
+    /// Convert to (Letter, Octave) tuple.
+    #[inline]
+    pub fn letter_octave(&self) -> (Letter, Octave) {
+        letter_octave_from_step(self.step())
+    }
+
+    /// Convert to Letter.
+    #[inline]
+    pub fn letter(&self) -> Letter {
+        let (letter, _) = self.letter_octave();
+        letter
+    }
+
+    /// Convert to Octave.
+    #[inline]
+    pub fn octave(&self) -> Octave {
+        let (_, octave) = self.letter_octave();
+        octave
+    }
+
+    /// Convert to LetterOctave struct with the closest pitch.
+    #[inline]
+    pub fn to_letter_octave(&self) -> LetterOctave {
+        let (letter, octave) = self.letter_octave();
+        LetterOctave(letter, octave)
+    }
+
+    /// Convert to the closest LetterOctave, spelling any black key with the given preference.
+    #[inline]
+    pub fn to_letter_octave_with_spelling(&self, spelling: Spelling) -> LetterOctave {
+        self.to_letter_octave().spelled(spelling)
+    }
+
+    /// Convert to a mel unit value.
+    #[inline]
+    pub fn mel(&self) -> calc::Mel {
+        mel_from_step(self.step())
+    }
+
+    /// Convert to a Mel struct.
+    #[inline]
+    pub fn to_mel(&self) -> Mel {
+        Mel(self.mel())
+    }
+
+    /// Convert to a percentage of the human hearing range.
+    #[inline]
+    pub fn perc(&self) -> calc::Perc {
+        perc_from_step(self.step())
+    }
+
+    /// Convert to a Perc struct.
+    #[inline]
+    pub fn to_perc(&self) -> Perc {
+        Perc(self.perc())
+    }
+
+    /// Convert to the unit value of a percentage of the human hearing range in log-frequency
+    /// space.
+    #[inline]
+    pub fn log_perc(&self) -> calc::Perc {
+        log_perc_from_step(self.step())
+    }
+
+    /// Convert to a percentage of the human hearing range in log-frequency space.
+    #[inline]
+    pub fn to_log_perc(&self) -> LogPerc {
+        LogPerc(self.log_perc())
+    }
+
+    /// Convert to a scaled percentage of the human hearing range with a given weight.
+    #[inline]
+    pub fn scaled_perc_with_weight(&self, weight: ScaleWeight) -> calc::Perc {
+        scaled_perc_from_step(self.step(), weight)
+    }
+
+    /// Convert to a scaled percentage of the human hearing range.
+    #[inline]
+    pub fn scaled_perc(&self) -> calc::Perc {
+        self.scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
+    }
+
+    /// Convert to a scaled percentage of the human hearing range with a given weight.
+    #[inline]
+    pub fn to_scaled_perc_with_weight(&self, weight: ScaleWeight) -> ScaledPerc {
+        ScaledPerc(self.scaled_perc_with_weight(weight), weight)
+    }
+
+    /// Convert to a scaled percentage of the human hearing range.
+    #[inline]
+    pub fn to_scaled_perc(&self) -> ScaledPerc {
+        self.to_scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
+    }
+
+    /// Convert to the unit value of a Step.
+    #[inline]
+    pub fn step(&self) -> calc::Step {
+        step_from_octave_fraction(self.octave_fraction())
+    }
+
+    /// Convert to a Step struct.
+    #[inline]
+    pub fn to_step(&self) -> Step {
+        Step(self.step())
+    }
+
+    /// The lower of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn min(self, other: OctaveFraction) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction().min(other.octave_fraction()))
+    }
+
+    /// The higher of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn max(self, other: OctaveFraction) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction().max(other.octave_fraction()))
+    }
+
+    /// The pitch limited to the range `min` to `max`. Never panics, even on a `NaN` pitch or
+    /// bound (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn clamp(self, min: OctaveFraction, max: OctaveFraction) -> OctaveFraction {
+        self.max(min).min(max)
+    }
+
+}
+
+impl fmt::Display for OctaveFraction {
+    /// Formats the value in octaves, appending the unit with the alternate flag (e.g. `4.75 oct`).
+    /// Any precision or width is applied to the number.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&(self.octave_fraction()), f)?;
+        if f.alternate() {
+            f.write_str(" oct")?;
+        }
+        Ok(())
+    }
+}
+
+impl Add for OctaveFraction {
+    type Output = OctaveFraction;
+    #[inline]
+    fn add(self, rhs: OctaveFraction) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction() + rhs.octave_fraction())
+    }
+}
+
+impl Sub for OctaveFraction {
+    type Output = OctaveFraction;
+    #[inline]
+    fn sub(self, rhs: OctaveFraction) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction() - rhs.octave_fraction())
+    }
+}
+
+impl Mul for OctaveFraction {
+    type Output = OctaveFraction;
+    #[inline]
+    fn mul(self, rhs: OctaveFraction) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction() * rhs.octave_fraction())
+    }
+}
+
+impl Div for OctaveFraction {
+    type Output = OctaveFraction;
+    #[inline]
+    fn div(self, rhs: OctaveFraction) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction() / rhs.octave_fraction())
+    }
+}
+
+impl Rem for OctaveFraction {
+    type Output = OctaveFraction;
+    #[inline]
+    fn rem(self, rhs: OctaveFraction) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction() % rhs.octave_fraction())
+    }
+}
+
+impl Neg for OctaveFraction {
+    type Output = OctaveFraction;
+    #[inline]
+    fn neg(self) -> OctaveFraction {
+        OctaveFraction(-self.octave_fraction())
+    }
+}
+
+impl PartialEq for OctaveFraction {
+    #[inline]
+    fn eq(&self, other: &OctaveFraction) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OctaveFraction {}
+
+impl Hash for OctaveFraction {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.octave_fraction().to_bits().hash(state);
+    }
+}
+
+impl PartialOrd for OctaveFraction {
+    #[inline]
+    fn partial_cmp(&self, other: &OctaveFraction) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Ordered by the [total order](crate#comparison) shared by the float pitch types.
+impl Ord for OctaveFraction {
+    #[inline]
+    fn cmp(&self, other: &OctaveFraction) -> Ordering {
+        self.octave_fraction().total_cmp(&other.octave_fraction())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", OctaveFraction(4.75)), "4.75");
+        assert_eq!(format!("{:#}", OctaveFraction(4.75)), "4.75 oct");
+        assert_eq!(format!("{:#.1}", OctaveFraction(4.75)), "4.8 oct");
+        assert_eq!(format!("{:>#6.1}", OctaveFraction(4.75)), "   4.8 oct");
+    }
+}
@@ -1,19 +1,28 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
 use super::{
     calc,
+    Curve,
     DEFAULT_SCALE_WEIGHT,
     Hz,
     LetterOctave,
     Letter,
+    LogPerc,
     Mel,
     Octave,
+    OctaveFraction,
     ScaledPerc,
     ScaleWeight,
+    Spelling,
     Step,
     hz_from_perc,
+    hz_from_perc_with_range,
     letter_octave_from_perc,
+    log_perc_from_hz,
     mel_from_perc,
+    octave_fraction_from_step,
     scaled_perc_from_perc,
     step_from_perc,
 };
@@ -42,6 +51,34 @@ impl Perc {
         Hz(self.hz())
     }
 
+    /// Convert to the unit value of the equivalent frequency in Hz, treating the percentage as a
+    /// percentage of the range between `min` and `max` hz rather than the human hearing range.
+    #[inline]
+    pub fn hz_with_range(&self, min: calc::Hz, max: calc::Hz) -> calc::Hz {
+        hz_from_perc_with_range(self.perc(), min, max)
+    }
+
+    /// Convert to the equivalent frequency in Hz, treating the percentage as a percentage of the
+    /// range between `min` and `max` hz rather than the human hearing range.
+    #[inline]
+    pub fn to_hz_with_range(&self, min: calc::Hz, max: calc::Hz) -> Hz {
+        Hz(self.hz_with_range(min, max))
+    }
+
+    /// Convert to the unit value of the equivalent frequency in Hz, treating the percentage as a
+    /// control position mapped onto the human hearing range by the given response curve.
+    #[inline]
+    pub fn hz_with_curve(&self, curve: Curve) -> calc::Hz {
+        curve.hz(self.perc())
+    }
+
+    /// Convert to the equivalent frequency in Hz, treating the percentage as a control position
+    /// mapped onto the human hearing range by the given response curve.
+    #[inline]
+    pub fn to_hz_with_curve(&self, curve: Curve) -> Hz {
+        Hz(self.hz_with_curve(curve))
+    }
+
     /// Convert to a (Letter, Octave).
     #[inline]
     pub fn letter_octave(&self) -> (Letter, Octave) {
@@ -69,6 +106,25 @@ impl Perc {
         LetterOctave(letter, octave)
     }
 
+    /// Convert to the closest LetterOctave, spelling any black key with the given preference.
+    #[inline]
+    pub fn to_letter_octave_with_spelling(&self, spelling: Spelling) -> LetterOctave {
+        self.to_letter_octave().spelled(spelling)
+    }
+
+    /// Convert to the unit value of a percentage of the human hearing range in log-frequency
+    /// space.
+    #[inline]
+    pub fn log_perc(&self) -> calc::Perc {
+        log_perc_from_hz(self.hz())
+    }
+
+    /// Convert to a percentage of the human hearing range in log-frequency space.
+    #[inline]
+    pub fn to_log_perc(&self) -> LogPerc {
+        LogPerc(self.log_perc())
+    }
+
     /// Convert to the unit value of a Mel.
     #[inline]
     pub fn mel(&self) -> calc::Mel {
@@ -117,6 +173,49 @@ impl Perc {
         Step(self.step())
     }
 
+    /// Convert to a fractional octave number.
+    #[inline]
+    pub fn octave_fraction(&self) -> calc::OctaveFraction {
+        octave_fraction_from_step(self.step())
+    }
+
+    /// Convert to an OctaveFraction struct.
+    #[inline]
+    pub fn to_octave_fraction(&self) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction())
+    }
+
+    /// The lower of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn min(self, other: Perc) -> Perc {
+        Perc(self.perc().min(other.perc()))
+    }
+
+    /// The higher of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn max(self, other: Perc) -> Perc {
+        Perc(self.perc().max(other.perc()))
+    }
+
+    /// The pitch limited to the range `min` to `max`. Never panics, even on a `NaN` pitch or
+    /// bound (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn clamp(self, min: Perc, max: Perc) -> Perc {
+        self.max(min).min(max)
+    }
+
+}
+
+impl fmt::Display for Perc {
+    /// Formats the value as a percentage (i.e. multiplied by `100`), appending the unit with the
+    /// alternate flag (e.g. `37.2 %`). Any precision or width is applied to the number.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&(self.perc() * 100.0), f)?;
+        if f.alternate() {
+            f.write_str(" %")?;
+        }
+        Ok(())
+    }
 }
 
 impl Add for Perc {
@@ -170,23 +269,63 @@ impl Neg for Perc {
 impl PartialEq for Perc {
     #[inline]
     fn eq(&self, other: &Perc) -> bool {
-        self.perc() == other.perc()
+        self.cmp(other) == Ordering::Equal
     }
 }
 
 impl Eq for Perc {}
 
+impl Hash for Perc {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.perc().to_bits().hash(state);
+    }
+}
+
 impl PartialOrd for Perc {
     #[inline]
     fn partial_cmp(&self, other: &Perc) -> Option<Ordering> {
-        self.perc().partial_cmp(&other.perc())
+        Some(self.cmp(other))
     }
 }
 
+/// Ordered by the [total order](crate#comparison) shared by the float pitch types.
 impl Ord for Perc {
     #[inline]
     fn cmp(&self, other: &Perc) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.perc().total_cmp(&other.perc())
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use calc::perc_from_hz_with_range;
+
+    #[test]
+    fn custom_range() {
+        // The range of a piano, from `A 0` to `C 8`.
+        let (min, max) = (27.5, 4_186.0);
+        assert!((Perc(0.0).hz_with_range(min, max) - min).abs() < 0.001);
+        assert!((Perc(1.0).hz_with_range(min, max) - max).abs() < 0.01);
+        assert!((Perc(0.5).hz_with_range(min, max) - 2_106.75).abs() < 0.01);
+        assert!((Hz(2_106.75).perc_with_range(min, max) - 0.5).abs() < 0.000_01);
+        for &hz in &[27.5, 440.0, 1_000.0, 4_186.0] {
+            let perc = Hz(hz).to_perc_with_range(min, max);
+            assert!((perc.to_hz_with_range(min, max).hz() - hz).abs() < 0.01);
+            assert!((hz_from_perc_with_range(perc.perc(), min, max) - hz).abs() < 0.01);
+        }
+        // The default range is the human hearing range.
+        assert_eq!(perc_from_hz_with_range(440.0, 20.0, 20_000.0), Hz(440.0).perc());
+        assert_eq!(Perc(0.25).hz_with_range(20.0, 20_000.0), Perc(0.25).hz());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Perc(0.5)), "50");
+        assert_eq!(format!("{:#}", Perc(0.5)), "50 %");
+        assert_eq!(format!("{:#.1}", Perc(0.372)), "37.2 %");
+        assert_eq!(format!("{:>#6.1}", Perc(0.5)), "  50.0 %");
+    }
+}
@@ -0,0 +1,152 @@
+//! Psychoacoustic limits of pitch perception: just-noticeable differences, auditory filter
+//! bandwidths and the tonotopic mapping of the cochlea.
+
+use super::{
+    calc,
+    cents_from_ratio,
+    Hz,
+};
+
+/// The frequency difference limen below roughly 500hz, in hz.
+const JND_LOW_HZ: calc::Hz = 3.6;
+/// The relative frequency difference limen above roughly 500hz.
+const JND_RATIO: calc::Hz = 0.007;
+
+/// The approximate just-noticeable difference in pitch at the given frequency, in cents.
+///
+/// Uses the frequency difference limen described by Zwicker & Fastl: a roughly constant 3.6hz
+/// below 500hz and 0.7% of the frequency above it.
+#[inline]
+pub fn jnd_at(hz: Hz) -> calc::Cents {
+    let f = hz.hz();
+    let delta = JND_LOW_HZ.max(f * JND_RATIO);
+    cents_from_ratio((f + delta) as f64 / f as f64)
+}
+
+/// Whether or not two pitches are far enough apart to be heard as different.
+///
+/// The just-noticeable difference is taken at the geometric mean of the two frequencies.
+pub fn is_distinguishable<A: Into<Hz>, B: Into<Hz>>(a: A, b: B) -> bool {
+    let (a, b) = (a.into().hz() as f64, b.into().hz() as f64);
+    let difference = cents_from_ratio(a / b).abs();
+    difference >= jnd_at(Hz((a * b).sqrt() as calc::Hz))
+}
+
+/// The critical bandwidth centred on the given frequency, after Zwicker & Terhardt (1980).
+#[inline]
+pub fn critical_bandwidth_at(hz: Hz) -> Hz {
+    let khz = hz.hz() / 1_000.0;
+    Hz(25.0 + 75.0 * (1.0 + 1.4 * khz * khz).powf(0.69))
+}
+
+/// The critical band rate (position on the Bark scale) of the given frequency, after Zwicker &
+/// Terhardt (1980).
+#[inline]
+pub fn bark_from_hz(hz: Hz) -> f32 {
+    let f = hz.hz();
+    13.0 * (0.000_76 * f).atan() + 3.5 * (f / 7_500.0).powi(2).atan()
+}
+
+/// The equivalent rectangular bandwidth of the auditory filter centred on the given frequency,
+/// after Glasberg & Moore (1990).
+#[inline]
+pub fn erb_at(hz: Hz) -> Hz {
+    Hz(24.7 * (4.37 * hz.hz() / 1_000.0 + 1.0))
+}
+
+/// The ERB-rate (number of ERBs below the given frequency), after Glasberg & Moore (1990).
+#[inline]
+pub fn erb_rate_from_hz(hz: Hz) -> f32 {
+    21.4 * (4.37 * hz.hz() / 1_000.0 + 1.0).log10()
+}
+
+/// The parameters of the Greenwood function `F = A * (10^(a * x) - k)`, which maps a relative
+/// position `x` along the basilar membrane (`0.0` at the apex, `1.0` at the base) to its
+/// characteristic frequency.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Greenwood {
+    /// The scaling constant `A` in hz.
+    pub scale: f32,
+    /// The slope `a`, for positions given as a proportion of the basilar membrane length.
+    pub slope: f32,
+    /// The integration constant `k`.
+    pub offset: f32,
+}
+
+/// Greenwood's (1990) parameters for the human cochlea.
+pub const HUMAN_GREENWOOD: Greenwood = Greenwood { scale: 165.4, slope: 2.1, offset: 0.88 };
+
+impl Greenwood {
+
+    /// The characteristic frequency at the given relative position along the basilar membrane.
+    #[inline]
+    pub fn hz(&self, position: f32) -> Hz {
+        Hz(self.scale * (10.0f32.powf(self.slope * position) - self.offset))
+    }
+
+    /// The relative position along the basilar membrane whose characteristic frequency is the
+    /// given frequency.
+    #[inline]
+    pub fn position(&self, hz: Hz) -> f32 {
+        (hz.hz() / self.scale + self.offset).log10() / self.slope
+    }
+
+}
+
+/// The relative position along the human basilar membrane (`0.0` at the apex, `1.0` at the
+/// base) that responds most strongly to the given frequency.
+#[inline]
+pub fn cochlear_position_from_hz(hz: Hz) -> f32 {
+    HUMAN_GREENWOOD.position(hz)
+}
+
+/// The characteristic frequency of the given relative position along the human basilar membrane.
+#[inline]
+pub fn hz_from_cochlear_position(position: f32) -> Hz {
+    HUMAN_GREENWOOD.hz(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn just_noticeable_difference() {
+        // A constant 3.6hz at low frequencies and 0.7% at high frequencies.
+        assert!((jnd_at(Hz(200.0)) - 30.885).abs() < 0.01);
+        assert!((jnd_at(Hz(1_000.0)) - 12.076).abs() < 0.01);
+        assert!(jnd_at(Hz(100.0)) > jnd_at(Hz(400.0)));
+        assert!(is_distinguishable(Hz(1_000.0), Hz(1_010.0)));
+        assert!(!is_distinguishable(Hz(1_000.0), Hz(1_005.0)));
+        assert!(!is_distinguishable(Hz(200.0), Hz(203.0)));
+        // The order of the pitches does not matter.
+        assert!(is_distinguishable(Hz(1_010.0), Hz(1_000.0)));
+    }
+
+    #[test]
+    fn auditory_filters() {
+        // Glasberg & Moore give an ERB of 132.6hz at 1khz.
+        assert!((erb_at(Hz(1_000.0)).hz() - 132.639).abs() < 0.01);
+        assert!((erb_rate_from_hz(Hz(1_000.0)) - 15.621).abs() < 0.01);
+        assert_eq!(erb_rate_from_hz(Hz(0.0)), 0.0);
+        // Zwicker & Terhardt give a critical bandwidth of about 160hz and 8.5 Bark at 1khz.
+        assert!((critical_bandwidth_at(Hz(1_000.0)).hz() - 162.217).abs() < 0.01);
+        assert!((critical_bandwidth_at(Hz(0.0)).hz() - 100.0).abs() < 0.001);
+        assert!((bark_from_hz(Hz(1_000.0)) - 8.511).abs() < 0.01);
+        assert!((bark_from_hz(Hz(15_500.0)) - 24.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn greenwood() {
+        // The human cochlea spans roughly 20hz at the apex to 20khz at the base.
+        assert!((hz_from_cochlear_position(0.0).hz() - 19.848).abs() < 0.01);
+        assert!((hz_from_cochlear_position(1.0).hz() - 20_677.07).abs() < 1.0);
+        for &hz in &[20.0, 100.0, 440.0, 1_000.0, 8_000.0, 20_000.0] {
+            let position = cochlear_position_from_hz(Hz(hz));
+            assert!((0.0..=1.0).contains(&position));
+            let round_trip = hz_from_cochlear_position(position).hz();
+            assert!((round_trip - hz).abs() / hz < 0.0001);
+        }
+        assert!(cochlear_position_from_hz(Hz(880.0)) > cochlear_position_from_hz(Hz(440.0)));
+    }
+}
@@ -0,0 +1,318 @@
+//! A trait shared by every pitch representation, allowing library code to accept any of them,
+//! along with helpers for measuring the distance between any two pitches.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use super::{
+    calc,
+    AnyPitch,
+    Hz,
+    LetterOctave,
+    LogPerc,
+    Mel,
+    MidiNote,
+    MpeNote,
+    OctaveFraction,
+    Perc,
+    PitchDescription,
+    QuarterTone,
+    ScaledPerc,
+    Step,
+};
+
+/// Conversions between each of the pitch representations.
+///
+/// Only `to_step` is required. The remaining methods are provided by way of the Step, though
+/// each representation implements them using its own exact conversions where available.
+pub trait Pitch {
+    /// Convert to the equivalent Step.
+    fn to_step(&self) -> Step;
+
+    /// Convert to the equivalent frequency.
+    #[inline]
+    fn to_hz(&self) -> Hz {
+        self.to_step().to_hz()
+    }
+
+    /// Convert to the closest LetterOctave.
+    #[inline]
+    fn to_letter_octave(&self) -> LetterOctave {
+        self.to_step().to_letter_octave()
+    }
+
+    /// Convert to the equivalent Mel.
+    #[inline]
+    fn to_mel(&self) -> Mel {
+        self.to_step().to_mel()
+    }
+
+    /// Convert to the equivalent OctaveFraction.
+    #[inline]
+    fn to_octave_fraction(&self) -> OctaveFraction {
+        self.to_step().to_octave_fraction()
+    }
+
+    /// Convert to a percentage of the human hearing range.
+    #[inline]
+    fn to_perc(&self) -> Perc {
+        self.to_step().to_perc()
+    }
+
+    /// Convert to a percentage of the human hearing range in log-frequency space.
+    #[inline]
+    fn to_log_perc(&self) -> LogPerc {
+        self.to_step().to_log_perc()
+    }
+
+    /// Convert to a scaled percentage of the human hearing range with the default weight.
+    #[inline]
+    fn to_scaled_perc(&self) -> ScaledPerc {
+        self.to_step().to_scaled_perc()
+    }
+
+    /// Describe the pitch in each of the common representations at once (step, frequency, the
+    /// nearest name with an offset in cents, mels and percentage of the hearing range).
+    #[inline]
+    fn describe(&self) -> PitchDescription where Self: Sized {
+        PitchDescription::new(self)
+    }
+}
+
+/// The signed distance from pitch `a` to pitch `b` in steps (semitones), positive when `b` is
+/// higher than `a`.
+#[inline]
+pub fn steps_between<A: Into<Step>, B: Into<Step>>(a: A, b: B) -> calc::Step {
+    b.into().step() - a.into().step()
+}
+
+/// The signed distance from pitch `a` to pitch `b` in cents, positive when `b` is higher than `a`.
+#[inline]
+pub fn cents_between<A: Into<Step>, B: Into<Step>>(a: A, b: B) -> calc::Cents {
+    steps_between(a, b) * 100.0
+}
+
+/// The signed distance from pitch `a` to pitch `b` in octaves, positive when `b` is higher than
+/// `a`.
+#[inline]
+pub fn octaves_between<A: Into<Step>, B: Into<Step>>(a: A, b: B) -> calc::OctaveFraction {
+    steps_between(a, b) / 12.0
+}
+
+/// A wrapper ordering any Pitch by the Step it represents, for use as a key within ordered or
+/// hashed collections (e.g. a `BTreeMap` of per-note samples).
+///
+/// Hz, LetterOctave, LogPerc, Mel, OctaveFraction, Perc, ScaledPerc and Step are already totally
+/// ordered by themselves. The wrapper is useful for types that are not (such as `AnyPitch`), or
+/// for collections mixing multiple representations, where pitches representing the same Step are
+/// considered equal.
+#[derive(Copy, Clone, Debug)]
+pub struct OrderedPitch<T>(pub T);
+
+impl<T: Pitch> OrderedPitch<T> {
+
+    /// Return the wrapped pitch.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        let OrderedPitch(pitch) = self;
+        pitch
+    }
+
+    /// The unit value of the Step by which the pitch is ordered.
+    #[inline]
+    fn key(&self) -> calc::Step {
+        let OrderedPitch(ref pitch) = *self;
+        pitch.to_step().step()
+    }
+
+}
+
+impl<T: Pitch> PartialEq for OrderedPitch<T> {
+    #[inline]
+    fn eq(&self, other: &OrderedPitch<T>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Pitch> Eq for OrderedPitch<T> {}
+
+impl<T: Pitch> Hash for OrderedPitch<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().to_bits().hash(state);
+    }
+}
+
+impl<T: Pitch> PartialOrd for OrderedPitch<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &OrderedPitch<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Ordered by the [total order](crate#comparison) of the Step.
+impl<T: Pitch> Ord for OrderedPitch<T> {
+    #[inline]
+    fn cmp(&self, other: &OrderedPitch<T>) -> Ordering {
+        self.key().total_cmp(&other.key())
+    }
+}
+
+/// Implement Pitch using the inherent conversion methods of the given type.
+macro_rules! impl_pitch {
+    ($T:ident, $identity:ident, [$($method:ident => $To:ty),*]) => {
+        impl Pitch for $T {
+            #[inline]
+            fn $identity(&self) -> $T { *self }
+            $(
+                #[inline]
+                fn $method(&self) -> $To { $T::$method(self) }
+            )*
+        }
+    };
+
+    ($T:ident, [$($method:ident => $To:ty),*]) => {
+        impl Pitch for $T {
+            $(
+                #[inline]
+                fn $method(&self) -> $To { $T::$method(self) }
+            )*
+        }
+    };
+}
+
+impl_pitch!(Hz, to_hz, [
+    to_step => Step,
+    to_letter_octave => LetterOctave,
+    to_mel => Mel,
+    to_octave_fraction => OctaveFraction,
+    to_perc => Perc,
+    to_log_perc => LogPerc,
+    to_scaled_perc => ScaledPerc
+]);
+
+impl_pitch!(LetterOctave, to_letter_octave, [
+    to_step => Step,
+    to_hz => Hz,
+    to_mel => Mel,
+    to_octave_fraction => OctaveFraction,
+    to_perc => Perc,
+    to_log_perc => LogPerc,
+    to_scaled_perc => ScaledPerc
+]);
+
+impl_pitch!(Mel, to_mel, [
+    to_step => Step,
+    to_hz => Hz,
+    to_letter_octave => LetterOctave,
+    to_octave_fraction => OctaveFraction,
+    to_perc => Perc,
+    to_log_perc => LogPerc,
+    to_scaled_perc => ScaledPerc
+]);
+
+impl_pitch!(OctaveFraction, to_octave_fraction, [
+    to_step => Step,
+    to_hz => Hz,
+    to_letter_octave => LetterOctave,
+    to_mel => Mel,
+    to_perc => Perc,
+    to_log_perc => LogPerc,
+    to_scaled_perc => ScaledPerc
+]);
+
+impl_pitch!(Perc, to_perc, [
+    to_step => Step,
+    to_log_perc => LogPerc,
+    to_hz => Hz,
+    to_letter_octave => LetterOctave,
+    to_mel => Mel,
+    to_octave_fraction => OctaveFraction,
+    to_scaled_perc => ScaledPerc
+]);
+
+impl_pitch!(ScaledPerc, to_scaled_perc, [
+    to_step => Step,
+    to_log_perc => LogPerc,
+    to_hz => Hz,
+    to_letter_octave => LetterOctave,
+    to_mel => Mel,
+    to_octave_fraction => OctaveFraction,
+    to_perc => Perc
+]);
+
+impl_pitch!(Step, to_step, [
+    to_hz => Hz,
+    to_letter_octave => LetterOctave,
+    to_mel => Mel,
+    to_octave_fraction => OctaveFraction,
+    to_perc => Perc,
+    to_log_perc => LogPerc,
+    to_scaled_perc => ScaledPerc
+]);
+
+impl_pitch!(LogPerc, to_log_perc, [
+    to_step => Step,
+    to_hz => Hz,
+    to_letter_octave => LetterOctave,
+    to_mel => Mel,
+    to_octave_fraction => OctaveFraction,
+    to_perc => Perc,
+    to_scaled_perc => ScaledPerc
+]);
+
+impl_pitch!(AnyPitch, [
+    to_step => Step,
+    to_hz => Hz,
+    to_letter_octave => LetterOctave,
+    to_mel => Mel,
+    to_octave_fraction => OctaveFraction,
+    to_perc => Perc,
+    to_log_perc => LogPerc,
+    to_scaled_perc => ScaledPerc
+]);
+impl_pitch!(MidiNote, [to_step => Step, to_hz => Hz, to_letter_octave => LetterOctave]);
+impl_pitch!(MpeNote, [to_step => Step, to_hz => Hz]);
+impl_pitch!(QuarterTone, [to_step => Step, to_hz => Hz]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Letter;
+
+    fn octave_above<P: Pitch>(pitch: P) -> LetterOctave {
+        Step(pitch.to_step().step() + 12.0).to_letter_octave()
+    }
+
+    #[test]
+    fn generic_over_representations() {
+        let a4 = LetterOctave(Letter::A, 4);
+        assert_eq!(octave_above(Hz(220.0)), a4);
+        assert_eq!(octave_above(LetterOctave(Letter::A, 3)), a4);
+        assert_eq!(octave_above(Mel(Hz(220.0).mel())), a4);
+        assert_eq!(octave_above(Step(57.0)), a4);
+        assert_eq!(Pitch::to_letter_octave(&Step(69.0)), LetterOctave(Letter::A, 4));
+        assert_eq!(Pitch::to_step(&"A4".parse::<AnyPitch>().unwrap()), Step(69.0));
+    }
+
+    #[test]
+    fn ordered_pitch_keys() {
+        use std::collections::BTreeMap;
+        let mut samples = BTreeMap::new();
+        samples.insert(OrderedPitch("A4".parse::<AnyPitch>().unwrap()), "a.wav");
+        samples.insert(OrderedPitch(AnyPitch::Step(Step(60.0))), "c.wav");
+        samples.insert(OrderedPitch(AnyPitch::Hz(Hz(440.0))), "a2.wav");
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples.values().cloned().collect::<Vec<_>>(), vec!["c.wav", "a2.wav"]);
+        let key = OrderedPitch(AnyPitch::LetterOctave(LetterOctave(Letter::C, 4)));
+        assert_eq!(samples.get(&key), Some(&"c.wav"));
+    }
+
+    #[test]
+    fn distances() {
+        let a4 = LetterOctave(Letter::A, 4);
+        assert!((cents_between(a4, Hz(880.0)) - 1_200.0).abs() < 0.01);
+        assert!((cents_between(Hz(880.0), a4) + 1_200.0).abs() < 0.01);
+        assert_eq!(steps_between(Step(60.0), a4), 9.0);
+        assert_eq!(octaves_between(a4, Step(63.0)), -0.5);
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt;
+use std::str::FromStr;
+use accidental::split_note_name;
+use super::{
+    calc,
+    Accidental,
+    Error,
+    Hz,
+    Letter,
+    Octave,
+    ParseError,
+    Step,
+    hz_from_step,
+    nearest_step,
+    step_from_letter_octave,
+};
+
+/// The spelling of each of the 24 quarter tones within an octave, starting from `C`.
+const SPELLINGS: [(Letter, Accidental); 24] = [
+    (Letter::C, Accidental::Natural),
+    (Letter::C, Accidental::HalfSharp),
+    (Letter::C, Accidental::Sharp),
+    (Letter::D, Accidental::HalfFlat),
+    (Letter::D, Accidental::Natural),
+    (Letter::D, Accidental::HalfSharp),
+    (Letter::E, Accidental::Flat),
+    (Letter::E, Accidental::HalfFlat),
+    (Letter::E, Accidental::Natural),
+    (Letter::E, Accidental::HalfSharp),
+    (Letter::F, Accidental::Natural),
+    (Letter::F, Accidental::HalfSharp),
+    (Letter::F, Accidental::Sharp),
+    (Letter::G, Accidental::HalfFlat),
+    (Letter::G, Accidental::Natural),
+    (Letter::G, Accidental::HalfSharp),
+    (Letter::A, Accidental::Flat),
+    (Letter::A, Accidental::HalfFlat),
+    (Letter::A, Accidental::Natural),
+    (Letter::A, Accidental::HalfSharp),
+    (Letter::B, Accidental::Flat),
+    (Letter::B, Accidental::HalfFlat),
+    (Letter::B, Accidental::Natural),
+    (Letter::B, Accidental::HalfSharp),
+];
+
+/// Pitch representation in the form of a letter, an accidental and an octave, capable of naming
+/// every pitch of the 24-EDO (quarter-tone) scale.
+///
+/// The accidental is applied in addition to any alteration within the letter itself, so
+/// `QuarterTone(Letter::Csh, Accidental::HalfSharp, 4)` is `C` three-quarter-sharp.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct QuarterTone(pub Letter, pub Accidental, pub Octave);
+
+impl QuarterTone {
+
+    /// The closest quarter tone to the given pitch, spelled conventionally.
+    pub fn from_step<P: Into<Step>>(pitch: P) -> QuarterTone {
+        let quarters = nearest_step(pitch.into().step() * 2.0) as i32;
+        let octave = quarters.div_euclid(24) - 1;
+        let (letter, accidental) = SPELLINGS[quarters.rem_euclid(24) as usize];
+        QuarterTone(letter, accidental, octave)
+    }
+
+    /// Return the letter.
+    #[inline]
+    pub fn letter(&self) -> Letter {
+        let QuarterTone(letter, _, _) = *self;
+        letter
+    }
+
+    /// Return the accidental.
+    #[inline]
+    pub fn accidental(&self) -> Accidental {
+        let QuarterTone(_, accidental, _) = *self;
+        accidental
+    }
+
+    /// Return the octave.
+    #[inline]
+    pub fn octave(&self) -> Octave {
+        let QuarterTone(_, _, octave) = *self;
+        octave
+    }
+
+    /// The total alteration applied to the natural letter in steps.
+    #[inline]
+    pub fn alteration(&self) -> calc::Step {
+        self.letter().alteration() as calc::Step + self.accidental().step_offset()
+    }
+
+    /// Convert to the unit value of a Step.
+    #[inline]
+    pub fn step(&self) -> calc::Step {
+        step_from_letter_octave(self.letter().natural(), self.octave()) + self.alteration()
+    }
+
+    /// Convert to a floating point MIDI-esque Step.
+    #[inline]
+    pub fn to_step(&self) -> Step {
+        Step(self.step())
+    }
+
+    /// Convert to the unit value of the equivalent frequency Hz.
+    #[inline]
+    pub fn hz(&self) -> calc::Hz {
+        hz_from_step(self.step())
+    }
+
+    /// Convert to the equivalent frequency in Hz.
+    #[inline]
+    pub fn to_hz(&self) -> Hz {
+        Hz(self.hz())
+    }
+
+}
+
+impl fmt::Display for QuarterTone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let natural = self.letter().natural();
+        let accidental = Accidental::from_step_offset(self.alteration())
+            .unwrap_or(self.accidental());
+        if f.alternate() {
+            write!(f, "{}{:#}{}", natural, accidental, self.octave())
+        } else {
+            write!(f, "{}{}{}", natural, accidental, self.octave())
+        }
+    }
+}
+
+impl FromStr for QuarterTone {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<QuarterTone, Error> {
+        let (letter, accidental, octave) = split_note_name(s.trim())?;
+        let octave = octave.parse().map_err(|_| ParseError::InvalidOctave)?;
+        Ok(QuarterTone(letter, accidental, octave))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarter_tone_spelling() {
+        let names: Vec<String> = (0..24)
+            .map(|q| QuarterTone::from_step(Step(60.0 + q as calc::Step / 2.0)).to_string())
+            .collect();
+        assert_eq!(names[0], "C4");
+        assert_eq!(names[1], "C+4");
+        assert_eq!(names[7], "Ed4");
+        assert_eq!(names[23], "B+4");
+        for (q, name) in names.iter().enumerate() {
+            let parsed: QuarterTone = name.parse().unwrap();
+            assert_eq!(parsed.step(), 60.0 + q as calc::Step / 2.0);
+        }
+    }
+
+    #[test]
+    fn combined_alterations() {
+        let tone = QuarterTone(Letter::Csh, Accidental::HalfSharp, 4);
+        assert_eq!(tone.step(), 61.5);
+        assert_eq!(tone.to_string(), "C#+4");
+        assert_eq!("Bdb-1".parse::<QuarterTone>().unwrap().step(), 9.5);
+        assert_eq!("H4".parse::<QuarterTone>(), Err(ParseError::InvalidLetter.into()));
+        assert_eq!("C?4".parse::<QuarterTone>(), Err(ParseError::InvalidAccidental.into()));
+        assert_eq!("C#".parse::<QuarterTone>(), Err(ParseError::InvalidOctave.into()));
+    }
+
+    #[test]
+    fn unicode_accidentals() {
+        let tone = QuarterTone(Letter::E, Accidental::HalfFlat, 4);
+        assert_eq!(format!("{:#}", tone), "E\u{1D133}4");
+        assert_eq!("E\u{1D133}4".parse::<QuarterTone>(), Ok(tone));
+        assert_eq!("F\u{266F}\u{1D132}3".parse::<QuarterTone>().unwrap().step(), 54.5);
+    }
+}
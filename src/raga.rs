@@ -0,0 +1,186 @@
+//! Swaras, srutis and ragas of Indian classical music.
+
+use super::{
+    Hz,
+    OCTAVE_CENTS,
+    Ratio,
+    Scale,
+};
+
+/// The frequency ratios of the 22 srutis of Indian classical music above Sa, in the common
+/// 5-limit reconstruction.
+pub const SRUTI_RATIOS: [Ratio; 22] = [
+    Ratio(1, 1),
+    Ratio(256, 243),
+    Ratio(16, 15),
+    Ratio(10, 9),
+    Ratio(9, 8),
+    Ratio(32, 27),
+    Ratio(6, 5),
+    Ratio(5, 4),
+    Ratio(81, 64),
+    Ratio(4, 3),
+    Ratio(27, 20),
+    Ratio(45, 32),
+    Ratio(729, 512),
+    Ratio(3, 2),
+    Ratio(128, 81),
+    Ratio(8, 5),
+    Ratio(5, 3),
+    Ratio(27, 16),
+    Ratio(16, 9),
+    Ratio(9, 5),
+    Ratio(15, 8),
+    Ratio(243, 128),
+];
+
+/// The scale of all 22 srutis.
+#[inline]
+pub fn sruti_scale() -> Scale {
+    Scale::normalize(SRUTI_RATIOS.iter().map(|ratio| ratio.cents()), OCTAVE_CENTS)
+}
+
+/// The twelve swara positions used by both Hindustani and Carnatic music, named as in
+/// Hindustani theory (`Komal` for flattened and `Tivra` for sharpened swaras).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Swara {
+    Sa,
+    KomalRe,
+    Re,
+    KomalGa,
+    Ga,
+    Ma,
+    TivraMa,
+    Pa,
+    KomalDha,
+    Dha,
+    KomalNi,
+    Ni,
+}
+
+impl Swara {
+
+    /// The index of the sruti at which the swara lies within `SRUTI_RATIOS`.
+    pub fn sruti(&self) -> usize {
+        match *self {
+            Swara::Sa => 0,
+            Swara::KomalRe => 2,
+            Swara::Re => 4,
+            Swara::KomalGa => 6,
+            Swara::Ga => 7,
+            Swara::Ma => 9,
+            Swara::TivraMa => 11,
+            Swara::Pa => 13,
+            Swara::KomalDha => 15,
+            Swara::Dha => 16,
+            Swara::KomalNi => 19,
+            Swara::Ni => 20,
+        }
+    }
+
+    /// The frequency ratio of the swara above Sa.
+    #[inline]
+    pub fn ratio(&self) -> Ratio {
+        SRUTI_RATIOS[self.sruti()]
+    }
+
+    /// The frequency of the swara given the frequency of Sa.
+    #[inline]
+    pub fn hz(&self, sa: Hz) -> Hz {
+        self.ratio().apply(sa)
+    }
+
+}
+
+/// A selection of common ragas, described by the swaras of their ascending scale.
+///
+/// Includes the ten Hindustani thaats (named after their principal raga, e.g. `Yaman` for Kalyan
+/// thaat) along with several common Carnatic melakarta and janya ragas.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Raga {
+    Bilawal,
+    Yaman,
+    Khamaj,
+    Bhairav,
+    Purvi,
+    Marwa,
+    Kafi,
+    Asavari,
+    Bhairavi,
+    Todi,
+    Mayamalavagowla,
+    Shankarabharanam,
+    Kalyani,
+    Kharaharapriya,
+    Mohanam,
+    Hindolam,
+}
+
+impl Raga {
+
+    /// The swaras of the ascending scale, starting from Sa.
+    pub fn swaras(&self) -> &'static [Swara] {
+        use self::Swara::*;
+        match *self {
+            Raga::Bilawal | Raga::Shankarabharanam => &[Sa, Re, Ga, Ma, Pa, Dha, Ni],
+            Raga::Yaman | Raga::Kalyani => &[Sa, Re, Ga, TivraMa, Pa, Dha, Ni],
+            Raga::Khamaj => &[Sa, Re, Ga, Ma, Pa, Dha, KomalNi],
+            Raga::Bhairav | Raga::Mayamalavagowla => &[Sa, KomalRe, Ga, Ma, Pa, KomalDha, Ni],
+            Raga::Purvi => &[Sa, KomalRe, Ga, TivraMa, Pa, KomalDha, Ni],
+            Raga::Marwa => &[Sa, KomalRe, Ga, TivraMa, Pa, Dha, Ni],
+            Raga::Kafi | Raga::Kharaharapriya => &[Sa, Re, KomalGa, Ma, Pa, Dha, KomalNi],
+            Raga::Asavari => &[Sa, Re, KomalGa, Ma, Pa, KomalDha, KomalNi],
+            Raga::Bhairavi => &[Sa, KomalRe, KomalGa, Ma, Pa, KomalDha, KomalNi],
+            Raga::Todi => &[Sa, KomalRe, KomalGa, TivraMa, Pa, KomalDha, Ni],
+            Raga::Mohanam => &[Sa, Re, Ga, Pa, Dha],
+            Raga::Hindolam => &[Sa, KomalGa, Ma, KomalDha, KomalNi],
+        }
+    }
+
+    /// The ascending scale of the raga, tuned to the srutis of its swaras.
+    #[inline]
+    pub fn scale(&self) -> Scale {
+        let cents = self.swaras().iter().map(|swara| swara.ratio().cents());
+        Scale::normalize(cents, OCTAVE_CENTS)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swaras() {
+        assert_eq!(Swara::Sa.ratio(), Ratio(1, 1));
+        assert_eq!(Swara::Pa.ratio(), Ratio(3, 2));
+        assert_eq!(Swara::Ga.ratio(), Ratio(5, 4));
+        assert_eq!(Swara::TivraMa.ratio(), Ratio(45, 32));
+        assert_eq!(Swara::Sa.hz(Hz(240.0)), Hz(240.0));
+        assert_eq!(Swara::Pa.hz(Hz(240.0)), Hz(360.0));
+        assert_eq!(Swara::Dha.hz(Hz(240.0)), Hz(400.0));
+        // The swaras lie on ascending srutis.
+        let srutis: Vec<usize> = [
+            Swara::Sa, Swara::KomalRe, Swara::Re, Swara::KomalGa, Swara::Ga, Swara::Ma,
+            Swara::TivraMa, Swara::Pa, Swara::KomalDha, Swara::Dha, Swara::KomalNi, Swara::Ni,
+        ].iter().map(|swara| swara.sruti()).collect();
+        assert!(srutis.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(srutis.iter().all(|&sruti| sruti < SRUTI_RATIOS.len()));
+    }
+
+    #[test]
+    fn scales() {
+        assert_eq!(sruti_scale().len(), 22);
+        let yaman = Raga::Yaman.scale();
+        assert_eq!(yaman.len(), 7);
+        assert!(Raga::Yaman.swaras().contains(&Swara::TivraMa));
+        assert!(!Raga::Yaman.swaras().contains(&Swara::Ma));
+        assert!((yaman.degrees[3] - Ratio(45, 32).cents()).abs() < 0.001);
+        assert!((yaman.degrees[4] - Ratio(3, 2).cents()).abs() < 0.001);
+        assert_eq!(Raga::Mohanam.scale().len(), 5);
+        assert_eq!(Raga::Hindolam.scale().len(), 5);
+        assert_eq!(Raga::Kalyani.scale(), yaman);
+    }
+}
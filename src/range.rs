@@ -0,0 +1,146 @@
+use super::{
+    calc,
+    Letter,
+    LetterOctave,
+    Octave,
+    Step,
+};
+
+/// An inclusive range of pitches between a lowest and a highest Step.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct PitchRange(pub Step, pub Step);
+
+impl PitchRange {
+
+    /// Construct a PitchRange from any two pitches, ordering them so that the lower one comes
+    /// first.
+    pub fn new<A: Into<Step>, B: Into<Step>>(a: A, b: B) -> PitchRange {
+        let (a, b) = (a.into(), b.into());
+        if a.step() <= b.step() { PitchRange(a, b) } else { PitchRange(b, a) }
+    }
+
+    /// Return the lowest pitch within the range.
+    #[inline]
+    pub fn lowest(&self) -> Step {
+        let PitchRange(lowest, _) = *self;
+        lowest
+    }
+
+    /// Return the highest pitch within the range.
+    #[inline]
+    pub fn highest(&self) -> Step {
+        let PitchRange(_, highest) = *self;
+        highest
+    }
+
+    /// The distance between the lowest and highest pitch in semitones.
+    #[inline]
+    pub fn span(&self) -> calc::Step {
+        self.highest().step() - self.lowest().step()
+    }
+
+    /// Whether or not the given pitch lies within the range (inclusive).
+    #[inline]
+    pub fn contains<P: Into<Step>>(&self, pitch: P) -> bool {
+        let step = pitch.into().step();
+        step >= self.lowest().step() && step <= self.highest().step()
+    }
+
+    /// Whether or not every pitch of `other` also lies within this range.
+    #[inline]
+    pub fn contains_range(&self, other: &PitchRange) -> bool {
+        self.contains(other.lowest()) && self.contains(other.highest())
+    }
+
+    /// The range of pitches shared by both ranges, if they overlap.
+    pub fn intersection(&self, other: &PitchRange) -> Option<PitchRange> {
+        let lowest = self.lowest().step().max(other.lowest().step());
+        let highest = self.highest().step().min(other.highest().step());
+        if lowest <= highest { Some(PitchRange(Step(lowest), Step(highest))) } else { None }
+    }
+
+    /// An iterator yielding the given letter in every octave within the range, from lowest to
+    /// highest, e.g. every `C` between 20hz and 20khz for a drone generator.
+    pub fn octaves_of(&self, letter: Letter) -> Octaves {
+        let (lowest, highest) = (self.lowest().step(), self.highest().step());
+        // A bound that is not finite has no octave, so the iterator would never end.
+        if !lowest.is_finite() || !highest.is_finite() {
+            return Octaves { letter, octave: 1, last: 0, highest };
+        }
+        // The octave above the highest pitch bounds the iteration, even when `highest` lies
+        // beyond the octaves that a LetterOctave can represent.
+        let last = Step(highest).to_letter_octave().octave() + 1;
+        // Begin from the octave below the lowest pitch, then skip any that lie beneath it.
+        let mut octave = Step(lowest).to_letter_octave().octave() - 1;
+        while octave <= last && LetterOctave(letter, octave).step() < lowest {
+            octave += 1;
+        }
+        Octaves { letter, octave, last, highest }
+    }
+
+}
+
+/// An iterator yielding the same letter in each successive octave within a PitchRange, produced
+/// by `PitchRange::octaves_of`.
+#[derive(Copy, Clone, Debug)]
+pub struct Octaves {
+    letter: Letter,
+    octave: Octave,
+    last: Octave,
+    highest: calc::Step,
+}
+
+impl Iterator for Octaves {
+    type Item = LetterOctave;
+    fn next(&mut self) -> Option<LetterOctave> {
+        let letter_octave = LetterOctave(self.letter, self.octave);
+        if self.octave > self.last || letter_octave.step() > self.highest {
+            return None;
+        }
+        self.octave += 1;
+        Some(letter_octave)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Hz;
+
+    #[test]
+    fn ranges() {
+        let range = PitchRange::new(Step(72.0), LetterOctave(Letter::C, 4));
+        assert_eq!(range, PitchRange(Step(60.0), Step(72.0)));
+        assert_eq!(range.span(), 12.0);
+        assert!(range.contains(Step(60.0)) && range.contains(Step(72.0)));
+        assert!(!range.contains(Step(72.5)));
+        assert!(range.contains_range(&PitchRange(Step(62.0), Step(70.0))));
+        assert!(!range.contains_range(&PitchRange(Step(62.0), Step(74.0))));
+        let other = PitchRange(Step(69.0), Step(81.0));
+        assert_eq!(range.intersection(&other), Some(PitchRange(Step(69.0), Step(72.0))));
+        assert_eq!(range.intersection(&PitchRange(Step(73.0), Step(80.0))), None);
+    }
+
+    #[test]
+    fn octaves_within_range() {
+        let audible = PitchRange::new(Hz(20.0), Hz(20_000.0));
+        let cs: Vec<_> = audible.octaves_of(Letter::C).collect();
+        assert_eq!(cs.len(), 10);
+        assert_eq!(cs[0], LetterOctave(Letter::C, 1));
+        assert_eq!(cs[9], LetterOctave(Letter::C, 10));
+        let range = PitchRange(Step(57.0), Step(81.0));
+        let octaves: Vec<_> = range.octaves_of(Letter::A).map(|a| a.octave()).collect();
+        assert_eq!(octaves, vec![3, 4, 5]);
+        // The spelling of the letter is preserved.
+        assert!(range.octaves_of(Letter::Db).all(|db| db.letter() == Letter::Db));
+        assert_eq!(PitchRange(Step(61.5), Step(62.5)).octaves_of(Letter::C).count(), 0);
+        // Bounds that are not finite give no octaves rather than iterating forever.
+        for &bound in [calc::Step::NAN, calc::Step::INFINITY, calc::Step::NEG_INFINITY].iter() {
+            assert_eq!(PitchRange(Step(60.0), Step(bound)).octaves_of(Letter::C).count(), 0);
+            assert_eq!(PitchRange(Step(bound), Step(60.0)).octaves_of(Letter::C).count(), 0);
+        }
+        let beyond = PitchRange(Step(calc::Step::MAX), Step(calc::Step::MAX));
+        assert_eq!(beyond.octaves_of(Letter::C).count(), 0);
+    }
+}
@@ -0,0 +1,526 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::{Div, Mul};
+use super::{
+    calc,
+    cents_from_ratio,
+    ratio_from_cents,
+    Hz,
+};
+use utils::gcd;
+
+/// A frequency ratio between two pitches as a fraction of whole numbers (e.g. `Ratio(3, 2)` for a
+/// just perfect fifth).
+///
+/// Unlike `Cents`, ratios can be stacked and compared exactly, which makes them the natural
+/// representation for just intonation.
+///
+/// `Ratio::new` rejects zero terms, though they may still be given through the public fields.
+/// Such ratios are ordered by value, `0/d` lying below and `n/0` above every other ratio, while
+/// the meaningless `0/0` is ordered above all of them (as with the total order of `NaN`).
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Ratio(pub u32, pub u32);
+
+/// The unison `1/1`.
+pub const UNISON: Ratio = Ratio(1, 1);
+/// The octave `2/1`.
+pub const OCTAVE: Ratio = Ratio(2, 1);
+
+/// The syntonic comma `81/80`, between four pure fifths and a pure major third two octaves up.
+pub const SYNTONIC_COMMA: Ratio = Ratio(81, 80);
+/// The Pythagorean comma `3^12/2^19`, between twelve pure fifths and seven octaves.
+pub const PYTHAGOREAN_COMMA: Ratio = Ratio(531_441, 524_288);
+/// The schisma `32805/32768`, between the Pythagorean and syntonic commas.
+pub const SCHISMA: Ratio = Ratio(32_805, 32_768);
+/// The (lesser) diesis `128/125`, between an octave and three pure major thirds.
+pub const DIESIS: Ratio = Ratio(128, 125);
+
+/// The size of `SYNTONIC_COMMA` in cents.
+pub const SYNTONIC_COMMA_CENTS: calc::Cents = 21.506_29;
+/// The size of `PYTHAGOREAN_COMMA` in cents.
+pub const PYTHAGOREAN_COMMA_CENTS: calc::Cents = 23.460_01;
+/// The size of `SCHISMA` in cents.
+pub const SCHISMA_CENTS: calc::Cents = 1.953_721;
+/// The size of `DIESIS` in cents.
+pub const DIESIS_CENTS: calc::Cents = 41.058_86;
+
+/// Reduce the fraction `n/d`, panicking if the result does not fit within a Ratio.
+fn reduce(n: u64, d: u64) -> Ratio {
+    checked_reduce(n, d).unwrap_or_else(|| panic!("Ratio overflow: {}/{}", n, d))
+}
+
+/// Reduce `n/d` to its lowest terms, or `None` if either term does not fit within a `u32`.
+fn checked_reduce(n: u64, d: u64) -> Option<Ratio> {
+    let divisor = gcd(n, d).max(1);
+    let (n, d) = (n / divisor, d / divisor);
+    if n > u32::MAX as u64 || d > u32::MAX as u64 {
+        return None;
+    }
+    Some(Ratio(n as u32, d as u32))
+}
+
+impl Ratio {
+
+    /// Construct a Ratio reduced to its lowest terms.
+    ///
+    /// Panics if either `numerator` or `denominator` is zero.
+    pub fn new(numerator: u32, denominator: u32) -> Ratio {
+        assert!(numerator != 0 && denominator != 0, "a Ratio must not contain zero");
+        reduce(numerator as u64, denominator as u64)
+    }
+
+    /// Return the numerator.
+    #[inline]
+    pub fn numerator(&self) -> u32 {
+        let Ratio(numerator, _) = *self;
+        numerator
+    }
+
+    /// Return the denominator.
+    #[inline]
+    pub fn denominator(&self) -> u32 {
+        let Ratio(_, denominator) = *self;
+        denominator
+    }
+
+    /// The same ratio reduced to its lowest terms.
+    #[inline]
+    pub fn reduced(&self) -> Ratio {
+        reduce(self.numerator() as u64, self.denominator() as u64)
+    }
+
+    /// The inverse of the ratio, i.e. the same interval in the opposite direction.
+    #[inline]
+    pub fn inverse(&self) -> Ratio {
+        let Ratio(numerator, denominator) = *self;
+        Ratio(denominator, numerator)
+    }
+
+    /// Return the ratio as a floating point value.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.numerator() as f64 / self.denominator() as f64
+    }
+
+    /// The size of the interval in cents.
+    #[inline]
+    pub fn cents(&self) -> calc::Cents {
+        cents_from_ratio(self.value())
+    }
+
+    /// Transpose the given frequency by the interval.
+    #[inline]
+    pub fn apply(&self, hz: Hz) -> Hz {
+        Hz((hz.hz() as f64 * self.value()) as calc::Hz)
+    }
+
+    /// Raise the ratio to the given power, i.e. stack the interval `n` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either term of the result does not fit within a `u32`, e.g. for
+    /// `Ratio(3, 2).pow(21)`. See `checked_pow`.
+    pub fn pow(&self, n: u32) -> Ratio {
+        (0..n).fold(UNISON, |acc, _| acc * *self)
+    }
+
+    /// Stack the two intervals as with `*`, returning `None` if either term of the result does
+    /// not fit within a `u32`.
+    #[inline]
+    pub fn checked_mul(&self, other: Ratio) -> Option<Ratio> {
+        checked_reduce(self.numerator() as u64 * other.numerator() as u64,
+                       self.denominator() as u64 * other.denominator() as u64)
+    }
+
+    /// Take `other` away from the interval as with `/`, returning `None` if either term of the
+    /// result does not fit within a `u32`.
+    #[inline]
+    pub fn checked_div(&self, other: Ratio) -> Option<Ratio> {
+        self.checked_mul(other.inverse())
+    }
+
+    /// Raise the ratio to the given power as with `pow`, returning `None` if either term of the
+    /// result does not fit within a `u32`.
+    pub fn checked_pow(&self, n: u32) -> Option<Ratio> {
+        (0..n).try_fold(UNISON, |acc, _| acc.checked_mul(*self))
+    }
+
+    /// The prime limit of the ratio, i.e. the largest prime factor of either its numerator or
+    /// its denominator (`1` for the unison).
+    pub fn prime_limit(&self) -> u32 {
+        let Ratio(n, d) = self.reduced();
+        largest_prime_factor(n).max(largest_prime_factor(d))
+    }
+
+    /// Shift the ratio by octaves so that it lies within `[1/1, 2/1)`.
+    ///
+    /// A ratio with a zero term cannot be shifted into the octave, and is returned reduced but
+    /// otherwise unchanged.
+    pub fn octave_reduced(&self) -> Ratio {
+        let mut ratio = self.reduced();
+        if ratio.numerator() == 0 || ratio.denominator() == 0 {
+            return ratio;
+        }
+        while ratio >= OCTAVE { ratio = ratio / OCTAVE; }
+        while ratio < UNISON { ratio = ratio * OCTAVE; }
+        ratio
+    }
+
+}
+
+impl Mul for Ratio {
+    type Output = Ratio;
+    /// Stack the two intervals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either term of the result does not fit within a `u32`. See `checked_mul`.
+    #[inline]
+    fn mul(self, rhs: Ratio) -> Ratio {
+        reduce(self.numerator() as u64 * rhs.numerator() as u64,
+               self.denominator() as u64 * rhs.denominator() as u64)
+    }
+}
+
+impl Div for Ratio {
+    type Output = Ratio;
+    /// The interval remaining after taking `rhs` away from `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either term of the result does not fit within a `u32`. See `checked_div`.
+    #[inline]
+    fn div(self, rhs: Ratio) -> Ratio {
+        reduce(self.numerator() as u64 * rhs.denominator() as u64,
+               self.denominator() as u64 * rhs.numerator() as u64)
+    }
+}
+
+impl PartialEq for Ratio {
+    #[inline]
+    fn eq(&self, other: &Ratio) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ratio {}
+
+impl Hash for Ratio {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Ratio(n, d) = self.reduced();
+        n.hash(state);
+        d.hash(state);
+    }
+}
+
+impl PartialOrd for Ratio {
+    #[inline]
+    fn partial_cmp(&self, other: &Ratio) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    #[inline]
+    fn cmp(&self, other: &Ratio) -> Ordering {
+        let undefined = |ratio: &Ratio| ratio.numerator() == 0 && ratio.denominator() == 0;
+        match (undefined(self), undefined(other)) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                let a = self.numerator() as u64 * other.denominator() as u64;
+                let b = other.numerator() as u64 * self.denominator() as u64;
+                a.cmp(&b)
+            },
+        }
+    }
+}
+
+/// The largest prime factor of `n` (`1` for `0` and `1`).
+fn largest_prime_factor(n: u32) -> u32 {
+    let (mut n, mut largest, mut factor) = (n, 1, 2);
+    while n > 1 && factor as u64 * factor as u64 <= n as u64 {
+        while n % factor == 0 {
+            largest = factor;
+            n /= factor;
+        }
+        factor += 1;
+    }
+    if n > 1 { n } else { largest }
+}
+
+/// Stack the given intervals, e.g. `stack(vec![Ratio(3, 2); 4])` for four fifths.
+///
+/// # Panics
+///
+/// Panics if either term of the result does not fit within a `u32`. Each interval may instead be
+/// stacked with `Ratio::checked_mul`, or measured with `comma_drift`, which never overflows.
+pub fn stack<I: IntoIterator<Item=Ratio>>(intervals: I) -> Ratio {
+    intervals.into_iter().fold(UNISON, |acc, interval| acc * interval)
+}
+
+/// Measure how far a progression of just intervals drifts from equal temperament.
+///
+/// Each interval (given as a Ratio, with ratios below `1/1` moving downwards) is compared with
+/// the nearest whole number of equal tempered semitones. Returns the accumulated drift in cents
+/// after each interval. A progression returning to its starting pitch class by way of pure
+/// intervals accumulates a comma, e.g. `C - A - D - G - C` drifts by the syntonic comma.
+///
+/// The intervals are accumulated in cents, so arbitrarily long progressions never overflow.
+pub fn comma_drift<I: IntoIterator<Item=Ratio>>(intervals: I) -> Vec<calc::Cents> {
+    let mut drift = 0.0;
+    intervals.into_iter()
+        .map(|interval| {
+            let cents = cents_from_ratio(interval.value());
+            drift += cents - (cents / 100.0).round() * 100.0;
+            drift
+        })
+        .collect()
+}
+
+/// Find the best rational approximations of the given frequency ratio whose denominators do not
+/// exceed `max_denominator`, optionally only considering ratios within the given `prime_limit`.
+///
+/// The approximations are the convergents of the continued fraction of the ratio, followed by
+/// the closest semiconvergent within `max_denominator` (and `u32` numerators) once the next
+/// convergent would exceed it. They are returned in order of increasing denominator, each being
+/// closer than the last, skipping any outside the `prime_limit`. Each Ratio is paired with its
+/// error in cents (positive if the Ratio is wider than the given ratio).
+///
+/// Returns an empty `Vec` if `ratio` is not a positive, finite number.
+pub fn approximate(ratio: f64, max_denominator: u32, prime_limit: Option<u32>)
+    -> Vec<(Ratio, calc::Cents)>
+{
+    let mut approximations = Vec::new();
+    if ratio.is_nan() || ratio <= 0.0 || ratio.is_infinite() || max_denominator == 0 {
+        return approximations;
+    }
+    let target = cents_from_ratio(ratio);
+    let (max_numerator, max_denominator) = (u32::MAX as u64, max_denominator as u64);
+    // The last two convergents as (numerator, denominator), beginning from the bounds `0/1` and
+    // `1/0` of the Stern-Brocot tree.
+    let (mut before, mut last) = ((0u64, 1u64), (1u64, 0u64));
+    let mut remainder = ratio;
+    let mut best = calc::Cents::INFINITY;
+    loop {
+        // The next term of the continued fraction, i.e. the length of the next run of steps in
+        // the same direction down the tree.
+        let term = remainder.floor() as u64;
+        if term == 0 {
+            // Only the first term of a ratio below `1` may be zero, whose convergent is `0/1`.
+            ::std::mem::swap(&mut before, &mut last);
+            remainder = 1.0 / remainder;
+            continue;
+        }
+        let fits = |bound: u64, before: u64, last: u64| {
+            (bound - before).checked_div(last).unwrap_or(u64::MAX)
+        };
+        let steps = term
+            .min(fits(max_numerator, before.0, last.0))
+            .min(fits(max_denominator, before.1, last.1));
+        if steps == 0 {
+            break;
+        }
+        let (n, d) = (before.0 + steps * last.0, before.1 + steps * last.1);
+        let candidate = Ratio(n as u32, d as u32);
+        let error = candidate.cents() - target;
+        let within_limit = prime_limit.map_or(true, |limit| candidate.prime_limit() <= limit);
+        if within_limit && error.abs() < best {
+            best = error.abs();
+            approximations.push((candidate, error));
+        }
+        let fraction = remainder - remainder.floor();
+        if steps < term || fraction == 0.0 {
+            break;
+        }
+        before = last;
+        last = (n, d);
+        remainder = 1.0 / fraction;
+    }
+    approximations
+}
+
+/// Find the best rational approximations of an interval given in cents.
+///
+/// See `approximate` for details.
+#[inline]
+pub fn approximate_cents(cents: calc::Cents, max_denominator: u32, prime_limit: Option<u32>)
+    -> Vec<(Ratio, calc::Cents)>
+{
+    approximate(ratio_from_cents(cents), max_denominator, prime_limit)
+}
+
+/// List every `prime_limit` just interval within `tolerance` cents of the given interval whose
+/// denominator does not exceed `max_denominator` (e.g. a `prime_limit` of `5` for 5-limit
+/// intervals).
+///
+/// Each Ratio is paired with its error in cents. The intervals are ordered from the simplest to
+/// the most complex (by Tenney height, the product of the numerator and denominator).
+pub fn just_intervals(cents: calc::Cents,
+                      tolerance: calc::Cents,
+                      prime_limit: u32,
+                      max_denominator: u32) -> Vec<(Ratio, calc::Cents)> {
+    let tolerance = tolerance.abs();
+    let lowest = ratio_from_cents(cents - tolerance);
+    let highest = ratio_from_cents(cents + tolerance);
+    let mut intervals = Vec::new();
+    for d in 1..max_denominator as u64 + 1 {
+        let first = (lowest * d as f64).ceil().max(1.0) as u64;
+        let last = ((highest * d as f64).floor() as u64).min(u32::MAX as u64);
+        for n in first..last + 1 {
+            if gcd(n, d) != 1 {
+                continue;
+            }
+            let ratio = Ratio(n as u32, d as u32);
+            let error = ratio.cents() - cents;
+            if ratio.prime_limit() <= prime_limit && error.abs() <= tolerance {
+                intervals.push((ratio, error));
+            }
+        }
+    }
+    intervals.sort_by(|&(a, a_error), &(b, b_error)| {
+        let (a_height, b_height) = (a.numerator() as u64 * a.denominator() as u64,
+                                    b.numerator() as u64 * b.denominator() as u64);
+        a_height.cmp(&b_height)
+            .then(a_error.abs().total_cmp(&b_error.abs()))
+    });
+    intervals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduction() {
+        assert_eq!(Ratio::new(6, 4), Ratio(3, 2));
+        assert_eq!(Ratio::new(6, 4).numerator(), 3);
+        assert_eq!(Ratio(6, 4), Ratio(3, 2));
+    }
+
+    #[test]
+    fn zero_terms() {
+        use std::collections::hash_map::DefaultHasher;
+        let hash = |ratio: Ratio| {
+            let mut hasher = DefaultHasher::new();
+            ratio.hash(&mut hasher);
+            hasher.finish()
+        };
+        let (zero, infinite, undefined) = (Ratio(0, 1), Ratio(1, 0), Ratio(0, 0));
+        assert_eq!(zero.octave_reduced(), zero);
+        assert_eq!(Ratio(3, 0).octave_reduced(), infinite);
+        assert_eq!(undefined.octave_reduced(), undefined);
+        assert!(zero < UNISON && UNISON < infinite && infinite < undefined);
+        assert_ne!(undefined, UNISON);
+        assert_ne!(undefined, zero);
+        // Equal ratios hash equally.
+        assert_eq!(Ratio(0, 5), zero);
+        assert_eq!(hash(Ratio(0, 5)), hash(zero));
+        assert_eq!(Ratio(7, 0), infinite);
+        assert_eq!(hash(Ratio(7, 0)), hash(infinite));
+        assert_eq!(undefined, Ratio(0, 0));
+    }
+
+    #[test]
+    fn stacking() {
+        let fifth = Ratio(3, 2);
+        assert_eq!(fifth * Ratio(4, 3), OCTAVE);
+        assert_eq!(OCTAVE / fifth, Ratio(4, 3));
+        assert_eq!(fifth.pow(4).octave_reduced(), Ratio(81, 64));
+    }
+
+    #[test]
+    fn overflow() {
+        let fifth = Ratio(3, 2);
+        // `3^20` is the largest power of three within a `u32`.
+        assert_eq!(fifth.checked_pow(20), Some(Ratio(3_486_784_401, 1_048_576)));
+        assert_eq!(fifth.checked_pow(21), None);
+        assert_eq!(fifth.checked_mul(Ratio(4, 3)), Some(OCTAVE));
+        assert_eq!(Ratio(u32::MAX, 1).checked_mul(Ratio(2, 1)), None);
+        // Common factors are cancelled before the result is checked.
+        assert_eq!(Ratio(u32::MAX, 1).checked_div(Ratio(u32::MAX, 2)), Some(OCTAVE));
+        assert_eq!(Ratio(1, u32::MAX).checked_div(Ratio(2, 1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ratio overflow")]
+    fn pow_overflow() {
+        Ratio(3, 2).pow(21);
+    }
+
+    #[test]
+    fn commas() {
+        let fifth = Ratio(3, 2);
+        assert_eq!(fifth.pow(4) / (Ratio(5, 4) * OCTAVE.pow(2)), SYNTONIC_COMMA);
+        assert_eq!(fifth.pow(12) / OCTAVE.pow(7), PYTHAGOREAN_COMMA);
+        assert_eq!(PYTHAGOREAN_COMMA / SYNTONIC_COMMA, SCHISMA);
+        assert_eq!(OCTAVE / stack(vec![Ratio(5, 4); 3]), DIESIS);
+        for &(ratio, cents) in &[(SYNTONIC_COMMA, SYNTONIC_COMMA_CENTS),
+                                 (PYTHAGOREAN_COMMA, PYTHAGOREAN_COMMA_CENTS),
+                                 (SCHISMA, SCHISMA_CENTS),
+                                 (DIESIS, DIESIS_CENTS)] {
+            assert!((ratio.cents() - cents).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn drift() {
+        // C down a minor third to A, up a fourth to D, down a fifth to G, up a fourth to C.
+        let progression = vec![Ratio(5, 6), Ratio(4, 3), Ratio(2, 3), Ratio(4, 3)];
+        let drift = comma_drift(progression.clone());
+        assert_eq!(drift.len(), 4);
+        assert!((drift[3] + SYNTONIC_COMMA_CENTS).abs() < 0.001);
+        assert_eq!(stack(progression), SYNTONIC_COMMA.inverse());
+        assert!(comma_drift(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn cents_and_hz() {
+        assert!((Ratio(3, 2).cents() - 701.955).abs() < 0.001);
+        assert_eq!(OCTAVE.apply(Hz(220.0)), Hz(440.0));
+    }
+
+    #[test]
+    fn prime_limit() {
+        assert_eq!(UNISON.prime_limit(), 1);
+        assert_eq!(Ratio(81, 64).prime_limit(), 3);
+        assert_eq!(Ratio(7, 4).prime_limit(), 7);
+    }
+
+    #[test]
+    fn approximations() {
+        // The equal tempered fifth.
+        let fifth = approximate_cents(700.0, 1_000, None);
+        assert_eq!(fifth[1].0, Ratio(3, 2));
+        let &(closest, error) = fifth.last().unwrap();
+        assert!(closest.denominator() <= 1_000 && error.abs() < 0.01);
+        // A 5-limit major third.
+        let third = approximate_cents(400.0, 100, Some(5));
+        assert!(third.iter().any(|&(ratio, _)| ratio == Ratio(5, 4)));
+        assert!(third.iter().all(|&(ratio, _)| ratio.prime_limit() <= 5));
+        assert!(approximate(-1.0, 100, None).is_empty());
+        // Long runs of the same continued fraction term are taken in a single step.
+        assert_eq!(approximate(1e9, 10, None).last().unwrap().0, Ratio(1_000_000_000, 1));
+        assert_eq!(approximate(1e-9, 10, None).last().unwrap().0, Ratio(1, 10));
+        assert_eq!(approximate(1e15, 10, None).last().unwrap().0, Ratio(u32::MAX, 1));
+        let pi: Vec<Ratio> = approximate(::std::f64::consts::PI, 200, None).iter()
+            .map(|&(ratio, _)| ratio)
+            .collect();
+        assert_eq!(pi, vec![Ratio(3, 1), Ratio(22, 7), Ratio(333, 106), Ratio(355, 113)]);
+    }
+
+    #[test]
+    fn just_interval_search() {
+        // The equal tempered major third.
+        let five_limit = just_intervals(400.0, 15.0, 5, 128);
+        assert_eq!(five_limit[0].0, Ratio(5, 4));
+        assert!(five_limit.iter().any(|&(ratio, _)| ratio == Ratio(81, 64)));
+        assert!(five_limit.iter()
+            .all(|&(ratio, error)| ratio.prime_limit() <= 5 && error.abs() <= 15.0));
+        // The harmonic seventh only appears once 7 is allowed.
+        assert!(just_intervals(1000.0, 35.0, 5, 8).iter().all(|&(ratio, _)| ratio != Ratio(7, 4)));
+        assert_eq!(just_intervals(1000.0, 35.0, 7, 8)[0].0, Ratio(7, 4));
+    }
+}
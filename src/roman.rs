@@ -0,0 +1,261 @@
+//! Roman numeral analysis of chords within a key.
+
+use num::ToPrimitive;
+use std::fmt;
+use std::str::FromStr;
+use super::{
+    Chord,
+    ChordQuality,
+    Error,
+    Key,
+    Letter,
+    Mode,
+    ParseError,
+};
+use utils::modulo;
+
+/// The number of semitones above the tonic of each degree of the major scale.
+const MAJOR_DEGREES: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+/// The number of semitones above the tonic of each degree of the natural minor scale.
+const MINOR_DEGREES: [u8; 7] = [0, 2, 3, 5, 7, 8, 10];
+
+/// The upper case numerals for each degree, from `I` to `VII`.
+const NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+/// A chord described by the scale degree of its root within a key, e.g. `ii`, `V7` or `bVII`.
+///
+/// Degrees are relative to the scale of the key: the major scale for major keys and the natural
+/// minor scale for minor keys, so that `VII` is the subtonic in `A` minor while the leading
+/// tone is `#vii°`. The bass note of a chord is not described.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct RomanNumeral {
+    /// The scale degree of the root, from `1` to `7`. Degrees outside this range wrap around the
+    /// scale, so that `8` stands for `1` and `0` for `7`.
+    pub degree: u8,
+    /// The chromatic alteration of the root from the scale degree in semitones (e.g. `-1` for
+    /// the flattened seventh of `bVII`).
+    pub alteration: i8,
+    /// The quality of the chord built on the root.
+    pub quality: ChordQuality,
+}
+
+/// The semitones above the tonic of each degree of the key's scale.
+fn degrees(key: Key) -> &'static [u8; 7] {
+    match key.mode() {
+        Mode::Major => &MAJOR_DEGREES,
+        Mode::Minor => &MINOR_DEGREES,
+    }
+}
+
+/// Whether or not the numeral of the quality is written in upper case, i.e. whether its third
+/// is major (or, for suspended chords, whether the quality is not minor).
+fn is_upper_case(quality: ChordQuality) -> bool {
+    quality.intervals().get(1).map_or(true, |&third| third != 3)
+}
+
+/// The symbol following a numeral of the quality (e.g. `ø7` for `viiø7`).
+fn suffix(quality: ChordQuality) -> &'static str {
+    match quality {
+        ChordQuality::Major | ChordQuality::Minor => "",
+        ChordQuality::Diminished => "\u{B0}",
+        ChordQuality::Augmented => "+",
+        ChordQuality::Suspended2 => "sus2",
+        ChordQuality::Suspended4 => "sus4",
+        ChordQuality::Major6 | ChordQuality::Minor6 => "add6",
+        ChordQuality::Dominant7 | ChordQuality::Minor7 => "7",
+        ChordQuality::Major7 => "maj7",
+        ChordQuality::MinorMajor7 => "(maj7)",
+        ChordQuality::HalfDiminished7 => "\u{F8}7",
+        ChordQuality::Diminished7 => "\u{B0}7",
+        ChordQuality::Dominant9 | ChordQuality::Minor9 => "9",
+        ChordQuality::Major9 => "maj9",
+    }
+}
+
+impl RomanNumeral {
+
+    /// Construct a RomanNumeral, returning `Error::OutOfRange` for a degree outside `1` to `7`.
+    pub fn new(degree: u8, alteration: i8, quality: ChordQuality) -> Result<RomanNumeral, Error> {
+        if !(1..=7).contains(&degree) {
+            return Err(Error::OutOfRange);
+        }
+        Ok(RomanNumeral { degree, alteration, quality })
+    }
+
+    /// The Roman numeral of the chord within the given key.
+    ///
+    /// The degree follows the spelling of the root, so that `Bb` in `C` major is `bVII` while
+    /// `Ash` is `#VI`.
+    pub fn analyze(key: Key, chord: &Chord) -> RomanNumeral {
+        let tonic = key.tonic();
+        let index = modulo(chord.root.diatonic_index() as i8 - tonic.diatonic_index() as i8, 7);
+        let above_tonic = chord.root.to_i8().unwrap() - tonic.to_i8().unwrap();
+        let natural = degrees(key)[index as usize] as i8;
+        RomanNumeral {
+            degree: index as u8 + 1,
+            alteration: modulo(above_tonic - natural + 6, 12) - 6,
+            quality: chord.quality,
+        }
+    }
+
+    /// The index of the degree within the scale, from `0` for the tonic to `6`.
+    fn index(&self) -> usize {
+        modulo(self.degree as i16 - 1, 7) as usize
+    }
+
+    /// The chord of this numeral within the given key, in root position, with its root spelled
+    /// according to the degree.
+    pub fn chord(&self, key: Key) -> Chord {
+        let tonic = key.tonic();
+        let index = self.index();
+        let above_tonic = degrees(key)[index] as i16 + self.alteration as i16;
+        let pitch_class = modulo(tonic.to_i16().unwrap() + above_tonic, 12) as u8;
+        let natural = Letter::from_diatonic_index(tonic.diatonic_index() + index as u8);
+        Chord::new(Letter::spelled_from(natural, pitch_class), self.quality)
+    }
+
+}
+
+impl Key {
+
+    /// The Roman numeral of the chord within the key, see `RomanNumeral::analyze`.
+    #[inline]
+    pub fn roman_numeral(&self, chord: &Chord) -> RomanNumeral {
+        RomanNumeral::analyze(*self, chord)
+    }
+
+}
+
+impl fmt::Display for RomanNumeral {
+    /// Formats the numeral with any alteration as ASCII accidentals (e.g. `bVII`), or as Unicode
+    /// accidentals when the alternate flag is given (e.g. `{:#}` gives `♭VII`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let accidental = match (self.alteration > 0, f.alternate()) {
+            (true, false) => "#",
+            (false, false) => "b",
+            (true, true) => "\u{266F}",
+            (false, true) => "\u{266D}",
+        };
+        for _ in 0..self.alteration.unsigned_abs() {
+            f.write_str(accidental)?;
+        }
+        let numeral = NUMERALS[self.index()];
+        if is_upper_case(self.quality) {
+            f.write_str(numeral)?;
+        } else {
+            f.write_str(&numeral.to_lowercase())?;
+        }
+        f.write_str(suffix(self.quality))
+    }
+}
+
+impl FromStr for RomanNumeral {
+    type Err = Error;
+    /// Parse a Roman numeral such as `ii`, `V7`, `bVII`, `#iv°`, `viiø7` or `IVmaj7`, where an
+    /// upper case numeral has a major third and a lower case numeral a minor third. `o` may be
+    /// written for `°`, and `ø` alone stands for `ø7`.
+    fn from_str(s: &str) -> Result<RomanNumeral, Error> {
+        let s = s.trim();
+        let mut alteration: i8 = 0;
+        let mut rest = s;
+        loop {
+            let mut chars = rest.chars();
+            match chars.next() {
+                Some('b') | Some('\u{266D}') => alteration -= 1,
+                Some('#') | Some('\u{266F}') => alteration += 1,
+                _ => break,
+            }
+            rest = chars.as_str();
+        }
+        let length = rest.find(|c: char| !"IViv".contains(c)).unwrap_or(rest.len());
+        let (numeral, suffix) = rest.split_at(length);
+        let upper = numeral.to_uppercase();
+        let degree = NUMERALS.iter().position(|&n| n == upper)
+            .ok_or(ParseError::InvalidFormat)? as u8 + 1;
+        let quality = if numeral == upper {
+            match suffix {
+                "" => ChordQuality::Major,
+                "+" => ChordQuality::Augmented,
+                "sus2" => ChordQuality::Suspended2,
+                "sus4" => ChordQuality::Suspended4,
+                "add6" => ChordQuality::Major6,
+                "7" => ChordQuality::Dominant7,
+                "maj7" => ChordQuality::Major7,
+                "9" => ChordQuality::Dominant9,
+                "maj9" => ChordQuality::Major9,
+                _ => return Err(ParseError::InvalidFormat.into()),
+            }
+        } else if numeral == numeral.to_lowercase() {
+            match suffix {
+                "" => ChordQuality::Minor,
+                "\u{B0}" | "o" => ChordQuality::Diminished,
+                "add6" => ChordQuality::Minor6,
+                "7" => ChordQuality::Minor7,
+                "(maj7)" => ChordQuality::MinorMajor7,
+                "\u{F8}" | "\u{F8}7" => ChordQuality::HalfDiminished7,
+                "\u{B0}7" | "o7" => ChordQuality::Diminished7,
+                "9" => ChordQuality::Minor9,
+                _ => return Err(ParseError::InvalidFormat.into()),
+            }
+        } else {
+            return Err(ParseError::InvalidFormat.into());
+        };
+        Ok(RomanNumeral { degree, alteration, quality })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analysis() {
+        let c_major = Key(Letter::C, Mode::Major);
+        let label = |root, quality| {
+            format!("{}", c_major.roman_numeral(&Chord::new(root, quality)))
+        };
+        assert_eq!(label(Letter::D, ChordQuality::Minor), "ii");
+        assert_eq!(label(Letter::G, ChordQuality::Dominant7), "V7");
+        assert_eq!(label(Letter::Bb, ChordQuality::Major), "bVII");
+        assert_eq!(label(Letter::Ash, ChordQuality::Major), "#VI");
+        assert_eq!(label(Letter::B, ChordQuality::HalfDiminished7), "vii\u{F8}7");
+        let a_minor = Key(Letter::A, Mode::Minor);
+        let leading = Chord::new(Letter::Gsh, ChordQuality::Diminished);
+        assert_eq!(format!("{}", a_minor.roman_numeral(&leading)), "#vii\u{B0}");
+        let subtonic = Chord::new(Letter::G, ChordQuality::Major);
+        assert_eq!(format!("{:#}", a_minor.roman_numeral(&subtonic)), "VII");
+    }
+
+    #[test]
+    fn chords_from_numerals() {
+        let e_flat = Key(Letter::Eb, Mode::Major);
+        let chord = |s: &str| s.parse::<RomanNumeral>().unwrap().chord(e_flat);
+        assert_eq!(chord("V7"), Chord::new(Letter::Bb, ChordQuality::Dominant7));
+        assert_eq!(chord("ii").letters(), vec![Letter::F, Letter::Ab, Letter::C]);
+        // There is no `Cb`, so the flattened sixth falls back to `B`.
+        assert_eq!(chord("bVI").root, Letter::B);
+        assert_eq!(chord("viio7"), Chord::new(Letter::D, ChordQuality::Diminished7));
+        for &numeral in &["ii", "V7", "bVII", "#iv\u{B0}", "vii\u{F8}7", "IVmaj7", "i(maj7)"] {
+            assert_eq!(format!("{}", numeral.parse::<RomanNumeral>().unwrap()), numeral);
+        }
+        assert!("X".parse::<RomanNumeral>().is_err());
+        assert!("Vo".parse::<RomanNumeral>().is_err());
+        assert_eq!(RomanNumeral::new(8, 0, ChordQuality::Major), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn out_of_range_degrees() {
+        // Degrees given directly through the fields wrap around the scale rather than panicking.
+        let c_major = Key(Letter::C, Mode::Major);
+        let numeral = |degree, alteration| RomanNumeral { degree, alteration,
+                                                          quality: ChordQuality::Major };
+        assert_eq!(format!("{}", numeral(0, 0)), "VII");
+        assert_eq!(numeral(0, 0).chord(c_major).root, Letter::B);
+        assert_eq!(format!("{}", numeral(8, 0)), "I");
+        assert_eq!(numeral(9, 0).chord(c_major).root, Letter::D);
+        assert_eq!(numeral(255, 0).chord(c_major), numeral(3, 0).chord(c_major));
+        assert_eq!(format!("{}", numeral(1, -128)).len(), 129);
+        assert_eq!(numeral(1, i8::MAX).chord(c_major).root, Letter::G);
+    }
+}
@@ -0,0 +1,99 @@
+//! Writing of Scala scale (`.scl`) and keyboard mapping (`.kbm`) files, allowing tunings designed
+//! with the crate to be loaded by Scala itself and by the many synthesizers supporting it.
+
+use std::fmt::Write;
+use super::{
+    calc,
+    OCTAVE_CENTS,
+    Scale,
+    Step,
+    Tuning,
+    nearest_step,
+};
+
+/// The MIDI note to which the first degree of a Tuning's scale (always `C`) is mapped.
+const MIDDLE_C: i32 = 60;
+
+/// Write the given scale in the Scala `.scl` format with the given one line description.
+///
+/// Each degree above the tonic is written in cents, followed by the period (as `2/1` for the
+/// octave).
+pub fn write_scl(scale: &Scale, description: &str) -> String {
+    let mut scl = String::new();
+    writeln!(scl, "! {}", description).unwrap();
+    writeln!(scl, "!").unwrap();
+    writeln!(scl, "{}", description).unwrap();
+    writeln!(scl, " {}", scale.len()).unwrap();
+    writeln!(scl, "!").unwrap();
+    for &degree in scale.degrees.iter().skip(1) {
+        writeln!(scl, " {:.5}", degree).unwrap();
+    }
+    if scale.period == OCTAVE_CENTS {
+        writeln!(scl, " 2/1").unwrap();
+    } else {
+        writeln!(scl, " {:.5}", scale.period).unwrap();
+    }
+    scl
+}
+
+/// Write a Scala `.kbm` keyboard mapping placing the scale of the given Tuning (see
+/// `Tuning::scale`) across all 128 MIDI notes, with `C 4` on note `60`.
+///
+/// The reference frequency is given for the MIDI note nearest to the Tuning's reference pitch, so
+/// that `write_scl(&tuning.scale(), ..)` and `write_kbm(&tuning)` together reproduce the Tuning.
+pub fn write_kbm(tuning: &Tuning) -> String {
+    let reference_note = nearest_step(tuning.reference_step) as i32;
+    let reference_hz = tuning.hz(Step(reference_note as calc::Step)).hz();
+    let mut kbm = String::new();
+    writeln!(kbm, "! Map size").unwrap();
+    writeln!(kbm, "12").unwrap();
+    writeln!(kbm, "! First MIDI note number to retune").unwrap();
+    writeln!(kbm, "0").unwrap();
+    writeln!(kbm, "! Last MIDI note number to retune").unwrap();
+    writeln!(kbm, "127").unwrap();
+    writeln!(kbm, "! Middle note where the first entry of the mapping is mapped to").unwrap();
+    writeln!(kbm, "{}", MIDDLE_C).unwrap();
+    writeln!(kbm, "! Reference note for which frequency is given").unwrap();
+    writeln!(kbm, "{}", reference_note).unwrap();
+    writeln!(kbm, "! Frequency to tune the above note to").unwrap();
+    writeln!(kbm, "{:.6}", reference_hz).unwrap();
+    writeln!(kbm, "! Scale degree to consider as formal octave").unwrap();
+    writeln!(kbm, "12").unwrap();
+    writeln!(kbm, "! Mapping").unwrap();
+    for degree in 0..12 {
+        writeln!(kbm, "{}", degree).unwrap();
+    }
+    kbm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{Ratio, Temperament};
+
+    #[test]
+    fn scl() {
+        let scale = Scale::from_ratios(vec![Ratio(9, 8), Ratio(5, 4), Ratio(3, 2)]).unwrap();
+        let scl = write_scl(&scale, "Just tetrachord");
+        let lines: Vec<&str> = scl.lines().filter(|line| !line.starts_with('!')).collect();
+        assert_eq!(lines, vec!["Just tetrachord", " 4", " 203.91000", " 386.31372",
+                               " 701.95502", " 2/1"]);
+        let tritave = Scale::new(vec![0.0, 146.3], 1901.955).unwrap();
+        assert!(write_scl(&tritave, "").ends_with(" 146.30000\n 1901.95496\n"));
+    }
+
+    #[test]
+    fn tempered_tuning() {
+        let tuning = Tuning::from_a4(415.0).with_temperament(Temperament::Vallotti);
+        let scale = tuning.scale();
+        assert_eq!(scale.len(), 12);
+        assert!((scale.degrees[5] - 501.955).abs() < 0.001);
+        let kbm = write_kbm(&tuning);
+        let lines: Vec<&str> = kbm.lines().filter(|line| !line.starts_with('!')).collect();
+        assert_eq!(&lines[..7], &["12", "0", "127", "60", "69", "415.000000", "12"]);
+        assert_eq!(lines.len(), 19);
+        // The mapping reproduces the tuning: C 4 lies below the reference by the scale's A.
+        let c4 = 415.0 / (scale.degrees[9] as f64 / 1200.0).exp2();
+        assert!((c4 - tuning.hz(Step(60.0)).hz() as f64).abs() < 0.01);
+    }
+}
@@ -0,0 +1,397 @@
+use super::{
+    calc,
+    Error,
+    PitchRange,
+    Ratio,
+    Step,
+};
+
+/// The size of an octave in cents.
+pub const OCTAVE_CENTS: calc::Cents = 1200.0;
+
+/// The largest difference in cents between two degrees for `DiatonicMode::of` to consider them
+/// the same.
+const MODE_TOLERANCE: calc::Cents = 0.5;
+
+/// The seven modes of the diatonic scale, in the order of the degree of the major scale from
+/// which each begins.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum DiatonicMode {
+    Ionian,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Aeolian,
+    Locrian,
+}
+
+/// Every DiatonicMode variant, in order of the degree of the major scale from which each begins.
+pub const ALL_DIATONIC_MODES: [DiatonicMode; 7] = [
+    DiatonicMode::Ionian,
+    DiatonicMode::Dorian,
+    DiatonicMode::Phrygian,
+    DiatonicMode::Lydian,
+    DiatonicMode::Mixolydian,
+    DiatonicMode::Aeolian,
+    DiatonicMode::Locrian,
+];
+
+impl DiatonicMode {
+
+    /// The degree of the major scale from which this mode begins, counting the tonic as `0`.
+    #[inline]
+    pub fn rotation(&self) -> usize {
+        ALL_DIATONIC_MODES.iter().position(|mode| mode == self).unwrap()
+    }
+
+    /// The 12-TET scale of the mode.
+    pub fn scale(&self) -> Scale {
+        let major = Scale::normalize(vec![200.0, 400.0, 500.0, 700.0, 900.0, 1100.0], OCTAVE_CENTS);
+        major.mode(self.rotation())
+    }
+
+    /// The diatonic mode whose interval pattern the given scale follows, if any. Degrees are
+    /// compared to the 12-TET modes to within half a cent.
+    pub fn of(scale: &Scale) -> Option<DiatonicMode> {
+        ALL_DIATONIC_MODES.iter().cloned().find(|mode| {
+            let other = mode.scale();
+            scale.len() == other.len()
+                && (scale.period - other.period).abs() <= MODE_TOLERANCE
+                && scale.compare(&other).max_deviation <= MODE_TOLERANCE
+        })
+    }
+
+}
+
+/// A scale described by the size of each degree above the tonic in cents, repeating every
+/// `period` cents (usually the octave).
+///
+/// Unlike `Letter`s, the degrees may lie anywhere within the period, so a Scale can describe any
+/// tuning, including quarter tones and non-octave or non-12-TET systems.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize))]
+pub struct Scale {
+    /// The distance of each degree above the tonic in cents, in ascending order and lying within
+    /// `[0, period)`. The first degree is always the tonic itself.
+    pub degrees: Vec<calc::Cents>,
+    /// The interval in cents after which the scale repeats.
+    pub period: calc::Cents,
+}
+
+impl Scale {
+
+    /// Construct a Scale from the given degrees in cents and period.
+    ///
+    /// The degrees are wrapped into the period, sorted and deduplicated, and the tonic is added
+    /// if it is missing.
+    ///
+    /// Returns `Error::InvalidValue` if the period or any degree is not finite, and
+    /// `Error::OutOfRange` if the period is not greater than zero.
+    pub fn new<I>(degrees: I, period: calc::Cents) -> Result<Scale, Error>
+        where I: IntoIterator<Item=calc::Cents>,
+    {
+        let degrees: Vec<calc::Cents> = degrees.into_iter().collect();
+        if !period.is_finite() || degrees.iter().any(|cents| !cents.is_finite()) {
+            return Err(Error::InvalidValue);
+        }
+        if period <= 0.0 {
+            return Err(Error::OutOfRange);
+        }
+        Ok(Scale::normalize(degrees, period))
+    }
+
+    /// Construct a Scale as with `Scale::new`, but without validating the degrees or period.
+    ///
+    /// This never panics, though invalid input produces `NaN` degrees.
+    pub(crate) fn normalize<I>(degrees: I, period: calc::Cents) -> Scale
+        where I: IntoIterator<Item=calc::Cents>,
+    {
+        let mut degrees: Vec<calc::Cents> = degrees.into_iter()
+            .map(|cents| cents.rem_euclid(period))
+            .collect();
+        degrees.push(0.0);
+        degrees.sort_by(|a, b| a.total_cmp(b));
+        degrees.dedup();
+        Scale { degrees, period }
+    }
+
+    /// Construct an octave-repeating Scale from the frequency ratio of each degree above the
+    /// tonic.
+    ///
+    /// Returns `Error::InvalidValue` if any ratio has a zero term.
+    pub fn from_ratios<I>(ratios: I) -> Result<Scale, Error>
+        where I: IntoIterator<Item=Ratio>,
+    {
+        Scale::new(ratios.into_iter().map(|ratio| ratio.cents()), OCTAVE_CENTS)
+    }
+
+    /// The scale dividing the octave into `divisions` equal steps (e.g. `12` for 12-TET).
+    pub fn equal_temperament(divisions: u32) -> Scale {
+        let step = OCTAVE_CENTS / divisions as calc::Cents;
+        Scale::normalize((0..divisions).map(|i| i as calc::Cents * step), OCTAVE_CENTS)
+    }
+
+    /// The number of degrees within a single period.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.degrees.len()
+    }
+
+    /// Whether or not the scale has no degrees.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.degrees.is_empty()
+    }
+
+    /// The distance in cents of the given degree above the tonic. Degrees beyond the length of
+    /// the scale continue into the following periods, while negative degrees descend below the
+    /// tonic.
+    ///
+    /// Panics if the scale has no degrees, which is only possible by emptying `degrees` directly.
+    pub fn cents(&self, degree: i32) -> calc::Cents {
+        let len = self.len() as i32;
+        let periods = degree.div_euclid(len);
+        self.degrees[degree.rem_euclid(len) as usize] + periods as calc::Cents * self.period
+    }
+
+    /// The pitch of the given degree when the scale is played from the given tonic.
+    #[inline]
+    pub fn pitch<T: Into<Step>>(&self, tonic: T, degree: i32) -> Step {
+        Step(tonic.into().step() + self.cents(degree) / 100.0)
+    }
+
+    /// The degree of the scale played from `tonic` that lies closest to the given pitch.
+    pub fn nearest_degree<T, P>(&self, tonic: T, pitch: P) -> i32
+        where T: Into<Step>,
+              P: Into<Step>,
+    {
+        let offset = (pitch.into().step() - tonic.into().step()) * 100.0;
+        let periods = (offset / self.period).floor() as i32;
+        let within = offset - periods as calc::Cents * self.period;
+        let len = self.len() as i32;
+        let mut nearest = (0, within.abs());
+        for (i, &degree) in self.degrees.iter().enumerate().skip(1) {
+            let distance = (within - degree).abs();
+            if distance < nearest.1 {
+                nearest = (i as i32, distance);
+            }
+        }
+        if self.period - within < nearest.1 {
+            nearest = (len, self.period - within);
+        }
+        periods * len + nearest.0
+    }
+
+    /// Quantize the given pitch to the closest pitch of the scale played from `tonic`.
+    #[inline]
+    pub fn quantize<T, P>(&self, tonic: T, pitch: P) -> Step
+        where T: Into<Step>,
+              P: Into<Step>,
+    {
+        let tonic = tonic.into();
+        self.pitch(tonic, self.nearest_degree(tonic, pitch))
+    }
+
+    /// An iterator yielding every pitch of the scale played from `tonic`, ascending from the
+    /// tonic without end.
+    #[inline]
+    pub fn pitches<T: Into<Step>>(&self, tonic: T) -> Pitches<'_> {
+        Pitches { scale: self, tonic: tonic.into(), degree: 0 }
+    }
+
+    /// Every pitch of the scale played from `tonic` that lies within the given range.
+    ///
+    /// Returns no pitches when the tonic or either bound is not finite, or when the range is
+    /// inverted.
+    pub fn pitches_within<T: Into<Step>>(&self, tonic: T, range: PitchRange) -> Vec<Step> {
+        let tonic = tonic.into();
+        let (lowest, highest) = (range.lowest().step(), range.highest().step());
+        // Otherwise no pitch would ever lie above the range and the search would never end.
+        let finite = tonic.step().is_finite() && lowest.is_finite() && highest.is_finite();
+        if !finite || lowest > highest {
+            return Vec::new();
+        }
+        let first = self.nearest_degree(tonic, range.lowest()) - 1;
+        (first..)
+            .map(|degree| self.pitch(tonic, degree))
+            .take_while(|pitch| pitch.step() <= highest)
+            .filter(|pitch| range.contains(*pitch))
+            .collect()
+    }
+
+    /// The mode of the scale beginning from the given degree (counting the tonic as `0`), i.e.
+    /// the same degrees rotated so that the given degree becomes the tonic. For example `mode(1)`
+    /// of the major scale is the Dorian mode. Degrees wrap around the length of the scale.
+    pub fn mode(&self, degree: usize) -> Scale {
+        if self.is_empty() {
+            return self.clone();
+        }
+        let start = self.degrees[degree % self.len()];
+        Scale::normalize(self.degrees.iter().map(|&cents| cents - start), self.period)
+    }
+
+    /// Compare each degree of this scale against the corresponding degree of `other` (e.g. 12-TET
+    /// against quarter-comma meantone).
+    ///
+    /// When both scales have the same number of degrees they are compared degree by degree.
+    /// Otherwise each degree of this scale is compared against the nearest degree of `other`.
+    pub fn compare(&self, other: &Scale) -> Comparison {
+        let deviations: Vec<calc::Cents> = if self.len() == other.len() {
+            self.degrees.iter().zip(other.degrees.iter()).map(|(a, b)| b - a).collect()
+        } else {
+            self.degrees.iter().enumerate()
+                .map(|(i, &cents)| {
+                    let pitch = Step(cents / 100.0);
+                    other.cents(other.nearest_degree(Step(0.0), pitch)) - self.cents(i as i32)
+                })
+                .collect()
+        };
+        let count = deviations.len().max(1) as calc::Cents;
+        let max_deviation = deviations.iter().fold(0.0, |max: calc::Cents, d| max.max(d.abs()));
+        let mean_deviation = deviations.iter().map(|d| d.abs()).sum::<calc::Cents>() / count;
+        let rms_deviation = (deviations.iter().map(|d| d * d).sum::<calc::Cents>() / count).sqrt();
+        Comparison { deviations, max_deviation, mean_deviation, rms_deviation }
+    }
+
+}
+
+/// Deserializes from a `degrees` and `period` map, validated and normalized as by `Scale::new`.
+#[cfg(feature = "serde_serialization")]
+impl<'de> ::serde::Deserialize<'de> for Scale {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Scale, D::Error> {
+        use serde::de::Error as DeError;
+
+        #[derive(Deserialize)]
+        #[serde(rename = "Scale")]
+        struct Fields {
+            degrees: Vec<calc::Cents>,
+            period: calc::Cents,
+        }
+
+        let fields = <Fields as ::serde::Deserialize>::deserialize(deserializer)?;
+        Scale::new(fields.degrees, fields.period).map_err(D::Error::custom)
+    }
+}
+
+/// A report on the deviation of one scale from another, produced by `Scale::compare`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Comparison {
+    /// The deviation of each degree in cents, positive where the other scale is sharper.
+    pub deviations: Vec<calc::Cents>,
+    /// The largest absolute deviation in cents.
+    pub max_deviation: calc::Cents,
+    /// The mean absolute deviation in cents.
+    pub mean_deviation: calc::Cents,
+    /// The root mean square deviation in cents.
+    pub rms_deviation: calc::Cents,
+}
+
+/// An iterator yielding each pitch of a Scale in ascending order.
+#[derive(Clone, Debug)]
+pub struct Pitches<'a> {
+    scale: &'a Scale,
+    tonic: Step,
+    degree: i32,
+}
+
+impl<'a> Iterator for Pitches<'a> {
+    type Item = Step;
+    fn next(&mut self) -> Option<Step> {
+        let pitch = self.scale.pitch(self.tonic, self.degree);
+        self.degree += 1;
+        Some(pitch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn construction() {
+        let scale = Scale::new(vec![700.0, 400.0, 1200.0, 1900.0], OCTAVE_CENTS).unwrap();
+        assert_eq!(scale.degrees, vec![0.0, 400.0, 700.0]);
+        assert_eq!(Scale::equal_temperament(12).len(), 12);
+        assert_eq!(Scale::new(vec![calc::Cents::NAN], OCTAVE_CENTS), Err(Error::InvalidValue));
+        assert_eq!(Scale::new(vec![700.0], calc::Cents::INFINITY), Err(Error::InvalidValue));
+        assert_eq!(Scale::new(vec![700.0], 0.0), Err(Error::OutOfRange));
+        assert_eq!(Scale::new(vec![700.0], -1200.0), Err(Error::OutOfRange));
+        assert_eq!(Scale::from_ratios(vec![Ratio(3, 2), Ratio(1, 0)]), Err(Error::InvalidValue));
+        assert_eq!(Scale::from_ratios(vec![Ratio(3, 2)]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn degrees_wrap_into_periods() {
+        let major = DiatonicMode::Ionian.scale();
+        assert_eq!(major.cents(7), 1200.0);
+        assert_eq!(major.cents(-1), -100.0);
+        assert_eq!(major.pitch(Step(60.0), 9), Step(76.0));
+    }
+
+    #[test]
+    fn quantize() {
+        let major = DiatonicMode::Ionian.scale();
+        assert_eq!(major.quantize(Step(60.0), Step(61.4)), Step(62.0));
+        assert_eq!(major.quantize(Step(60.0), Step(71.8)), Step(72.0));
+        assert_eq!(major.quantize(Step(60.0), Step(58.6)), Step(59.0));
+        assert_eq!(major.nearest_degree(Step(60.0), Step(48.0)), -7);
+        assert_eq!(major.nearest_degree(Step(60.0), Step(47.0)), -8);
+    }
+
+    #[test]
+    fn pitches() {
+        let pentatonic = Scale::new(vec![200.0, 400.0, 700.0, 900.0], OCTAVE_CENTS).unwrap();
+        let pitches: Vec<Step> = pentatonic.pitches(Step(60.0)).take(6).collect();
+        assert_eq!(pitches[5], Step(72.0));
+        let within = pentatonic.pitches_within(Step(60.0), PitchRange(Step(61.0), Step(72.0)));
+        assert_eq!(within, vec![Step(62.0), Step(64.0), Step(67.0), Step(69.0), Step(72.0)]);
+        // A range between two degrees holds no pitches.
+        let empty = |range| pentatonic.pitches_within(Step(60.0), range).is_empty();
+        assert!(empty(PitchRange(Step(65.0), Step(66.0))));
+        // Inverted and non-finite ranges give no pitches rather than searching forever.
+        assert!(empty(PitchRange(Step(72.0), Step(60.0))));
+        assert!(empty(PitchRange(Step(calc::Step::NAN), Step(72.0))));
+        assert!(empty(PitchRange(Step(60.0), Step(calc::Step::INFINITY))));
+        assert!(empty(PitchRange(Step(calc::Step::NEG_INFINITY), Step(72.0))));
+        assert!(pentatonic.pitches_within(Step(calc::Step::NAN), PitchRange(Step(60.0), Step(72.0)))
+            .is_empty());
+    }
+
+    #[test]
+    fn modes() {
+        let major = DiatonicMode::Ionian.scale();
+        let dorian = major.mode(1);
+        assert_eq!(dorian.degrees, vec![0.0, 200.0, 300.0, 500.0, 700.0, 900.0, 1000.0]);
+        assert_eq!(major.mode(7), major);
+        assert_eq!(DiatonicMode::of(&dorian), Some(DiatonicMode::Dorian));
+        assert_eq!(DiatonicMode::of(&major.mode(5)), Some(DiatonicMode::Aeolian));
+        for &mode in &ALL_DIATONIC_MODES {
+            assert_eq!(DiatonicMode::of(&mode.scale()), Some(mode));
+        }
+        let pentatonic = Scale::new(vec![200.0, 400.0, 700.0, 900.0], OCTAVE_CENTS).unwrap();
+        assert_eq!(DiatonicMode::of(&pentatonic), None);
+        assert_eq!(DiatonicMode::of(&Scale::equal_temperament(7)), None);
+    }
+
+    #[test]
+    fn comparison() {
+        let tet = Scale::equal_temperament(12);
+        let detune = |(i, cents): (usize, &calc::Cents)| match i {
+            0 => 0.0,
+            4 => cents - 14.0,
+            _ => cents + 2.0,
+        };
+        let detuned = Scale::new(tet.degrees.iter().enumerate().map(detune), OCTAVE_CENTS).unwrap();
+        let report = tet.compare(&detuned);
+        assert_eq!(report.deviations.len(), 12);
+        assert!((report.max_deviation - 14.0).abs() < 0.001);
+        assert!((report.mean_deviation - 34.0 / 12.0).abs() < 0.001);
+        // Scales of different sizes are compared against the nearest degree.
+        let pentatonic = Scale::new(vec![200.0, 400.0, 700.0, 900.0], OCTAVE_CENTS).unwrap();
+        assert_eq!(pentatonic.compare(&tet).max_deviation, 0.0);
+        assert_eq!(tet.compare(&pentatonic).deviations[1], -100.0);
+    }
+}
@@ -0,0 +1,128 @@
+//! Scale degrees, allowing melodies to be written relative to a scale and tonic so that they may
+//! be re-rooted or played in another scale.
+
+use std::fmt;
+use std::str::FromStr;
+use super::{
+    Error,
+    Letter,
+    LetterOctave,
+    Octave,
+    ParseError,
+    Scale,
+    Step,
+};
+
+/// A degree of a scale counted from `1` for the tonic, optionally altered by a number of
+/// semitones (e.g. the `b3` of a blues melody over a major scale).
+///
+/// Degrees beyond the length of the scale continue into the following periods, so that `9` is
+/// the second degree an octave up within a seven note scale.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct ScaleDegree {
+    /// The degree counted from `1` for the tonic.
+    pub degree: u8,
+    /// The alteration in semitones, e.g. `-1` for a flattened degree.
+    pub alteration: i8,
+}
+
+impl ScaleDegree {
+
+    /// Construct a ScaleDegree, returning `Error::OutOfRange` for a degree of zero.
+    pub fn new(degree: u8, alteration: i8) -> Result<ScaleDegree, Error> {
+        if degree == 0 {
+            return Err(Error::OutOfRange);
+        }
+        Ok(ScaleDegree { degree, alteration })
+    }
+
+    /// The same degree lowered by a semitone.
+    #[inline]
+    pub fn flat(self) -> ScaleDegree {
+        ScaleDegree { alteration: self.alteration - 1, ..self }
+    }
+
+    /// The same degree raised by a semitone.
+    #[inline]
+    pub fn sharp(self) -> ScaleDegree {
+        ScaleDegree { alteration: self.alteration + 1, ..self }
+    }
+
+    /// The pitch of the degree within the given scale played from the given tonic.
+    #[inline]
+    pub fn pitch<T: Into<Step>>(&self, scale: &Scale, tonic: T) -> Step {
+        let pitch = scale.pitch(tonic, self.degree as i32 - 1);
+        Step(pitch.step() + self.alteration as f32)
+    }
+
+    /// The pitch of the degree within the given scale played from the given tonic letter in the
+    /// given octave.
+    #[inline]
+    pub fn pitch_in_octave(&self, scale: &Scale, tonic: Letter, octave: Octave) -> Step {
+        self.pitch(scale, LetterOctave(tonic, octave))
+    }
+
+}
+
+impl fmt::Display for ScaleDegree {
+    /// Formats the degree preceded by any alteration as ASCII accidentals (e.g. `b3`), or as
+    /// Unicode accidentals when the alternate flag is given (e.g. `{:#}` gives `♭3`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let accidental = match (self.alteration > 0, f.alternate()) {
+            (true, false) => "#",
+            (false, false) => "b",
+            (true, true) => "\u{266F}",
+            (false, true) => "\u{266D}",
+        };
+        for _ in 0..self.alteration.abs() {
+            f.write_str(accidental)?;
+        }
+        write!(f, "{}", self.degree)
+    }
+}
+
+impl FromStr for ScaleDegree {
+    type Err = Error;
+    /// Parse a degree preceded by any number of accidentals, e.g. `5`, `b3`, `#4` or `♭7`.
+    fn from_str(s: &str) -> Result<ScaleDegree, Error> {
+        let s = s.trim();
+        let digits = s.trim_start_matches(|c| "b#\u{266D}\u{266F}".contains(c));
+        let alteration = s[..s.len() - digits.len()].chars()
+            .map(|c| if c == 'b' || c == '\u{266D}' { -1 } else { 1 })
+            .sum();
+        let degree = digits.parse::<u8>().map_err(|_| ParseError::InvalidNumber)?;
+        ScaleDegree::new(degree, alteration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{DiatonicMode, OCTAVE_CENTS};
+
+    #[test]
+    fn degrees_within_scales() {
+        let major = DiatonicMode::Ionian.scale();
+        let melody: Vec<ScaleDegree> = ["1", "b3", "5", "9"].iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let in_c: Vec<Step> = melody.iter()
+            .map(|d| d.pitch_in_octave(&major, Letter::C, 4))
+            .collect();
+        assert_eq!(in_c, vec![Step(60.0), Step(63.0), Step(67.0), Step(74.0)]);
+        // Re-rooting the same melody requires no recomputation of the degrees.
+        assert_eq!(melody[1].pitch(&major, LetterOctave(Letter::A, 3)), Step(60.0));
+        let pentatonic = Scale::new(vec![200.0, 400.0, 700.0, 900.0], OCTAVE_CENTS).unwrap();
+        assert_eq!(melody[2].pitch(&pentatonic, Step(60.0)), Step(69.0));
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!("#4".parse(), Ok(ScaleDegree { degree: 4, alteration: 1 }));
+        assert_eq!(format!("{:#}", ScaleDegree::new(7, 0).unwrap().flat()), "\u{266D}7");
+        assert_eq!(format!("{}", ScaleDegree::new(2, -2).unwrap()), "bb2");
+        assert_eq!("b".parse::<ScaleDegree>(), Err(ParseError::InvalidNumber.into()));
+        assert_eq!("0".parse::<ScaleDegree>(), Err(Error::OutOfRange));
+    }
+}
@@ -1,25 +1,37 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
 use super::{
     calc,
     Hz,
     LetterOctave,
     Letter,
+    LogPerc,
     Mel,
     Octave,
+    OctaveFraction,
     Perc,
+    Spelling,
     Step,
     hz_from_scaled_perc,
     letter_octave_from_scaled_perc,
+    log_perc_from_hz,
     mel_from_scaled_perc,
+    octave_fraction_from_step,
     perc_from_scaled_perc,
     step_from_scaled_perc,
 };
 
 pub type ScaleWeight = calc::Weight;
 pub const DEFAULT_SCALE_WEIGHT: ScaleWeight = 4.0;
+/// The maximum error in cents introduced by converting a pitch to a ScaledPerc and back.
+pub const ROUND_TRIP_TOLERANCE: calc::Cents = 0.01;
 
 /// Pitch representation in the form of a scaled percentage between the min and max hz.
+///
+/// Converting any pitch to a ScaledPerc and back is accurate to within `ROUND_TRIP_TOLERANCE`
+/// cents, for every weight and including pitches that lie outside the min and max hz.
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct ScaledPerc(pub calc::Perc, pub ScaleWeight);
@@ -84,6 +96,12 @@ impl ScaledPerc {
         LetterOctave(letter, octave)
     }
 
+    /// Convert to the closest LetterOctave, spelling any black key with the given preference.
+    #[inline]
+    pub fn to_letter_octave_with_spelling(&self, spelling: Spelling) -> LetterOctave {
+        self.to_letter_octave().spelled(spelling)
+    }
+
     /// Convert to the unit value of a Mel.
     #[inline]
     pub fn mel(&self) -> calc::Mel {
@@ -110,6 +128,19 @@ impl ScaledPerc {
         Perc(self.perc())
     }
 
+    /// Convert to the unit value of a percentage of the human hearing range in log-frequency
+    /// space.
+    #[inline]
+    pub fn log_perc(&self) -> calc::Perc {
+        log_perc_from_hz(self.hz())
+    }
+
+    /// Convert to a percentage of the human hearing range in log-frequency space.
+    #[inline]
+    pub fn to_log_perc(&self) -> LogPerc {
+        LogPerc(self.log_perc())
+    }
+
     /// Convert to the unit value of a Step.
     #[inline]
     pub fn step(&self) -> calc::Step {
@@ -123,6 +154,53 @@ impl ScaledPerc {
         Step(self.step())
     }
 
+    /// Convert to a fractional octave number.
+    #[inline]
+    pub fn octave_fraction(&self) -> calc::OctaveFraction {
+        octave_fraction_from_step(self.step())
+    }
+
+    /// Convert to an OctaveFraction struct.
+    #[inline]
+    pub fn to_octave_fraction(&self) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction())
+    }
+
+    /// The lower of the two pitches, compared by the unscaled percentage each represents and
+    /// ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn min(self, other: ScaledPerc) -> ScaledPerc {
+        let (a, b) = (self.to_perc().perc(), other.to_perc().perc());
+        if a.is_nan() || b < a { other } else { self }
+    }
+
+    /// The higher of the two pitches, compared by the unscaled percentage each represents and
+    /// ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn max(self, other: ScaledPerc) -> ScaledPerc {
+        let (a, b) = (self.to_perc().perc(), other.to_perc().perc());
+        if a.is_nan() || b > a { other } else { self }
+    }
+
+    /// The pitch limited to the range `min` to `max`. Never panics, even on a `NaN` pitch or
+    /// bound (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn clamp(self, min: ScaledPerc, max: ScaledPerc) -> ScaledPerc {
+        self.max(min).min(max)
+    }
+
+}
+
+impl fmt::Display for ScaledPerc {
+    /// Formats the scaled value as a percentage (i.e. multiplied by `100`), appending the unit with
+    /// the alternate flag (e.g. `37.2 %`). Any precision or width is applied to the number.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&(self.scaled_perc() * 100.0), f)?;
+        if f.alternate() {
+            f.write_str(" %")?;
+        }
+        Ok(())
+    }
 }
 
 impl Add for ScaledPerc {
@@ -176,23 +254,83 @@ impl Neg for ScaledPerc {
 impl PartialEq for ScaledPerc {
     #[inline]
     fn eq(&self, other: &ScaledPerc) -> bool {
-        self.perc() == other.perc()
+        self.cmp(other) == Ordering::Equal
     }
 }
 
 impl Eq for ScaledPerc {}
 
+impl Hash for ScaledPerc {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_perc().perc().to_bits().hash(state);
+    }
+}
+
 impl PartialOrd for ScaledPerc {
     #[inline]
     fn partial_cmp(&self, other: &ScaledPerc) -> Option<Ordering> {
-        self.to_perc().partial_cmp(&other.to_perc())
+        Some(self.cmp(other))
     }
 }
 
+/// Ordered by the unscaled percentage that each represents, so that ScaledPercs of differing
+/// weights compare by pitch, using the [total order](crate#comparison) shared by the float pitch
+/// types.
 impl Ord for ScaledPerc {
     #[inline]
     fn cmp(&self, other: &ScaledPerc) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.to_perc().perc().total_cmp(&other.to_perc().perc())
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEIGHTS: [ScaleWeight; 7] = [0.1, 0.25, 0.5, 1.0, 4.0, 16.0, 64.0];
+
+    fn assert_round_trip(step: calc::Step, weight: ScaleWeight) {
+        let back = Step(step).to_scaled_perc_with_weight(weight).to_step().step();
+        let error = (back - step).abs() * 100.0;
+        assert!(error <= ROUND_TRIP_TOLERANCE,
+                "step {} with weight {} came back as {} ({} cents)", step, weight, back, error);
+    }
+
+    #[test]
+    fn round_trip_within_range() {
+        for &weight in WEIGHTS.iter() {
+            for i in 0..1_200 {
+                assert_round_trip(16.0 + i as calc::Step * 0.1, weight);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_below_min_hz() {
+        for &weight in WEIGHTS.iter() {
+            for i in 0..400 {
+                assert_round_trip(-24.0 + i as calc::Step * 0.1, weight);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_above_max_hz() {
+        for &weight in WEIGHTS.iter() {
+            for i in 0..200 {
+                assert_round_trip(136.0 + i as calc::Step * 0.1, weight);
+            }
+        }
+    }
+
+    #[test]
+    fn display() {
+        // The scaled value is shown, whatever the weight.
+        assert_eq!(format!("{}", ScaledPerc(0.5, 4.0)), "50");
+        assert_eq!(format!("{:#}", ScaledPerc(0.5, 4.0)), "50 %");
+        assert_eq!(format!("{:#.1}", ScaledPerc(0.372, 1.0)), "37.2 %");
+        assert_eq!(format!("{:>#6.1}", ScaledPerc(0.5, 0.25)), "  50.0 %");
+    }
+}
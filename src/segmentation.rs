@@ -0,0 +1,191 @@
+//! Segmentation of pitch tracks into stable notes and the glides (glissandi, portamenti and
+//! transitions) between them.
+
+use super::{
+    calc,
+    PitchTrack,
+    Seconds,
+    Step,
+    nearest_step,
+    stats,
+};
+
+/// A region of a PitchTrack.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Segment {
+    /// The time of the first point within the segment.
+    pub start: Seconds,
+    /// The time of the first point of the following segment, or of the last point within this
+    /// segment if it is the last.
+    pub end: Seconds,
+    /// The kind of region.
+    pub kind: SegmentKind,
+}
+
+/// The kind of region covered by a Segment.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum SegmentKind {
+    /// A stable note, quantized to the nearest whole step of its median pitch.
+    Note(Step),
+    /// A region in which the pitch moves faster than a stable note allows.
+    Glide,
+    /// A region of unvoiced points.
+    Unvoiced,
+}
+
+/// The settings used to segment a PitchTrack.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Segmenter {
+    /// The fastest rate of change in semitones per second at which the pitch is still considered
+    /// stable. Vibrato reaches roughly `2π × rate × depth`, e.g. `15` semitones per second for a
+    /// 6hz vibrato of 40 cents, so this should lie above that of the expected vibrato.
+    pub max_slope: calc::Step,
+    /// The shortest stable region considered a note. Shorter stable regions are merged into the
+    /// surrounding glides.
+    pub min_note_duration: Seconds,
+}
+
+impl Default for Segmenter {
+    /// A maximum slope of `20` semitones per second and a minimum note duration of `50ms`.
+    fn default() -> Segmenter {
+        Segmenter { max_slope: 20.0, min_note_duration: 0.05 }
+    }
+}
+
+/// Segment the given track using the default `Segmenter` settings.
+#[inline]
+pub fn segment<P: Copy + Into<Step>>(track: &PitchTrack<P>) -> Vec<Segment> {
+    Segmenter::default().segment(track)
+}
+
+/// The classification of a single point before grouping.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Class {
+    Stable,
+    Glide,
+    Unvoiced,
+}
+
+impl Segmenter {
+
+    /// Split the given track into consecutive stable note, glide and unvoiced segments.
+    ///
+    /// Each voiced point is classified by the slope of the pitch about it (the central difference
+    /// in step space) and consecutive points of the same class are grouped together. A change of
+    /// note without an intervening glide (e.g. a slur sampled too coarsely to show the jump) also
+    /// begins a new note.
+    pub fn segment<P: Copy + Into<Step>>(&self, track: &PitchTrack<P>) -> Vec<Segment> {
+        let points = track.points();
+        let steps: Vec<calc::Step> = points.iter()
+            .map(|p| if p.voiced { p.pitch.into().step() } else { calc::Step::NAN })
+            .collect();
+        let classes: Vec<Class> = (0..points.len())
+            .map(|i| {
+                if steps[i].is_nan() {
+                    return Class::Unvoiced;
+                }
+                let before = if i > 0 && !steps[i - 1].is_nan() { i - 1 } else { i };
+                let after = if i + 1 < steps.len() && !steps[i + 1].is_nan() { i + 1 } else { i };
+                if before == after {
+                    return Class::Stable;
+                }
+                let slope = (steps[after] - steps[before]) as f64
+                    / (points[after].time - points[before].time);
+                if slope.abs() <= self.max_slope as f64 { Class::Stable } else { Class::Glide }
+            })
+            .collect();
+
+        // Group consecutive points of the same class (and, for notes, of the same note).
+        let mut groups: Vec<(usize, usize, Class)> = Vec::new();
+        for i in 0..points.len() {
+            let new_note = classes[i] == Class::Stable && i > 0
+                && nearest_step(steps[i]) != nearest_step(steps[i - 1]);
+            match groups.last_mut() {
+                Some(&mut (_, ref mut end, class)) if class == classes[i] && !new_note => *end = i,
+                _ => groups.push((i, i, classes[i])),
+            }
+        }
+
+        // Short stable regions become glides, merging with their neighbours.
+        let mut merged: Vec<(usize, usize, Class)> = Vec::new();
+        for (start, end, mut class) in groups {
+            let duration = points[end].time - points[start].time;
+            if class == Class::Stable && duration < self.min_note_duration {
+                class = Class::Glide;
+            }
+            match merged.last_mut() {
+                Some(&mut (_, ref mut last_end, Class::Glide)) if class == Class::Glide => {
+                    *last_end = end;
+                },
+                _ => merged.push((start, end, class)),
+            }
+        }
+
+        merged.iter().enumerate()
+            .map(|(i, &(start, end, class))| {
+                let end_time = merged.get(i + 1)
+                    .map_or(points[end].time, |next| points[next.0].time);
+                let kind = match class {
+                    Class::Stable => {
+                        let median = stats::median(steps[start..end + 1].iter().map(|&s| Step(s)));
+                        SegmentKind::Note(Step(nearest_step(median.unwrap().step())))
+                    },
+                    Class::Glide => SegmentKind::Glide,
+                    Class::Unvoiced => SegmentKind::Unvoiced,
+                };
+                Segment { start: points[start].time, end: end_time, kind }
+            })
+            .collect()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::TrackPoint;
+
+    #[test]
+    fn notes_and_glides() {
+        // C4 for 200ms, a 100ms glide up to E4, E4 for 200ms, then silence.
+        let mut track = PitchTrack::new();
+        for i in 0..60 {
+            let t = i as f64 * 0.01;
+            let step = match i {
+                0..=19 => 60.0,
+                20..=29 => 60.0 + (i - 19) as calc::Step * 0.4,
+                _ => 64.05,
+            };
+            if i >= 50 {
+                track.push_point(TrackPoint { time: t, pitch: Step(step), voiced: false,
+                                              confidence: None });
+            } else {
+                track.push(t, Step(step));
+            }
+        }
+        let segments = segment(&track);
+        let kinds: Vec<SegmentKind> = segments.iter().map(|s| s.kind).collect();
+        assert_eq!(kinds, vec![SegmentKind::Note(Step(60.0)), SegmentKind::Glide,
+                               SegmentKind::Note(Step(64.0)), SegmentKind::Unvoiced]);
+        assert_eq!(segments[0].start, 0.0);
+        assert_eq!(segments[0].end, segments[1].start);
+        assert!((segments[3].end - 0.59).abs() < 1e-9);
+        assert!(segment(&PitchTrack::<Step>::new()).is_empty());
+    }
+
+    #[test]
+    fn coarse_jumps() {
+        // A jump between two samples is too fast for either note, so becomes a short glide that
+        // is absorbed by neither note.
+        let mut track = PitchTrack::new();
+        for i in 0..20 {
+            track.push(i as f64 * 0.01, Step(if i < 10 { 67.0 } else { 69.0 }));
+        }
+        let notes: Vec<SegmentKind> = segment(&track).iter().map(|s| s.kind)
+            .filter(|kind| *kind != SegmentKind::Glide)
+            .collect();
+        assert_eq!(notes, vec![SegmentKind::Note(Step(67.0)), SegmentKind::Note(Step(69.0))]);
+    }
+}
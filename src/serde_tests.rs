@@ -1,12 +1,22 @@
 extern crate serde_json;
 
+use chord::{Chord, ChordQuality};
+use curve::Curve;
 use hz::Hz;
+use interval::Interval;
+use key::{Key, Mode};
 use letter::Letter;
 use letter_octave::LetterOctave;
 use mel::Mel;
+use midi::MidiNote;
+use octave_fraction::OctaveFraction;
 use perc::Perc;
+use scale::Scale;
 use scaled_perc::ScaledPerc;
 use step::Step;
+use temperament::Temperament;
+use tone_row::ToneRow;
+use tuning::Tuning;
 
 #[test]
 fn test_hz() {
@@ -24,6 +34,17 @@ fn test_hz() {
     assert_eq!(hz, deserialized);
 }
 
+#[test]
+fn test_hz_lenient() {
+    let hz: Hz = serde_json::from_str("440").unwrap();
+    assert_eq!(hz, Hz(440.0));
+    let hz: Hz = serde_json::from_str("\"440Hz\"").unwrap();
+    assert_eq!(hz, Hz(440.0));
+    let hz: Hz = serde_json::from_str("\"1.5 kHz\"").unwrap();
+    assert_eq!(hz, Hz(1_500.0));
+    assert!(serde_json::from_str::<Hz>("\"loud\"").is_err());
+}
+
 #[test]
 fn test_letter() {
     let div = Letter::Fsh;
@@ -52,6 +73,18 @@ fn test_letter_octave() {
     assert_eq!(letter_octave, deserialized);
 }
 
+#[test]
+fn test_letter_octave_lenient() {
+    let expected = LetterOctave(Letter::C, 4);
+    let from_map: LetterOctave = serde_json::from_str("{\"letter\":\"C\",\"octave\":4}").unwrap();
+    assert_eq!(from_map, expected);
+    let from_name: LetterOctave = serde_json::from_str("\"C4\"").unwrap();
+    assert_eq!(from_name, expected);
+    let from_sharp: LetterOctave = serde_json::from_str("\"F#-1\"").unwrap();
+    assert_eq!(from_sharp, LetterOctave(Letter::Fsh, -1));
+    assert!(serde_json::from_str::<LetterOctave>("{\"letter\":\"C\"}").is_err());
+}
+
 #[test]
 fn test_mel() {
     let mel = Mel(440.0);
@@ -66,6 +99,20 @@ fn test_mel() {
     assert_eq!(mel, deserialized);
 }
 
+#[test]
+fn test_octave_fraction() {
+    let octave = OctaveFraction(4.75);
+    let serialized = serde_json::to_string(&octave).unwrap();
+
+    println!("{}", serialized);
+    assert_eq!("4.75", &serialized);
+
+    let deserialized: OctaveFraction = serde_json::from_str(&serialized).unwrap();
+
+    println!("{:?}", deserialized);
+    assert_eq!(octave, deserialized);
+}
+
 #[test]
 fn test_perc() {
     let perc = Perc(440.0);
@@ -107,3 +154,332 @@ fn test_step() {
     println!("{:?}", deserialized);
     assert_eq!(step, deserialized);
 }
+
+#[test]
+fn test_interval() {
+    let interval: Interval = "m3".parse().unwrap();
+    let serialized = serde_json::to_string(&interval).unwrap();
+
+    println!("{}", serialized);
+    assert_eq!("{\"quality\":\"Minor\",\"number\":3}", &serialized);
+
+    let deserialized: Interval = serde_json::from_str(&serialized).unwrap();
+
+    println!("{:?}", deserialized);
+    assert_eq!(interval, deserialized);
+}
+
+#[test]
+fn test_key() {
+    let key = Key(Letter::Bb, Mode::Minor);
+    let serialized = serde_json::to_string(&key).unwrap();
+
+    println!("{}", serialized);
+    assert_eq!("[\"Bb\",\"Minor\"]", &serialized);
+
+    let deserialized: Key = serde_json::from_str(&serialized).unwrap();
+
+    println!("{:?}", deserialized);
+    assert_eq!(key, deserialized);
+}
+
+#[test]
+fn test_scale() {
+    let scale = Scale::equal_temperament(4);
+    let serialized = serde_json::to_string(&scale).unwrap();
+
+    println!("{}", serialized);
+    assert_eq!("{\"degrees\":[0.0,300.0,600.0,900.0],\"period\":1200.0}", &serialized);
+
+    let deserialized: Scale = serde_json::from_str(&serialized).unwrap();
+
+    println!("{:?}", deserialized);
+    assert_eq!(scale, deserialized);
+
+    // Deserialized scales are normalized and validated as by `Scale::new`.
+    let tonic: Scale = serde_json::from_str("{\"degrees\":[],\"period\":1200}").unwrap();
+    assert_eq!(tonic.degrees, vec![0.0]);
+    assert_eq!(tonic.cents(3), 3_600.0);
+    let wrapped: Scale = serde_json::from_str("{\"degrees\":[1500,700],\"period\":1200}").unwrap();
+    assert_eq!(wrapped.degrees, vec![0.0, 300.0, 700.0]);
+    assert!(serde_json::from_str::<Scale>("{\"degrees\":[0],\"period\":0}").is_err());
+}
+
+#[test]
+fn test_curve() {
+    let curves = [Curve::Linear, Curve::Logarithmic, Curve::Exponential(2.0), Curve::SCurve(3.0)];
+    for &curve in curves.iter() {
+        let serialized = serde_json::to_string(&curve).unwrap();
+
+        println!("{}", serialized);
+
+        let deserialized: Curve = serde_json::from_str(&serialized).unwrap();
+
+        println!("{:?}", deserialized);
+        assert_eq!(curve, deserialized);
+    }
+    assert_eq!("{\"Exponential\":2.0}", &serde_json::to_string(&Curve::Exponential(2.0)).unwrap());
+}
+
+#[test]
+fn test_midi_note() {
+    let note = MidiNote::new(60).unwrap();
+    let serialized = serde_json::to_string(&note).unwrap();
+
+    println!("{}", serialized);
+    assert_eq!("60", &serialized);
+
+    let deserialized: MidiNote = serde_json::from_str(&serialized).unwrap();
+
+    println!("{:?}", deserialized);
+    assert_eq!(note, deserialized);
+    assert!(serde_json::from_str::<MidiNote>("200").is_err());
+}
+
+#[test]
+fn test_tuning() {
+    let tuning = Tuning::from_a4(415.0).with_temperament(Temperament::Meantone(0.25));
+    let serialized = serde_json::to_string(&tuning).unwrap();
+
+    println!("{}", serialized);
+    assert_eq!(
+        "{\"reference_step\":69.0,\"reference_hz\":415.0,\"temperament\":{\"Meantone\":0.25},\
+         \"stretch\":0.0}",
+        &serialized
+    );
+
+    let deserialized: Tuning = serde_json::from_str(&serialized).unwrap();
+
+    println!("{:?}", deserialized);
+    assert_eq!(tuning, deserialized);
+
+    // Tunings saved without a stretch are not stretched.
+    let unstretched: Tuning = serde_json::from_str(
+        "{\"reference_step\":69.0,\"reference_hz\":415.0,\"temperament\":\"Equal\"}"
+    ).unwrap();
+    assert_eq!(unstretched, Tuning::from_a4(415.0));
+
+    let custom = Tuning::default().with_temperament(Temperament::Custom([1.0; 12]));
+    let serialized = serde_json::to_string(&custom).unwrap();
+    assert_eq!(custom, serde_json::from_str(&serialized).unwrap());
+}
+
+#[test]
+fn test_chord() {
+    let chord = Chord::new(Letter::C, ChordQuality::Major7).with_bass(Letter::E);
+    let serialized = serde_json::to_string(&chord).unwrap();
+
+    println!("{}", serialized);
+    assert_eq!("{\"root\":\"C\",\"quality\":\"Major7\",\"bass\":\"E\"}", &serialized);
+
+    let deserialized: Chord = serde_json::from_str(&serialized).unwrap();
+
+    println!("{:?}", deserialized);
+    assert_eq!(chord, deserialized);
+}
+
+#[test]
+fn test_tone_row() {
+    let row = ToneRow::new([7, 10, 2, 6, 9, 0, 4, 8, 11, 1, 3, 5]).unwrap();
+    let serialized = serde_json::to_string(&row).unwrap();
+
+    println!("{}", serialized);
+    assert_eq!("[7,10,2,6,9,0,4,8,11,1,3,5]", &serialized);
+
+    let deserialized: ToneRow = serde_json::from_str(&serialized).unwrap();
+
+    println!("{:?}", deserialized);
+    assert_eq!(row, deserialized);
+
+    assert!(serde_json::from_str::<ToneRow>("[0,0,0,0,0,0,0,0,0,0,0,0]").is_err());
+}
+
+#[test]
+fn test_compact() {
+    use self::compact::{from_tokens, Token};
+
+    // Formats that are not human readable receive the derived representations.
+    let hz: Hz = from_tokens(&[Token::F32(440.0)]).unwrap();
+    assert_eq!(hz, Hz(440.0));
+    let letter_octave: LetterOctave = from_tokens(&[Token::Variant(13), Token::I32(4)]).unwrap();
+    assert_eq!(letter_octave, LetterOctave(Letter::A, 4));
+}
+
+/// A minimal encoding that, like bincode, is not self-describing and so cannot support
+/// `deserialize_any`.
+mod compact {
+    use serde::de::{self, DeserializeSeed, EnumAccess, SeqAccess, VariantAccess, Visitor};
+    use std::fmt;
+
+    #[derive(Copy, Clone, Debug)]
+    pub enum Token {
+        F32(f32),
+        I32(i32),
+        Variant(u32),
+    }
+
+    #[derive(Debug)]
+    pub struct Error(String);
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl ::std::error::Error for Error {}
+
+    impl de::Error for Error {
+        fn custom<T: fmt::Display>(msg: T) -> Error {
+            Error(msg.to_string())
+        }
+    }
+
+    pub fn from_tokens<T: de::DeserializeOwned>(tokens: &[Token]) -> Result<T, Error> {
+        let mut deserializer = Deserializer { tokens };
+        let value = T::deserialize(&mut deserializer)?;
+        if !deserializer.tokens.is_empty() {
+            return Err(Error("trailing tokens".to_string()));
+        }
+        Ok(value)
+    }
+
+    pub struct Deserializer<'a> {
+        tokens: &'a [Token],
+    }
+
+    impl<'a> Deserializer<'a> {
+        fn next(&mut self) -> Result<Token, Error> {
+            let (&token, rest) = self.tokens.split_first()
+                .ok_or_else(|| Error("unexpected end of input".to_string()))?;
+            self.tokens = rest;
+            Ok(token)
+        }
+    }
+
+    fn unexpected(token: Token) -> Error {
+        Error(format!("unexpected token {:?}", token))
+    }
+
+    impl<'de, 'a, 'b> de::Deserializer<'de> for &'b mut Deserializer<'a> {
+        type Error = Error;
+
+        fn is_human_readable(&self) -> bool {
+            false
+        }
+
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Error> {
+            Err(Error("the encoding is not self-describing".to_string()))
+        }
+
+        fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.next()? {
+                Token::F32(value) => visitor.visit_f32(value),
+                token => Err(unexpected(token)),
+            }
+        }
+
+        fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.next()? {
+                Token::I32(value) => visitor.visit_i32(value),
+                token => Err(unexpected(token)),
+            }
+        }
+
+        fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.next()? {
+                Token::Variant(index) => visitor.visit_u32(index),
+                token => Err(unexpected(token)),
+            }
+        }
+
+        fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V)
+            -> Result<V::Value, Error>
+            where V: Visitor<'de>,
+        {
+            visitor.visit_newtype_struct(self)
+        }
+
+        fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V)
+            -> Result<V::Value, Error>
+        {
+            visitor.visit_seq(Elements { deserializer: self, len })
+        }
+
+        fn deserialize_tuple_struct<V>(self, _: &'static str, len: usize, visitor: V)
+            -> Result<V::Value, Error>
+            where V: Visitor<'de>,
+        {
+            self.deserialize_tuple(len, visitor)
+        }
+
+        fn deserialize_enum<V>(self, _: &'static str, _: &'static [&'static str], visitor: V)
+            -> Result<V::Value, Error>
+            where V: Visitor<'de>,
+        {
+            visitor.visit_enum(self)
+        }
+
+        ::serde::forward_to_deserialize_any! {
+            bool i8 i16 i64 i128 u8 u16 u32 u64 u128 f64 char str string bytes byte_buf option
+            unit unit_struct seq map struct ignored_any
+        }
+    }
+
+    struct Elements<'b, 'a: 'b> {
+        deserializer: &'b mut Deserializer<'a>,
+        len: usize,
+    }
+
+    impl<'de, 'a, 'b> SeqAccess<'de> for Elements<'b, 'a> {
+        type Error = Error;
+
+        fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+            where T: DeserializeSeed<'de>,
+        {
+            if self.len == 0 {
+                return Ok(None);
+            }
+            self.len -= 1;
+            seed.deserialize(&mut *self.deserializer).map(Some)
+        }
+    }
+
+    impl<'de, 'a, 'b> EnumAccess<'de> for &'b mut Deserializer<'a> {
+        type Error = Error;
+        type Variant = Self;
+
+        fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+            where V: DeserializeSeed<'de>,
+        {
+            let value = seed.deserialize(&mut *self)?;
+            Ok((value, self))
+        }
+    }
+
+    impl<'de, 'a, 'b> VariantAccess<'de> for &'b mut Deserializer<'a> {
+        type Error = Error;
+
+        fn unit_variant(self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+            where T: DeserializeSeed<'de>,
+        {
+            seed.deserialize(self)
+        }
+
+        fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Error>
+            where V: Visitor<'de>,
+        {
+            de::Deserializer::deserialize_tuple(self, len, visitor)
+        }
+
+        fn struct_variant<V>(self, _: &'static [&'static str], _: V) -> Result<V::Value, Error>
+            where V: Visitor<'de>,
+        {
+            Err(Error("struct variants are not supported".to_string()))
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Pitch-class set theory: the interval content of pitch collections and their equivalence.
+
+use key::pitch_class_profile;
+use super::{
+    calc,
+    Step,
+};
+use utils::modulo;
+
+/// A transformation of pitch classes relating two equivalent pitch-class sets.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum SetTransform {
+    /// `Tn`: transposition by `n` semitones, mapping each pitch class `p` to `p + n`.
+    Transposition(u8),
+    /// `TnI`: inversion about `C` followed by transposition by `n` semitones, mapping each pitch
+    /// class `p` to `n - p`.
+    Inversion(u8),
+}
+
+impl SetTransform {
+
+    /// Apply the transformation to the given pitch class.
+    #[inline]
+    pub fn apply(&self, pitch_class: u8) -> u8 {
+        match *self {
+            SetTransform::Transposition(n) => modulo(pitch_class as i32 + n as i32, 12) as u8,
+            SetTransform::Inversion(n) => modulo(n as i32 - pitch_class as i32, 12) as u8,
+        }
+    }
+
+}
+
+/// The pitch-class set of the pitches as a bit mask with `C` as the lowest bit, rounding each
+/// pitch to the nearest semitone.
+fn pitch_class_set<I, P>(pitches: I) -> u16
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    pitches.into_iter().fold(0, |set, pitch| {
+        set | 1 << modulo(calc::nearest_step(pitch.into().step()) as i32, 12)
+    })
+}
+
+/// The pitch-class set transformed by the given transformation.
+fn transform_set(set: u16, transform: SetTransform) -> u16 {
+    (0..12u8).filter(|&pc| set & 1 << pc != 0).fold(0, |t, pc| t | 1 << transform.apply(pc))
+}
+
+/// The transpositions `Tn` that map the pitch-class set of `a` onto that of `b`, from the
+/// smallest `n` upwards.
+///
+/// There is usually at most one, though symmetrical sets (such as the augmented triad) map onto
+/// themselves under several. Empty sets are related by every transposition.
+pub fn transpositions_between<A, B, P, Q>(a: A, b: B) -> Vec<u8>
+    where A: IntoIterator<Item=P>,
+          B: IntoIterator<Item=Q>,
+          P: Into<Step>,
+          Q: Into<Step>,
+{
+    related_by(pitch_class_set(a), pitch_class_set(b), SetTransform::Transposition)
+}
+
+/// The inversions `TnI` that map the pitch-class set of `a` onto that of `b`, from the smallest
+/// `n` upwards.
+pub fn inversions_between<A, B, P, Q>(a: A, b: B) -> Vec<u8>
+    where A: IntoIterator<Item=P>,
+          B: IntoIterator<Item=Q>,
+          P: Into<Step>,
+          Q: Into<Step>,
+{
+    related_by(pitch_class_set(a), pitch_class_set(b), SetTransform::Inversion)
+}
+
+/// Each `n` for which the transformation maps set `a` onto set `b`.
+fn related_by<F>(a: u16, b: u16, transform: F) -> Vec<u8>
+    where F: Fn(u8) -> SetTransform,
+{
+    (0..12).filter(|&n| transform_set(a, transform(n)) == b).collect()
+}
+
+/// Whether or not the pitch-class sets of `a` and `b` are equivalent under transposition (i.e.
+/// `Tn`-equivalent).
+#[inline]
+pub fn is_tn_equivalent<A, B, P, Q>(a: A, b: B) -> bool
+    where A: IntoIterator<Item=P>,
+          B: IntoIterator<Item=Q>,
+          P: Into<Step>,
+          Q: Into<Step>,
+{
+    !transpositions_between(a, b).is_empty()
+}
+
+/// Whether or not the pitch-class sets of `a` and `b` are equivalent under transposition or
+/// inversion (i.e. `TnI`-equivalent, belonging to the same set class).
+#[inline]
+pub fn is_tni_equivalent<A, B, P, Q>(a: A, b: B) -> bool
+    where A: IntoIterator<Item=P>,
+          B: IntoIterator<Item=Q>,
+          P: Into<Step>,
+          Q: Into<Step>,
+{
+    set_relation(a, b).is_some()
+}
+
+/// The simplest transformation mapping the pitch-class set of `a` onto that of `b`, if they are
+/// `TnI`-equivalent: the smallest transposition if there is one, or otherwise the smallest
+/// inversion.
+pub fn set_relation<A, B, P, Q>(a: A, b: B) -> Option<SetTransform>
+    where A: IntoIterator<Item=P>,
+          B: IntoIterator<Item=Q>,
+          P: Into<Step>,
+          Q: Into<Step>,
+{
+    let (a, b) = (pitch_class_set(a), pitch_class_set(b));
+    let first = |transform: fn(u8) -> SetTransform| {
+        related_by(a, b, transform).first().map(|&n| transform(n))
+    };
+    first(SetTransform::Transposition).or_else(|| first(SetTransform::Inversion))
+}
+
+/// The interval class (from `1` to `6`) between two pitch classes, or `0` if they are equal.
+#[inline]
+fn interval_class(a: usize, b: usize) -> usize {
+    let interval = (b + 12 - a) % 12;
+    interval.min(12 - interval)
+}
+
+/// The interval-class vector of the pitch-class set of the given pitches: the number of pairs
+/// of distinct pitch classes separated by each interval class, from the minor second (or major
+/// seventh) at index `0` to the tritone at index `5`.
+///
+/// Each pitch is rounded to the nearest semitone, and repeated pitch classes count once, so
+/// that a C major triad in any voicing gives `[0, 0, 1, 1, 1, 0]`.
+pub fn interval_class_vector<I, P>(pitches: I) -> [u32; 6]
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let profile = pitch_class_profile(pitches.into_iter().map(|p| (p, 1.0)));
+    let mut vector = [0; 6];
+    for a in 0..12 {
+        for b in (a + 1)..12 {
+            if profile[a] > 0.0 && profile[b] > 0.0 {
+                vector[interval_class(a, b) - 1] += 1;
+            }
+        }
+    }
+    vector
+}
+
+/// The interval-class content of a weighted collection of pitches (e.g. the notes of a
+/// passage weighted by their durations), indexed as for `interval_class_vector`.
+///
+/// The weights of each pitch class are first summed, and each pair of distinct pitch classes
+/// then contributes the product of their weights to its interval class. Given a weight of `1.0`
+/// for every pitch of a set this is the interval-class vector.
+pub fn interval_class_content<I, P>(pitches: I) -> [calc::Weight; 6]
+    where I: IntoIterator<Item=(P, calc::Weight)>,
+          P: Into<Step>,
+{
+    let profile = pitch_class_profile(pitches);
+    let mut content = [0.0; 6];
+    for a in 0..12 {
+        for b in (a + 1)..12 {
+            content[interval_class(a, b) - 1] += profile[a] * profile[b];
+        }
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(steps: &[calc::Step]) -> Vec<Step> {
+        steps.iter().cloned().map(Step).collect()
+    }
+
+    #[test]
+    fn interval_class_vectors() {
+        assert_eq!(interval_class_vector(steps(&[60.0, 64.0, 67.0])), [0, 0, 1, 1, 1, 0]);
+        assert_eq!(interval_class_vector(steps(&[52.0, 60.0, 67.0, 72.0])), [0, 0, 1, 1, 1, 0]);
+        let diatonic = [60.0, 62.0, 64.0, 65.0, 67.0, 69.0, 71.0];
+        assert_eq!(interval_class_vector(steps(&diatonic)), [2, 5, 4, 3, 6, 1]);
+        let chromatic: Vec<calc::Step> = (0..12).map(|s| s as calc::Step).collect();
+        assert_eq!(interval_class_vector(steps(&chromatic)), [12, 12, 12, 12, 12, 6]);
+        assert_eq!(interval_class_vector(Vec::<Step>::new()), [0; 6]);
+    }
+
+    #[test]
+    fn weighted_content() {
+        // A held C against a short G and a repeated E.
+        let notes = vec![
+            (Step(60.0), 2.0),
+            (Step(67.0), 0.5),
+            (Step(64.0), 1.0),
+            (Step(76.0), 1.0),
+        ];
+        assert_eq!(interval_class_content(notes), [0.0, 0.0, 1.0, 4.0, 1.0, 0.0]);
+        let set = steps(&[60.0, 62.0, 66.0]).into_iter().map(|s| (s, 1.0));
+        assert_eq!(interval_class_content(set), [0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn set_equivalence() {
+        let c_major = steps(&[60.0, 64.0, 67.0]);
+        let e_major = steps(&[64.0, 68.0, 71.0]);
+        let a_minor = steps(&[57.0, 60.0, 64.0]);
+        assert_eq!(transpositions_between(c_major.clone(), e_major.clone()), vec![4]);
+        assert!(is_tn_equivalent(c_major.clone(), e_major.clone()));
+        assert!(!is_tn_equivalent(c_major.clone(), a_minor.clone()));
+        assert!(is_tni_equivalent(c_major.clone(), a_minor.clone()));
+        assert_eq!(set_relation(c_major.clone(), e_major.clone()),
+                   Some(SetTransform::Transposition(4)));
+        // C E G inverts to E C A under T4I.
+        assert_eq!(set_relation(c_major.clone(), a_minor.clone()),
+                   Some(SetTransform::Inversion(4)));
+        assert_eq!(inversions_between(c_major.clone(), a_minor), vec![4]);
+        let augmented = steps(&[60.0, 64.0, 68.0]);
+        assert_eq!(transpositions_between(augmented.clone(), augmented), vec![0, 4, 8]);
+        assert_eq!(set_relation(c_major, steps(&[60.0, 62.0, 64.0])), None);
+        assert_eq!(SetTransform::Inversion(4).apply(7), 9);
+    }
+}
@@ -0,0 +1,171 @@
+//! Filters for de-jittering sequences of pitches, such as the output of a pitch tracker, before
+//! display or resynthesis.
+//!
+//! As with `stats`, all filtering happens in step (log-frequency) space so that a glide of a given
+//! interval is smoothed the same way in every register.
+
+use stats::median;
+use super::{
+    calc,
+    Step,
+    WeightedPitch,
+};
+
+/// A duration or point in time in seconds.
+pub type Seconds = f64;
+
+/// A one-pole low-pass filter (an exponential moving average) over a sequence of pitches.
+///
+/// Each update moves the smoothed pitch towards the latest pitch by a fixed fraction, determined
+/// by the time constant (the time taken to cover ~63% of a jump) and the rate at which pitches
+/// are given.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PitchSmoother {
+    /// The fraction of the distance to each new pitch covered by an update.
+    coefficient: f64,
+    /// The current smoothed pitch in steps, or `None` before the first update.
+    state: Option<f64>,
+}
+
+impl PitchSmoother {
+
+    /// A smoother with the given time constant for pitches given `update_rate` times per second
+    /// (e.g. the hop rate of a pitch tracker).
+    ///
+    /// A time constant of zero (or less) disables smoothing.
+    pub fn new(time_constant: Seconds, update_rate: f64) -> PitchSmoother {
+        let coefficient = if time_constant > 0.0 {
+            1.0 - (-1.0 / (time_constant * update_rate)).exp()
+        } else {
+            1.0
+        };
+        PitchSmoother { coefficient, state: None }
+    }
+
+    /// Add the next pitch, returning the smoothed pitch.
+    ///
+    /// The first pitch is passed through unchanged. Pitches that are not a number (e.g. the
+    /// `NaN` often used to mark unvoiced frames) are ignored, returning the current smoothed
+    /// pitch.
+    pub fn next<P: Into<Step>>(&mut self, pitch: P) -> Step {
+        let step = pitch.into().step() as f64;
+        let state = match self.state {
+            _ if step.is_nan() => self.state.unwrap_or(step),
+            None => step,
+            Some(state) => state + (step - state) * self.coefficient,
+        };
+        if !state.is_nan() {
+            self.state = Some(state);
+        }
+        Step(state as calc::Step)
+    }
+
+    /// Add the next pitch estimate, scaling its influence by its confidence so that uncertain
+    /// estimates move the smoothed pitch less. An estimate of zero confidence leaves the smoothed
+    /// pitch unchanged, unless it is the first.
+    pub fn next_weighted<P: Into<Step>>(&mut self, estimate: WeightedPitch<P>) -> Step {
+        let coefficient = self.coefficient;
+        self.coefficient *= estimate.confidence.clamp(0.0, 1.0) as f64;
+        let step = self.next(estimate.pitch);
+        self.coefficient = coefficient;
+        step
+    }
+
+    /// The current smoothed pitch, or `None` if no pitch has been given since the smoother was
+    /// created or reset.
+    #[inline]
+    pub fn value(&self) -> Option<Step> {
+        self.state.map(|state| Step(state as calc::Step))
+    }
+
+    /// Forget the smoothed pitch so that the next pitch is passed through unchanged, e.g. at the
+    /// start of a new note.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.state = None;
+    }
+
+}
+
+/// A windowed median filter over a sequence of pitches, as is commonly used to clean up the
+/// fundamental frequency estimates of a pitch tracker.
+///
+/// Each pitch is replaced by the median of the `window` pitches centred upon it. Towards the ends
+/// of the sequence the window shrinks so that it remains centred. Before the median is taken,
+/// every pitch in the window is moved by whole octaves to lie within a tritone of the window's
+/// median, so that spurious octave jumps (the most common tracking error) are corrected rather
+/// than merely outvoted.
+///
+/// Pitches that are not a number (e.g. unvoiced frames) are passed through and ignored within
+/// the windows of their neighbours. A `window` of `0` or `1` returns the pitches unchanged.
+pub fn median_filter<I, P>(pitches: I, window: usize) -> Vec<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let steps: Vec<calc::Step> = pitches.into_iter().map(|p| p.into().step()).collect();
+    let radius = window / 2;
+    (0..steps.len())
+        .map(|i| {
+            if steps[i].is_nan() || window < 2 {
+                return Step(steps[i]);
+            }
+            let radius = radius.min(i).min(steps.len() - 1 - i);
+            let (start, end) = (i - radius, i + radius + 1);
+            let voiced = steps[start..end].iter().cloned().filter(|step| !step.is_nan());
+            let voiced: Vec<calc::Step> = voiced.collect();
+            let rough = median(voiced.iter().map(|&step| Step(step))).unwrap().step();
+            let folded = voiced.iter().map(|&step| {
+                Step(step - ((step - rough) / 12.0).round() * 12.0)
+            });
+            median(folded).unwrap()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Hz;
+
+    #[test]
+    fn smoothing() {
+        // A 10ms time constant at 1khz covers ~63% of a jump after 10 updates.
+        let mut smoother = PitchSmoother::new(0.01, 1_000.0);
+        assert_eq!(smoother.value(), None);
+        assert_eq!(smoother.next(Step(60.0)), Step(60.0));
+        let mut step = Step(60.0);
+        for _ in 0..10 {
+            step = smoother.next(Step(72.0));
+        }
+        assert!((step.step() - (60.0 + 12.0 * 0.632)).abs() < 0.01);
+        // Unvoiced frames are skipped.
+        assert_eq!(smoother.next(Step(calc::Step::NAN)), step);
+        assert!(smoother.next(Hz(440.0)).step() > step.step());
+        smoother.reset();
+        assert_eq!(smoother.next(Step(50.0)), Step(50.0));
+        let mut bypass = PitchSmoother::new(0.0, 100.0);
+        bypass.next(Step(60.0));
+        assert_eq!(bypass.next(Step(61.5)), Step(61.5));
+        assert_eq!(bypass.next_weighted(WeightedPitch::new(Step(70.0), 0.0)), Step(61.5));
+        assert_eq!(bypass.next_weighted(WeightedPitch::new(Step(71.5), 0.5)), Step(66.5));
+    }
+
+    #[test]
+    fn median_filtering() {
+        let nan = calc::Step::NAN;
+        let track = [60.0, 60.1, 72.2, 60.2, 48.1, 59.9, 60.0, nan, 60.1];
+        let filtered = median_filter(track.iter().map(|&s| Step(s)), 3);
+        assert_eq!(filtered.len(), track.len());
+        // Both octave errors are folded back near their neighbours.
+        assert!(filtered.iter().filter(|s| !s.step().is_nan())
+            .all(|s| (s.step() - 60.0).abs() <= 0.2));
+        assert!((filtered[2].step() - 60.2).abs() < 0.001);
+        assert!(filtered[7].step().is_nan());
+        // Consecutive octave errors may outnumber the correct pitch within a window, but are
+        // still corrected once folded.
+        let track = [60.0, 60.1, 72.0, 72.1, 60.2, 60.1, 60.0];
+        let filtered = median_filter(track.iter().map(|&s| Step(s)), 5);
+        assert!(filtered.iter().all(|s| (s.step() - 60.1).abs() <= 0.11), "{:?}", filtered);
+        assert_eq!(median_filter(vec![Step(1.0), Step(20.0)], 1), vec![Step(1.0), Step(20.0)]);
+    }
+}
@@ -0,0 +1,107 @@
+//! Placement of spelled pitches on the five line staff, for rendering notation.
+
+use super::{
+    Letter,
+    LetterOctave,
+};
+
+/// The number of lines on a staff.
+pub const STAFF_LINES: i32 = 5;
+
+/// The clefs used to fix the pitches of the staff lines.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Clef {
+    /// The G clef on the second line, whose bottom line is `E4`.
+    Treble,
+    /// The F clef on the fourth line, whose bottom line is `G2`.
+    Bass,
+    /// The C clef on the third line, whose bottom line is `F3`.
+    Alto,
+    /// The C clef on the fourth line, whose bottom line is `D3`.
+    Tenor,
+}
+
+/// The number of natural letters from `C-1` up to the natural of the given pitch (e.g. `C4` is
+/// `35`). The spelling of the pitch decides its position, so `Csh 4` lies one below `Db 4`.
+#[inline]
+pub fn diatonic_number(letter_octave: LetterOctave) -> i32 {
+    let LetterOctave(letter, octave) = letter_octave;
+    (octave + 1) * 7 + letter.diatonic_index() as i32
+}
+
+impl Clef {
+
+    /// The natural pitch lying on the bottom line of the staff.
+    pub fn bottom_line(&self) -> LetterOctave {
+        match *self {
+            Clef::Treble => LetterOctave(Letter::E, 4),
+            Clef::Bass => LetterOctave(Letter::G, 2),
+            Clef::Alto => LetterOctave(Letter::F, 3),
+            Clef::Tenor => LetterOctave(Letter::D, 3),
+        }
+    }
+
+    /// The natural pitch lying on the top line of the staff.
+    pub fn top_line(&self) -> LetterOctave {
+        let LetterOctave(letter, octave) = self.bottom_line();
+        // Eight naturals above the bottom line, i.e. an octave and a third.
+        let index = letter.diatonic_index() as i32 + 8;
+        let natural = [Letter::C, Letter::D, Letter::E, Letter::F, Letter::G, Letter::A, Letter::B];
+        LetterOctave(natural[(index % 7) as usize], octave + index / 7)
+    }
+
+    /// The position of the pitch on the staff, counted in lines and spaces from the bottom line.
+    ///
+    /// Even positions lie on lines and odd positions in spaces, so `0` is the bottom line, `1`
+    /// the space above it and `8` the top line. Positions below `0` or above `8` require ledger
+    /// lines.
+    #[inline]
+    pub fn position(&self, letter_octave: LetterOctave) -> i32 {
+        diatonic_number(letter_octave) - diatonic_number(self.bottom_line())
+    }
+
+    /// The number of ledger lines needed to place the pitch above or below the staff.
+    pub fn ledger_lines(&self, letter_octave: LetterOctave) -> u32 {
+        let top = (STAFF_LINES - 1) * 2;
+        let position = self.position(letter_octave);
+        if position < 0 {
+            (-position / 2) as u32
+        } else if position > top {
+            ((position - top) / 2) as u32
+        } else {
+            0
+        }
+    }
+
+}
+
+/// Whether or not the given staff position lies on a line rather than in a space.
+#[inline]
+pub fn is_line(position: i32) -> bool {
+    position.rem_euclid(2) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staff_positions() {
+        let middle_c = LetterOctave(Letter::C, 4);
+        assert_eq!(Clef::Treble.position(middle_c), -2);
+        assert_eq!(Clef::Treble.ledger_lines(middle_c), 1);
+        assert_eq!(Clef::Bass.position(middle_c), 10);
+        assert_eq!(Clef::Bass.ledger_lines(middle_c), 1);
+        assert_eq!(Clef::Alto.position(middle_c), 4);
+        assert_eq!(Clef::Tenor.position(middle_c), 6);
+        assert_eq!(Clef::Treble.position(LetterOctave(Letter::Csh, 5)), 5);
+        assert_eq!(Clef::Treble.position(LetterOctave(Letter::Db, 5)), 6);
+        assert_eq!(Clef::Treble.top_line().to_string(), "F5");
+        assert_eq!(Clef::Bass.top_line().to_string(), "A3");
+        assert!(is_line(-2) && !is_line(-1));
+        assert_eq!(Clef::Treble.ledger_lines(LetterOctave(Letter::B, 3)), 1);
+        assert_eq!(Clef::Treble.ledger_lines(LetterOctave(Letter::A, 5)), 1);
+        assert_eq!(Clef::Treble.ledger_lines(LetterOctave(Letter::G, 5)), 0);
+    }
+}
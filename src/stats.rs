@@ -0,0 +1,146 @@
+//! Statistics over collections of pitches.
+//!
+//! Pitch perception is logarithmic in frequency, so averaging raw frequencies skews results
+//! towards the higher pitches. All functions in this module operate in step (log-frequency)
+//! space instead.
+//!
+//! `NaN` pitches (as often used to mark unvoiced frames, see `smoothing::median_filter`) are
+//! ignored by every function, which returns `None` when no other pitches remain.
+
+use super::{
+    calc,
+    Hz,
+    Step,
+};
+
+/// Collect the pitches as steps, ignoring any that are `NaN`.
+fn steps<I, P>(pitches: I) -> Vec<calc::Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    pitches.into_iter().map(|p| p.into().step()).filter(|step| !step.is_nan()).collect()
+}
+
+/// The arithmetic mean of the given pitches in step space.
+///
+/// Returns `None` if there are no pitches.
+pub fn mean<I, P>(pitches: I) -> Option<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let steps = steps(pitches);
+    if steps.is_empty() {
+        return None;
+    }
+    let sum = steps.iter().fold(0.0f64, |sum, &s| sum + s as f64);
+    Some(Step((sum / steps.len() as f64) as calc::Step))
+}
+
+/// The median of the given pitches in step space. For an even number of pitches this is the
+/// mean of the two middle pitches.
+///
+/// Returns `None` if there are no pitches.
+pub fn median<I, P>(pitches: I) -> Option<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let mut steps = steps(pitches);
+    if steps.is_empty() {
+        return None;
+    }
+    steps.sort_by(|a, b| a.total_cmp(b));
+    let mid = steps.len() / 2;
+    let median = if steps.len() % 2 == 0 {
+        (steps[mid - 1] + steps[mid]) / 2.0
+    } else {
+        steps[mid]
+    };
+    Some(Step(median))
+}
+
+/// The population standard deviation of the given pitches in semitones.
+///
+/// Returns `None` if there are no pitches.
+pub fn std_dev<I, P>(pitches: I) -> Option<calc::Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let steps = steps(pitches);
+    if steps.is_empty() {
+        return None;
+    }
+    let n = steps.len() as f64;
+    let mean = steps.iter().fold(0.0f64, |sum, &s| sum + s as f64) / n;
+    let variance = steps.iter().fold(0.0f64, |sum, &s| sum + (s as f64 - mean).powi(2)) / n;
+    Some(variance.sqrt() as calc::Step)
+}
+
+/// The geometric mean of the given frequencies.
+///
+/// This is the frequency equivalent of taking the `mean` in step space. Returns `None` if there
+/// are no frequencies.
+pub fn geometric_mean_hz<I>(frequencies: I) -> Option<Hz>
+    where I: IntoIterator<Item=Hz>,
+{
+    let (count, log_sum) = frequencies.into_iter()
+        .filter(|hz| !hz.hz().is_nan())
+        .fold((0usize, 0.0f64), |(count, sum), hz| (count + 1, sum + (hz.hz() as f64).ln()));
+    if count == 0 {
+        return None;
+    }
+    Some(Hz((log_sum / count as f64).exp() as calc::Hz))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let none: Vec<Step> = vec![];
+        assert_eq!(mean(none.clone()), None);
+        assert_eq!(median(none.clone()), None);
+        assert_eq!(std_dev(none), None);
+        assert_eq!(geometric_mean_hz(vec![]), None);
+        assert_eq!(median(vec![Step(calc::Step::NAN)]), None);
+    }
+
+    #[test]
+    fn medians() {
+        assert_eq!(median(vec![Step(64.0), Step(60.0), Step(67.0)]), Some(Step(64.0)));
+        assert_eq!(median(vec![Step(72.0), Step(60.0), Step(64.0), Step(67.0)]), Some(Step(65.5)));
+        // Unvoiced frames are ignored.
+        let nan = Step(calc::Step::NAN);
+        assert_eq!(median(vec![nan, Step(60.0), nan, Step(62.0), Step(61.0)]), Some(Step(61.0)));
+    }
+
+    #[test]
+    fn log_space_mean() {
+        // The mean of an octave in step space is the geometric mean of the frequencies.
+        let (low, high) = (Hz(220.0), Hz(880.0));
+        let geometric = geometric_mean_hz(vec![low, high]).unwrap();
+        assert!((geometric.hz() - 440.0).abs() < 0.01);
+        let step = mean(vec![low, high]).unwrap();
+        assert!((step.to_hz().hz() - geometric.hz()).abs() < 0.01);
+        assert_eq!(mean(vec![Step(60.0), Step(64.0), Step(71.0)]), Some(Step(65.0)));
+    }
+
+    #[test]
+    fn deviation() {
+        assert_eq!(std_dev(vec![Step(60.0); 4]), Some(0.0));
+        assert_eq!(std_dev(vec![Step(58.0), Step(62.0)]), Some(2.0));
+    }
+
+    #[test]
+    fn nan_pitches() {
+        // Every function ignores unvoiced frames alike.
+        let nan = Step(calc::Step::NAN);
+        assert_eq!(mean(vec![nan, Step(60.0), Step(64.0)]), Some(Step(62.0)));
+        assert_eq!(std_dev(vec![Step(58.0), nan, Step(62.0)]), Some(2.0));
+        let hz = geometric_mean_hz(vec![Hz(220.0), Hz(calc::Hz::NAN), Hz(880.0)]).unwrap();
+        assert!((hz.hz() - 440.0).abs() < 0.01);
+        assert_eq!(mean(vec![nan, nan]), None);
+        assert_eq!(std_dev(vec![nan]), None);
+        assert_eq!(geometric_mean_hz(vec![Hz(calc::Hz::NAN)]), None);
+    }
+}
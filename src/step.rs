@@ -1,19 +1,31 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
 use super::{
     calc,
     DEFAULT_SCALE_WEIGHT,
+    Error,
     Hz,
     LetterOctave,
     Letter,
+    LogPerc,
     Mel,
     Octave,
+    OctaveFraction,
+    ParseError,
     Perc,
+    PitchRange,
+    QuarterTone,
     ScaledPerc,
     ScaleWeight,
+    Spelling,
     hz_from_step,
     letter_octave_from_step,
+    log_perc_from_step,
     mel_from_step,
+    octave_fraction_from_step,
     perc_from_step,
     scaled_perc_from_step,
 };
@@ -69,6 +81,12 @@ impl Step {
         LetterOctave(letter, octave)
     }
 
+    /// Convert to the closest LetterOctave, spelling any black key with the given preference.
+    #[inline]
+    pub fn to_letter_octave_with_spelling(&self, spelling: Spelling) -> LetterOctave {
+        self.to_letter_octave().spelled(spelling)
+    }
+
     /// Convert to a Mel unit value.
     #[inline]
     pub fn mel(&self) -> calc::Mel {
@@ -93,6 +111,19 @@ impl Step {
         Perc(self.perc())
     }
 
+    /// Convert to the unit value of a percentage of the human hearing range in log-frequency
+    /// space.
+    #[inline]
+    pub fn log_perc(&self) -> calc::Perc {
+        log_perc_from_step(self.step())
+    }
+
+    /// Convert to a percentage of the human hearing range in log-frequency space.
+    #[inline]
+    pub fn to_log_perc(&self) -> LogPerc {
+        LogPerc(self.log_perc())
+    }
+
     /// Convert to a scaled percentage of the human hearing range with a given weight.
     #[inline]
     pub fn scaled_perc_with_weight(&self, weight: ScaleWeight) -> calc::Perc {
@@ -117,6 +148,132 @@ impl Step {
         self.to_scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
     }
 
+    /// Convert to a fractional octave number.
+    #[inline]
+    pub fn octave_fraction(&self) -> calc::OctaveFraction {
+        octave_fraction_from_step(self.step())
+    }
+
+    /// Convert to an OctaveFraction struct.
+    #[inline]
+    pub fn to_octave_fraction(&self) -> OctaveFraction {
+        OctaveFraction(self.octave_fraction())
+    }
+
+    /// The pitch class of the step within the range `[0, 12)`, where `0` is `C`. Negative steps
+    /// wrap around rather than producing negative pitch classes (e.g. `-1.0` is `11.0`).
+    #[inline]
+    pub fn pitch_class(&self) -> calc::Step {
+        self.step().rem_euclid(12.0)
+    }
+
+    /// Add the given number of semitones, wrapping around within the octave starting at the `C`
+    /// at or below this step (e.g. `B 4` plus one semitone is `C 4`).
+    #[inline]
+    pub fn wrapping_add_semitones(&self, semitones: calc::Step) -> Step {
+        self.wrapping_add_steps(semitones, 12.0)
+    }
+
+    /// Subtract the given number of semitones, wrapping around within the octave starting at the
+    /// `C` at or below this step (e.g. `C 4` minus one semitone is `B 4`).
+    #[inline]
+    pub fn wrapping_sub_semitones(&self, semitones: calc::Step) -> Step {
+        self.wrapping_add_steps(-semitones, 12.0)
+    }
+
+    /// The same pitch class moved by whole octaves into the given octave, i.e. between `C` of
+    /// that octave (inclusive) and the following `C` (exclusive).
+    #[inline]
+    pub fn fold_into_octave(&self, octave: Octave) -> Step {
+        Step((octave + 1) as calc::Step * 12.0 + self.pitch_class())
+    }
+
+    /// The pitch moved by as few whole octaves as possible so that it lies within the given range,
+    /// or `None` if the range is narrower than an octave and no octave of the pitch lies within it.
+    pub fn fold_into_range(&self, range: PitchRange) -> Option<Step> {
+        let (step, lowest, highest) = (self.step(), range.lowest().step(), range.highest().step());
+        let folded = if step < lowest {
+            step + ((lowest - step) / 12.0).ceil() * 12.0
+        } else if step > highest {
+            step - ((step - highest) / 12.0).ceil() * 12.0
+        } else {
+            step
+        };
+        if range.contains(Step(folded)) { Some(Step(folded)) } else { None }
+    }
+
+    /// The pitch reflected about the given axis, so that it lies the same interval below the
+    /// axis as it originally lay above it (e.g. `E 4` inverted around `D 4` is `C 4`).
+    #[inline]
+    pub fn invert_around<A: Into<Step>>(&self, axis: A) -> Step {
+        Step(2.0 * axis.into().step() - self.step())
+    }
+
+    /// Add the given number of steps, wrapping around within the period of `period` steps
+    /// containing this step, where periods start from step `0` (e.g. a period of `19.0` for a
+    /// 19-EDO sequencer).
+    pub fn wrapping_add_steps(&self, steps: calc::Step, period: calc::Step) -> Step {
+        let start = (self.step() / period).floor() * period;
+        Step(start + (self.step() - start + steps).rem_euclid(period))
+    }
+
+    /// The lower of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn min(self, other: Step) -> Step {
+        Step(self.step().min(other.step()))
+    }
+
+    /// The higher of the two pitches, ignoring a `NaN` pitch (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn max(self, other: Step) -> Step {
+        Step(self.step().max(other.step()))
+    }
+
+    /// The pitch limited to the range `min` to `max`. Never panics, even on a `NaN` pitch or
+    /// bound (see [comparison](crate#comparison)).
+    #[inline]
+    pub fn clamp(self, min: Step, max: Step) -> Step {
+        self.max(min).min(max)
+    }
+
+}
+
+impl FromStr for Step {
+    type Err = Error;
+    /// Parse either a plain step number (e.g. `60` or `60.5`) or a pitch name optionally followed
+    /// by an offset in cents (e.g. `C4`, `A4+12c`, `Bb3 -30c` or the quarter tone `C+4`).
+    fn from_str(s: &str) -> Result<Step, Error> {
+        let s = s.trim();
+        if let Ok(step) = s.parse::<calc::Step>() {
+            return Ok(Step(step));
+        }
+        let offset = s.strip_suffix('c').and_then(|s| s.rfind(['+', '-']).map(|i| s.split_at(i)));
+        let (name, cents) = match offset {
+            Some((name, cents)) if !name.trim().is_empty() => {
+                let cents = cents.replace(' ', "").parse::<calc::Cents>()
+                    .map_err(|_| ParseError::InvalidNumber)?;
+                (name.trim(), cents)
+            },
+            _ => (s, 0.0),
+        };
+        let step = match name.parse::<LetterOctave>() {
+            Ok(letter_octave) => letter_octave.step(),
+            Err(err) => name.parse::<QuarterTone>().map_err(|_| err)?.step(),
+        };
+        Ok(Step(step + cents / 100.0))
+    }
+}
+
+impl fmt::Display for Step {
+    /// Formats the value in steps, appending the unit with the alternate flag (e.g. `69 st`). Any
+    /// precision or width is applied to the number.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&(self.step()), f)?;
+        if f.alternate() {
+            f.write_str(" st")?;
+        }
+        Ok(())
+    }
 }
 
 impl Add for Step {
@@ -170,23 +327,125 @@ impl Neg for Step {
 impl PartialEq for Step {
     #[inline]
     fn eq(&self, other: &Step) -> bool {
-        self.step() == other.step()
+        self.cmp(other) == Ordering::Equal
     }
 }
 
 impl Eq for Step {}
 
+impl Hash for Step {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.step().to_bits().hash(state);
+    }
+}
+
 impl PartialOrd for Step {
     #[inline]
     fn partial_cmp(&self, other: &Step) -> Option<Ordering> {
-        self.step().partial_cmp(&other.step())
+        Some(self.cmp(other))
     }
 }
 
+/// Ordered by the [total order](crate#comparison) shared by the float pitch types.
 impl Ord for Step {
     #[inline]
     fn cmp(&self, other: &Step) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.step().total_cmp(&other.step())
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_round_trips() {
+        // Every whole step, including those beyond the MIDI and audible ranges, survives a round
+        // trip through each representation to within a cent, and so rounds back to itself.
+        for i in -24..160 {
+            let step = Step(i as calc::Step);
+            for &round_trip in &[step.to_hz().to_step(), step.to_mel().to_step(),
+                                 step.to_perc().to_step()] {
+                assert!((round_trip.step() - step.step()).abs() < 0.01, "{:?}", round_trip);
+                assert_eq!(calc::nearest_step(round_trip.step()), step.step());
+            }
+        }
+        assert_eq!(Step(69.0).to_hz(), Hz(440.0));
+        assert_eq!(Hz(440.0).to_step(), Step(69.0));
+        assert_eq!(Step(57.0).to_hz().to_step(), Step(57.0));
+    }
+
+    #[test]
+    fn parse_numbers_and_names() {
+        assert_eq!("60".parse::<Step>(), Ok(Step(60.0)));
+        assert_eq!(" 60.5 ".parse::<Step>(), Ok(Step(60.5)));
+        assert_eq!("C4".parse::<Step>(), Ok(Step(60.0)));
+        assert_eq!("A4+12c".parse::<Step>(), Ok(Step(69.12)));
+        assert_eq!("Bb3 -50c".parse::<Step>(), Ok(Step(57.5)));
+        assert_eq!("C#-1".parse::<Step>(), Ok(Step(1.0)));
+        assert_eq!("C+4".parse::<Step>(), Ok(Step(60.5)));
+        assert_eq!("A4+xc".parse::<Step>(), Err(ParseError::InvalidNumber.into()));
+        assert_eq!("H4".parse::<Step>(), Err(ParseError::InvalidLetter.into()));
+    }
+
+    #[test]
+    fn wrapping_arithmetic() {
+        assert_eq!(Step(-1.0).pitch_class(), 11.0);
+        assert_eq!(Step(61.5).pitch_class(), 1.5);
+        assert_eq!(Step(71.0).wrapping_add_semitones(1.0), Step(60.0));
+        assert_eq!(Step(60.0).wrapping_sub_semitones(1.0), Step(71.0));
+        assert_eq!(Step(-3.0).wrapping_add_semitones(5.0), Step(-10.0));
+        assert_eq!(Step(64.0).wrapping_add_semitones(-30.0), Step(70.0));
+        // A cycle of fifths returns to where it started.
+        let cycle = (0..12).fold(Step(60.0), |step, _| step.wrapping_add_semitones(7.0));
+        assert_eq!(cycle, Step(60.0));
+        assert_eq!(Step(20.0).wrapping_add_steps(5.0, 19.0), Step(25.0));
+        assert_eq!(Step(37.0).wrapping_add_steps(2.0, 19.0), Step(20.0));
+    }
+
+    #[test]
+    fn octave_folding() {
+        assert_eq!(Step(64.0).fold_into_octave(2), Step(40.0));
+        assert_eq!(Step(23.5).fold_into_octave(4), Step(71.5));
+        assert_eq!(Step(-1.0).fold_into_octave(-1), Step(11.0));
+        let range = PitchRange(Step(55.0), Step(67.0));
+        assert_eq!(Step(40.0).fold_into_range(range), Some(Step(64.0)));
+        assert_eq!(Step(91.0).fold_into_range(range), Some(Step(67.0)));
+        assert_eq!(Step(60.0).fold_into_range(range), Some(Step(60.0)));
+        assert_eq!(Step(50.0).fold_into_range(PitchRange(Step(60.0), Step(61.0))), None);
+    }
+
+    #[test]
+    fn min_max_clamp() {
+        let nan = Step(calc::Step::NAN);
+        assert_eq!(Step(60.0).min(Step(64.0)), Step(60.0));
+        assert_eq!(Step(60.0).max(nan), Step(60.0));
+        assert_eq!(nan.min(Step(64.0)), Step(64.0));
+        assert_eq!(Step(70.0).clamp(Step(60.0), Step(67.0)), Step(67.0));
+        assert_eq!(nan.clamp(Step(60.0), Step(67.0)), Step(60.0));
+        assert_eq!(Step(62.0).clamp(nan, Step(61.0)), Step(61.0));
+    }
+
+    #[test]
+    fn total_order() {
+        use std::collections::HashSet;
+        let mut steps = vec![Step(calc::Step::NAN), Step(64.0), Step(60.0), Step(64.0)];
+        steps.sort();
+        assert_eq!(steps[..3], [Step(60.0), Step(64.0), Step(64.0)]);
+        assert!(steps[3].step().is_nan());
+        assert_eq!(Step(calc::Step::NAN), Step(calc::Step::NAN));
+        assert_ne!(Step(0.0), Step(-0.0));
+        let unique: HashSet<Step> = steps.into_iter().collect();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Step(69.0)), "69");
+        assert_eq!(format!("{:#}", Step(69.0)), "69 st");
+        assert_eq!(format!("{:#.1}", Step(69.0)), "69.0 st");
+        assert_eq!(format!("{:<#4}", Step(69.0)), "69   st");
+    }
+}
@@ -0,0 +1,150 @@
+//! Temperaments describing how each of the twelve pitch classes deviates from equal temperament.
+
+use num::ToPrimitive;
+use ratio::SYNTONIC_COMMA_CENTS;
+use super::{
+    calc,
+    Letter,
+    Ratio,
+    Step,
+    nearest_step,
+};
+
+/// Werckmeister III (1691) in cents from equal temperament, from `C` to `B`.
+pub const WERCKMEISTER_III: [calc::Cents; 12] =
+    [0.0, -9.775, -7.82, -5.865, -9.775, -1.955, -11.73, -3.91, -7.82, -11.73, -3.91, -7.82];
+/// Kirnberger III (1779) in cents from equal temperament, from `C` to `B`.
+pub const KIRNBERGER_III: [calc::Cents; 12] =
+    [0.0, -9.775, -6.843, -5.865, -13.686, -1.955, -9.776, -3.422, -7.82, -10.265, -3.91, -11.731];
+/// Thomas Young's second temperament (1799) in cents from equal temperament, from `C` to `B`.
+pub const YOUNG_II: [calc::Cents; 12] =
+    [0.0, -9.775, -3.91, -5.865, -7.82, -1.955, -11.73, -1.955, -7.82, -5.865, -3.91, -9.775];
+/// Vallotti's temperament (c. 1754) in cents from equal temperament, from `C` to `B`.
+pub const VALLOTTI: [calc::Cents; 12] =
+    [0.0, -5.865, -3.91, -1.955, -7.82, 1.955, -7.82, -1.955, -3.91, -5.865, 0.0, -9.775];
+
+/// Quarter-comma meantone, in which the major thirds are pure.
+pub const QUARTER_COMMA_MEANTONE: Temperament = Temperament::Meantone(0.25);
+/// Sixth-comma meantone, in which the tritones are very nearly pure.
+pub const SIXTH_COMMA_MEANTONE: Temperament = Temperament::Meantone(1.0 / 6.0);
+/// Pythagorean tuning with the wolf fifth in its usual position, from `G♯` to `E♭`.
+pub const PYTHAGOREAN: Temperament = Temperament::Pythagorean(Letter::Gsh);
+
+/// The pitch class of `E♭`, from which the usual meantone chain of fifths begins.
+const E_FLAT: usize = 3;
+
+/// A temperament of the twelve pitch classes, described by the deviation of each from equal
+/// temperament in cents.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum Temperament {
+    /// Twelve tone equal temperament, in which no pitch class deviates.
+    #[default]
+    Equal,
+    /// Werckmeister III, see `WERCKMEISTER_III`.
+    WerckmeisterIII,
+    /// Kirnberger III, see `KIRNBERGER_III`.
+    KirnbergerIII,
+    /// Young's second temperament, see `YOUNG_II`.
+    YoungII,
+    /// Vallotti's temperament, see `VALLOTTI`.
+    Vallotti,
+    /// Meantone temperament, in which every fifth from `E♭` to `G♯` is narrowed by the given
+    /// fraction of the syntonic comma (e.g. `0.25` for quarter-comma meantone). The remaining
+    /// "wolf" fifth from `G♯` to `E♭` absorbs the difference.
+    Meantone(f32),
+    /// Pythagorean (3-limit) tuning, built from pure `3/2` fifths. The given letter is the lower
+    /// note of the wolf fifth, which is narrower than pure by the Pythagorean comma (e.g.
+    /// `Letter::Gsh` for the wolf from `G♯` to `E♭`).
+    Pythagorean(Letter),
+    /// Any other temperament, given in cents from equal temperament from `C` to `B`.
+    Custom([calc::Cents; 12]),
+}
+
+impl Temperament {
+
+    /// The deviation of each pitch class from equal temperament in cents, from `C` to `B`.
+    pub fn offsets(&self) -> [calc::Cents; 12] {
+        match *self {
+            Temperament::Equal => [0.0; 12],
+            Temperament::WerckmeisterIII => WERCKMEISTER_III,
+            Temperament::KirnbergerIII => KIRNBERGER_III,
+            Temperament::YoungII => YOUNG_II,
+            Temperament::Vallotti => VALLOTTI,
+            Temperament::Meantone(comma_fraction) => {
+                let fifth = Ratio(3, 2).cents() - comma_fraction * SYNTONIC_COMMA_CENTS;
+                chain_of_fifths(fifth, E_FLAT)
+            },
+            Temperament::Pythagorean(wolf) => {
+                let first = (wolf.to_usize().unwrap() + 7) % 12;
+                chain_of_fifths(Ratio(3, 2).cents(), first)
+            },
+            Temperament::Custom(offsets) => offsets,
+        }
+    }
+
+    /// The deviation of the pitch class nearest to the given pitch from equal temperament.
+    #[inline]
+    pub fn offset<P: Into<Step>>(&self, pitch: P) -> calc::Cents {
+        let pitch_class = (nearest_step(pitch.into().step()) as i32).rem_euclid(12);
+        self.offsets()[pitch_class as usize]
+    }
+
+    /// The deviation of the given letter from equal temperament.
+    #[inline]
+    pub fn letter_offset(&self, letter: Letter) -> calc::Cents {
+        self.offsets()[letter.to_usize().unwrap()]
+    }
+
+}
+
+/// The offsets of a temperament built from eleven fifths of the given size in cents, beginning
+/// from the given pitch class, such that the twelfth "wolf" fifth closes the circle back to it.
+/// Offsets are given relative to `C`.
+fn chain_of_fifths(fifth: calc::Cents, first: usize) -> [calc::Cents; 12] {
+    let mut offsets = [0.0; 12];
+    for k in 0..12 {
+        offsets[(first + k * 7) % 12] = k as calc::Cents * (fifth - 700.0);
+    }
+    let c = offsets[0];
+    for offset in offsets.iter_mut() {
+        *offset -= c;
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meantone() {
+        let offsets = QUARTER_COMMA_MEANTONE.offsets();
+        // Pure major thirds: `E` is 13.686 cents flat of equal temperament and `C` is unchanged.
+        assert_eq!(offsets[0], 0.0);
+        let e = QUARTER_COMMA_MEANTONE.letter_offset(Letter::E);
+        assert!((e - -13.686).abs() < 0.001);
+        assert!((e + 400.0 - Ratio(5, 4).cents()).abs() < 0.001);
+        // The wolf lies between `G♯` and `E♭`.
+        let wolf = 700.0 + offsets[E_FLAT] - QUARTER_COMMA_MEANTONE.letter_offset(Letter::Ab);
+        assert!((wolf - 737.637).abs() < 0.01);
+        assert!((SIXTH_COMMA_MEANTONE.letter_offset(Letter::G) - -1.629).abs() < 0.001);
+        assert!((Temperament::Meantone(0.0).offset(Step(67.0)) - 1.955).abs() < 0.001);
+    }
+
+    #[test]
+    fn pythagorean() {
+        let fifth = |t: Temperament, lower: Letter, upper: Letter| {
+            700.0 + t.letter_offset(upper) - t.letter_offset(lower)
+        };
+        let pythagorean_comma = ::ratio::PYTHAGOREAN_COMMA_CENTS;
+        assert!((fifth(PYTHAGOREAN, Letter::C, Letter::G) - Ratio(3, 2).cents()).abs() < 0.001);
+        assert!((fifth(PYTHAGOREAN, Letter::Gsh, Letter::Eb)
+                 - (Ratio(3, 2).cents() - pythagorean_comma)).abs() < 0.001);
+        // Moving the wolf leaves the previous wolf pure.
+        let moved = Temperament::Pythagorean(Letter::Fsh);
+        assert!((fifth(moved, Letter::Gsh, Letter::Eb) - Ratio(3, 2).cents()).abs() < 0.001);
+        assert!((fifth(moved, Letter::Fsh, Letter::Db) - 678.495).abs() < 0.001);
+        assert_eq!(moved.letter_offset(Letter::C), 0.0);
+    }
+}
@@ -0,0 +1,178 @@
+//! Twelve-tone rows and their serial transformations.
+
+use super::{
+    Error,
+    Step,
+};
+use utils::modulo;
+
+/// An ordering of all twelve pitch classes (with `C` as `0`), as used in serial composition.
+///
+/// Forms are labelled by the pitch class they begin on, so that `P 0` is the prime form
+/// beginning on `C` and `I 7` is the inversion beginning on `G`. Retrograde forms are labelled
+/// by the prime or inversion they reverse, so `R 0` is `P 0` backwards and ends on `C`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize))]
+pub struct ToneRow([u8; 12]);
+
+impl ToneRow {
+
+    /// Construct a row from the given pitch classes.
+    ///
+    /// Returns `Error::OutOfRange` if any pitch class is greater than `11`, or
+    /// `Error::InvalidValue` if any pitch class appears more than once.
+    pub fn new(pitch_classes: [u8; 12]) -> Result<ToneRow, Error> {
+        let mut seen = [false; 12];
+        for &pitch_class in &pitch_classes {
+            if pitch_class > 11 {
+                return Err(Error::OutOfRange);
+            }
+            if seen[pitch_class as usize] {
+                return Err(Error::InvalidValue);
+            }
+            seen[pitch_class as usize] = true;
+        }
+        Ok(ToneRow(pitch_classes))
+    }
+
+    /// Construct a row from the pitch classes of twelve pitches, each rounded to the nearest
+    /// semitone, e.g. from the opening notes of a melody.
+    ///
+    /// Returns `Error::InvalidValue` if there are not exactly twelve pitches of distinct pitch
+    /// class.
+    pub fn from_pitches<I, P>(pitches: I) -> Result<ToneRow, Error>
+        where I: IntoIterator<Item=P>,
+              P: Into<Step>,
+    {
+        let mut pitch_classes = [0; 12];
+        let mut count = 0;
+        for pitch in pitches {
+            if count == 12 {
+                return Err(Error::InvalidValue);
+            }
+            let step = pitch.into().step().round() as i32;
+            pitch_classes[count] = modulo(step, 12) as u8;
+            count += 1;
+        }
+        if count < 12 {
+            return Err(Error::InvalidValue);
+        }
+        ToneRow::new(pitch_classes)
+    }
+
+    /// Return the pitch classes of the row in order.
+    #[inline]
+    pub fn pitch_classes(&self) -> [u8; 12] {
+        let ToneRow(pitch_classes) = *self;
+        pitch_classes
+    }
+
+    /// The row transposed by the given number of semitones.
+    pub fn transposed(&self, semitones: i32) -> ToneRow {
+        let mut pitch_classes = self.pitch_classes();
+        for pitch_class in pitch_classes.iter_mut() {
+            *pitch_class = modulo(*pitch_class as i32 + semitones, 12) as u8;
+        }
+        ToneRow(pitch_classes)
+    }
+
+    /// The prime form beginning on the given pitch class.
+    #[inline]
+    pub fn prime(&self, pitch_class: u8) -> ToneRow {
+        self.transposed(pitch_class as i32 - self.pitch_classes()[0] as i32)
+    }
+
+    /// The inversion beginning on the given pitch class, in which every interval of the prime
+    /// form is reversed in direction.
+    pub fn inversion(&self, pitch_class: u8) -> ToneRow {
+        let first = self.pitch_classes()[0] as i32;
+        let mut pitch_classes = self.pitch_classes();
+        for p in pitch_classes.iter_mut() {
+            *p = modulo(pitch_class as i32 + first - *p as i32, 12) as u8;
+        }
+        ToneRow(pitch_classes)
+    }
+
+    /// The retrograde of the prime form beginning on the given pitch class, i.e. that prime
+    /// form backwards.
+    #[inline]
+    pub fn retrograde(&self, pitch_class: u8) -> ToneRow {
+        self.prime(pitch_class).reversed()
+    }
+
+    /// The retrograde of the inversion beginning on the given pitch class.
+    #[inline]
+    pub fn retrograde_inversion(&self, pitch_class: u8) -> ToneRow {
+        self.inversion(pitch_class).reversed()
+    }
+
+    /// The twelve-tone matrix of the row.
+    ///
+    /// The first row is the row itself and the first column its inversion from the same pitch
+    /// class, so that each row reads as a prime form from left to right (and as a retrograde
+    /// from right to left) while each column reads as an inversion from top to bottom (and as a
+    /// retrograde inversion from bottom to top).
+    pub fn matrix(&self) -> [[u8; 12]; 12] {
+        let first = self.pitch_classes()[0];
+        let inversion = self.inversion(first).pitch_classes();
+        let mut matrix = [[0; 12]; 12];
+        for (row, &pitch_class) in matrix.iter_mut().zip(inversion.iter()) {
+            *row = self.prime(pitch_class).pitch_classes();
+        }
+        matrix
+    }
+
+    /// The row in reverse order.
+    #[inline]
+    fn reversed(&self) -> ToneRow {
+        let mut pitch_classes = self.pitch_classes();
+        pitch_classes.reverse();
+        ToneRow(pitch_classes)
+    }
+
+}
+
+/// Deserializes from an array of twelve pitch classes, validated as by `ToneRow::new`.
+#[cfg(feature = "serde_serialization")]
+impl<'de> ::serde::Deserialize<'de> for ToneRow {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<ToneRow, D::Error> {
+        use serde::de::Error as DeError;
+        let pitch_classes = <[u8; 12] as ::serde::Deserialize>::deserialize(deserializer)?;
+        ToneRow::new(pitch_classes).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The row of Berg's Violin Concerto.
+    const BERG: [u8; 12] = [7, 10, 2, 6, 9, 0, 4, 8, 11, 1, 3, 5];
+
+    #[test]
+    fn validation() {
+        assert!(ToneRow::new(BERG).is_ok());
+        assert_eq!(ToneRow::new([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12]), Err(Error::OutOfRange));
+        assert_eq!(ToneRow::new([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 10]), Err(Error::InvalidValue));
+        let steps = BERG.iter().map(|&pc| Step(pc as f32 + 60.0));
+        assert_eq!(ToneRow::from_pitches(steps), ToneRow::new(BERG));
+        assert!(ToneRow::from_pitches(vec![Step(60.0); 3]).is_err());
+    }
+
+    #[test]
+    fn forms() {
+        let row = ToneRow::new(BERG).unwrap();
+        assert_eq!(row.prime(0).pitch_classes(), [0, 3, 7, 11, 2, 5, 9, 1, 4, 6, 8, 10]);
+        assert_eq!(row.inversion(7).pitch_classes(), [7, 4, 0, 8, 5, 2, 10, 6, 3, 1, 11, 9]);
+        assert_eq!(row.retrograde(7).pitch_classes()[11], 7);
+        assert_eq!(row.retrograde_inversion(7).pitch_classes()[0], 9);
+        let matrix = row.matrix();
+        assert_eq!(matrix[0], BERG);
+        for i in 0..12 {
+            // The diagonal of the matrix is constant.
+            assert_eq!(matrix[i][i], 7);
+            let column: Vec<u8> = matrix.iter().map(|row| row[i]).collect();
+            assert_eq!(column, row.inversion(BERG[i]).pitch_classes().to_vec());
+        }
+    }
+}
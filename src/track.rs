@@ -0,0 +1,302 @@
+//! A container of timestamped pitches, such as the output of a pitch tracker, along with the
+//! operations commonly applied to them within analysis pipelines.
+
+use stats;
+use super::{
+    calc,
+    Seconds,
+    Step,
+    WeightedPitch,
+};
+
+/// A single pitch within a PitchTrack.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct TrackPoint<P> {
+    /// The time of the pitch in seconds.
+    pub time: Seconds,
+    /// The pitch itself.
+    pub pitch: P,
+    /// Whether or not the pitch was voiced. The pitch of an unvoiced point is usually only the
+    /// tracker's best guess and is ignored by the statistics of the track.
+    pub voiced: bool,
+    /// The tracker's confidence in the pitch, if known (usually from `0` to `1`).
+    pub confidence: Option<f32>,
+}
+
+/// A sequence of timestamped pitches, ordered by time.
+///
+/// The track may hold any pitch representation `P`, and may be converted between them as a
+/// whole with `convert`. Interpolation, resampling and statistics operate in step space.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct PitchTrack<P> {
+    points: Vec<TrackPoint<P>>,
+}
+
+impl<P> TrackPoint<P> {
+
+    /// A voiced point of unknown confidence.
+    #[inline]
+    pub fn new(time: Seconds, pitch: P) -> TrackPoint<P> {
+        TrackPoint { time, pitch, voiced: true, confidence: None }
+    }
+
+    /// A point from a weighted pitch estimate, voiced when its confidence reaches the given
+    /// threshold.
+    #[inline]
+    pub fn from_weighted(time: Seconds, estimate: WeightedPitch<P>, threshold: f32)
+        -> TrackPoint<P>
+    {
+        let voiced = estimate.is_voiced(threshold);
+        TrackPoint { time, pitch: estimate.pitch, voiced, confidence: Some(estimate.confidence) }
+    }
+
+    /// The pitch as a weighted estimate, taking an unknown confidence as `1` for voiced points
+    /// and `0` for unvoiced points.
+    #[inline]
+    pub fn to_weighted(self) -> WeightedPitch<P> {
+        let confidence = self.confidence.unwrap_or(if self.voiced { 1.0 } else { 0.0 });
+        WeightedPitch::new(self.pitch, confidence)
+    }
+
+}
+
+impl<P> Default for PitchTrack<P> {
+    fn default() -> PitchTrack<P> {
+        PitchTrack { points: Vec::new() }
+    }
+}
+
+impl<P> PitchTrack<P> {
+
+    /// An empty track.
+    #[inline]
+    pub fn new() -> PitchTrack<P> {
+        PitchTrack::default()
+    }
+
+    /// A track of the given points, which are sorted by time.
+    pub fn from_points(mut points: Vec<TrackPoint<P>>) -> PitchTrack<P> {
+        points.sort_by(|a, b| a.time.total_cmp(&b.time));
+        PitchTrack { points }
+    }
+
+    /// Add a voiced pitch at the given time.
+    #[inline]
+    pub fn push(&mut self, time: Seconds, pitch: P) {
+        self.push_point(TrackPoint::new(time, pitch));
+    }
+
+    /// Add the given point, keeping the track ordered by time.
+    pub fn push_point(&mut self, point: TrackPoint<P>) {
+        let index = self.points.iter().rposition(|p| p.time <= point.time).map_or(0, |i| i + 1);
+        self.points.insert(index, point);
+    }
+
+    /// The points of the track, ordered by time.
+    #[inline]
+    pub fn points(&self) -> &[TrackPoint<P>] {
+        &self.points
+    }
+
+    /// The number of points within the track.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether or not the track has no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The time between the first and last points, or `0` for an empty track.
+    pub fn duration(&self) -> Seconds {
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.0,
+        }
+    }
+
+    /// Convert every pitch of the track to another representation.
+    pub fn convert<Q: From<P>>(self) -> PitchTrack<Q> {
+        self.map(Q::from)
+    }
+
+    /// Apply the given function to every pitch of the track.
+    pub fn map<Q, F: FnMut(P) -> Q>(self, mut f: F) -> PitchTrack<Q> {
+        let points = self.points.into_iter()
+            .map(|p| TrackPoint { time: p.time, pitch: f(p.pitch), voiced: p.voiced,
+                                  confidence: p.confidence })
+            .collect();
+        PitchTrack { points }
+    }
+
+    /// Mark every point whose confidence is below the given threshold as unvoiced. Points of
+    /// unknown confidence are left unchanged.
+    pub fn with_min_confidence(mut self, threshold: f32) -> PitchTrack<P> {
+        for point in &mut self.points {
+            if point.confidence.map_or(false, |confidence| confidence < threshold) {
+                point.voiced = false;
+            }
+        }
+        self
+    }
+
+}
+
+impl<P: Clone> PitchTrack<P> {
+
+    /// The points lying between `start` (inclusive) and `end` (exclusive) seconds.
+    pub fn slice(&self, start: Seconds, end: Seconds) -> PitchTrack<P> {
+        let points = self.points.iter()
+            .filter(|p| p.time >= start && p.time < end)
+            .cloned()
+            .collect();
+        PitchTrack { points }
+    }
+
+}
+
+impl<P: Copy + Into<Step>> PitchTrack<P> {
+
+    /// The steps of the voiced points.
+    fn voiced_steps(&self) -> Vec<Step> {
+        self.points.iter().filter(|p| p.voiced).map(|p| p.pitch.into()).collect()
+    }
+
+    /// The pitch at the given time, interpolated linearly in step space between the surrounding
+    /// points.
+    ///
+    /// Returns `None` if the time lies outside of the track or either surrounding point is
+    /// unvoiced.
+    pub fn step_at(&self, time: Seconds) -> Option<Step> {
+        let after = self.points.iter().position(|p| p.time >= time)?;
+        let b = &self.points[after];
+        if b.time == time {
+            return if b.voiced { Some(b.pitch.into()) } else { None };
+        }
+        let a = &self.points[after.checked_sub(1)?];
+        if !a.voiced || !b.voiced {
+            return None;
+        }
+        let fraction = (time - a.time) / (b.time - a.time);
+        let (a_step, b_step) = (a.pitch.into().step() as f64, b.pitch.into().step() as f64);
+        Some(Step((a_step + (b_step - a_step) * fraction) as calc::Step))
+    }
+
+    /// Resample the track to points evenly spaced at the given rate (points per second), from
+    /// the time of its first point up to its last.
+    ///
+    /// Resampled points lying between an unvoiced point and its neighbour are unvoiced, and
+    /// the confidence of each resampled point is unknown.
+    pub fn resample(&self, rate: f64) -> PitchTrack<Step> {
+        let start = match self.points.first() {
+            Some(first) => first.time,
+            None => return PitchTrack::new(),
+        };
+        let count = (self.duration() * rate).floor() as usize + 1;
+        let points = (0..count)
+            .map(|i| {
+                let time = start + i as Seconds / rate;
+                match self.step_at(time) {
+                    Some(step) => TrackPoint::new(time, step),
+                    None => TrackPoint {
+                        time,
+                        pitch: Step(calc::Step::NAN),
+                        voiced: false,
+                        confidence: None,
+                    },
+                }
+            })
+            .collect();
+        PitchTrack { points }
+    }
+
+    /// The mean of the voiced pitches in step space.
+    #[inline]
+    pub fn mean(&self) -> Option<Step> {
+        stats::mean(self.voiced_steps())
+    }
+
+    /// The median of the voiced pitches in step space.
+    #[inline]
+    pub fn median(&self) -> Option<Step> {
+        stats::median(self.voiced_steps())
+    }
+
+    /// The standard deviation of the voiced pitches in semitones.
+    #[inline]
+    pub fn std_dev(&self) -> Option<calc::Step> {
+        stats::std_dev(self.voiced_steps())
+    }
+
+    /// The lowest and highest of the voiced pitches.
+    pub fn range(&self) -> Option<(Step, Step)> {
+        let steps = self.voiced_steps();
+        let first = *steps.first()?;
+        Some(steps.iter().fold((first, first), |(min, max), &step| (min.min(step), max.max(step))))
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{Hz, LetterOctave, Letter};
+
+    fn track() -> PitchTrack<Hz> {
+        let mut track = PitchTrack::new();
+        track.push(0.0, Hz(440.0));
+        track.push(0.2, Hz(880.0));
+        track.push(0.1, Hz(440.0));
+        track.push_point(TrackPoint { time: 0.3, pitch: Hz(100.0), voiced: false,
+                                      confidence: Some(0.1) });
+        track
+    }
+
+    #[test]
+    fn ordering_and_slicing() {
+        let track = track();
+        let times: Vec<Seconds> = track.points().iter().map(|p| p.time).collect();
+        assert_eq!(times, vec![0.0, 0.1, 0.2, 0.3]);
+        assert!((track.duration() - 0.3).abs() < 1e-9);
+        assert_eq!(track.slice(0.1, 0.3).len(), 2);
+        let letters = track.clone().convert::<LetterOctave>();
+        assert_eq!(letters.points()[2].pitch, LetterOctave(Letter::A, 5));
+        // Points without a valid time are sorted to the end.
+        let points = vec![TrackPoint::new(Seconds::NAN, Hz(1.0)), TrackPoint::new(0.5, Hz(2.0)),
+                          TrackPoint::new(0.25, Hz(3.0))];
+        let sorted = PitchTrack::from_points(points);
+        assert_eq!(sorted.points()[0].time, 0.25);
+        assert!(sorted.points()[2].time.is_nan());
+    }
+
+    #[test]
+    fn interpolation_and_resampling() {
+        let track = track();
+        assert_eq!(track.step_at(0.15), Some(Step(75.0)));
+        assert_eq!(track.step_at(0.25), None);
+        assert_eq!(track.step_at(-1.0), None);
+        let resampled = track.resample(20.0);
+        assert_eq!(resampled.len(), 7);
+        assert!((resampled.points()[3].pitch.step() - 75.0).abs() < 0.001);
+        assert!(!resampled.points()[5].voiced);
+    }
+
+    #[test]
+    fn statistics() {
+        let track = track();
+        assert_eq!(track.median(), Some(Step(69.0)));
+        assert_eq!(track.mean(), Some(Step(73.0)));
+        assert_eq!(track.range(), Some((Step(69.0), Step(81.0))));
+        let weighted = track.points()[3].to_weighted();
+        assert_eq!(weighted, WeightedPitch::new(Hz(100.0), 0.1));
+        assert!(!TrackPoint::from_weighted(0.4, weighted, 0.5).voiced);
+        let confident = track.with_min_confidence(0.5);
+        assert_eq!(confident.points().iter().filter(|p| p.voiced).count(), 3);
+        assert_eq!(PitchTrack::<Step>::new().mean(), None);
+    }
+}
@@ -0,0 +1,191 @@
+//! Compositional transformations over sequences of pitches, such as melodies, motifs and chords.
+//!
+//! Every transformation takes any collection of pitches and returns a new sequence of Steps, so
+//! pitches of any representation may be mixed. Transformations that depend on spelling also have
+//! a variant over LetterOctaves.
+
+use num::ToPrimitive;
+use super::{
+    calc,
+    Key,
+    Letter,
+    LetterOctave,
+    Step,
+};
+
+/// Collect the pitches as Steps.
+fn steps<I, P>(pitches: I) -> Vec<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    pitches.into_iter().map(Into::into).collect()
+}
+
+/// Move each of the pitches by the given number of (possibly fractional) semitones.
+pub fn transpose<I, P>(pitches: I, semitones: calc::Step) -> Vec<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    pitches.into_iter().map(|p| Step(p.into().step() + semitones)).collect()
+}
+
+/// The pitches in reverse order.
+pub fn retrograde<I, P>(pitches: I) -> Vec<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let mut steps = steps(pitches);
+    steps.reverse();
+    steps
+}
+
+/// The melodic inversion of the pitches, in which every interval is reversed in direction while
+/// the first pitch is kept, i.e. the pitches inverted around the first pitch.
+pub fn invert<I, P>(pitches: I) -> Vec<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let steps = steps(pitches);
+    match steps.first() {
+        Some(&first) => invert_around(steps, first),
+        None => steps,
+    }
+}
+
+/// The retrograde of the inversion of the pitches, see `invert` and `retrograde`.
+#[inline]
+pub fn retrograde_inversion<I, P>(pitches: I) -> Vec<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    retrograde(invert(pitches))
+}
+
+/// The pitches rotated by the given number of places, so that the pitch at index `n` comes
+/// first and the preceding pitches wrap around to the end. A negative `n` rotates the other way.
+pub fn rotate<I, P>(pitches: I, n: isize) -> Vec<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let mut steps = steps(pitches);
+    if !steps.is_empty() {
+        let len = steps.len() as isize;
+        steps.rotate_left(n.rem_euclid(len) as usize);
+    }
+    steps
+}
+
+/// Reflect each of the pitches about the given axis, see `Step::invert_around`.
+pub fn invert_around<I, P, A>(pitches: I, axis: A) -> Vec<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+          A: Into<Step>,
+{
+    let axis = axis.into();
+    pitches.into_iter().map(|p| p.into().invert_around(axis)).collect()
+}
+
+/// The tonic-dominant axis of the key lying closest to the centre of the given pitches, about
+/// which negative harmony reflects them: midway between the minor and major third above the
+/// tonic (e.g. between `Eb` and `E` in `C`).
+fn negative_harmony_axis(steps: &[Step], key: Key) -> calc::Step {
+    let axis = key.tonic().to_f32().unwrap() + 3.5;
+    let (lowest, highest) = steps.iter().fold((calc::Step::MAX, calc::Step::MIN), |(lo, hi), s| {
+        (lo.min(s.step()), hi.max(s.step()))
+    });
+    let centre = if steps.is_empty() { axis } else { (lowest + highest) / 2.0 };
+    axis + ((centre - axis) / 12.0).round() * 12.0
+}
+
+/// Reflect the pitches about the tonic-dominant axis of the key ("negative harmony"), mapping
+/// each degree to its mirror image so that, in `C`, `C` and `G` swap, as do `E` and `Eb`.
+///
+/// The axis is placed in the octave closest to the centre of the pitches, so that the result
+/// lies in roughly the same register. Major triads become minor triads and vice versa (e.g. `C`
+/// major becomes `C` minor and `G` major becomes `F` minor).
+pub fn negative_harmony<I, P>(pitches: I, key: Key) -> Vec<Step>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let steps = steps(pitches);
+    let axis = negative_harmony_axis(&steps, key);
+    invert_around(steps, Step(axis))
+}
+
+/// As `negative_harmony`, spelling each resulting pitch as the mirror image of its letter about
+/// the axis (e.g. the `E` of `C` major becomes `Eb` rather than `Dsh`, and `A` becomes `Bb`).
+pub fn negative_harmony_spelled<I>(pitches: I, key: Key) -> Vec<LetterOctave>
+    where I: IntoIterator<Item=LetterOctave>,
+{
+    let pitches: Vec<LetterOctave> = pitches.into_iter().collect();
+    let reflected = negative_harmony(pitches.iter().cloned(), key);
+    let tonic = key.tonic().diatonic_index() as i32;
+    pitches.iter().zip(reflected).map(|(pitch, step)| {
+        // The letters reflect about the axis between the third and the tonic's fifth.
+        let index = (2 * tonic + 4 - pitch.letter().diatonic_index() as i32).rem_euclid(7);
+        let natural = Letter::from_diatonic_index(index as u8);
+        let letter = Letter::spelled_from(natural, step.pitch_class() as u8);
+        LetterOctave(letter, step.octave())
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{Letter, LetterOctave};
+
+    #[test]
+    fn inversion_around_axis() {
+        let d4 = LetterOctave(Letter::D, 4);
+        assert_eq!(LetterOctave(Letter::E, 4).to_step().invert_around(d4), Step(60.0));
+        let triad = vec![Step(60.0), Step(64.0), Step(67.0)];
+        assert_eq!(invert_around(triad, Step(62.0)), vec![Step(64.0), Step(60.0), Step(57.0)]);
+        // Quarter tone axes are allowed.
+        assert_eq!(Step(60.0).invert_around(Step(60.25)), Step(60.5));
+    }
+
+    #[test]
+    fn sequence_transforms() {
+        let motif = vec![Step(60.0), Step(62.0), Step(67.0)];
+        assert_eq!(transpose(motif.clone(), -12.0), vec![Step(48.0), Step(50.0), Step(55.0)]);
+        assert_eq!(retrograde(motif.clone()), vec![Step(67.0), Step(62.0), Step(60.0)]);
+        assert_eq!(invert(motif.clone()), vec![Step(60.0), Step(58.0), Step(53.0)]);
+        assert_eq!(retrograde_inversion(motif.clone()), vec![Step(53.0), Step(58.0), Step(60.0)]);
+        assert_eq!(rotate(motif.clone(), 1), vec![Step(62.0), Step(67.0), Step(60.0)]);
+        assert_eq!(rotate(motif.clone(), -1), vec![Step(67.0), Step(60.0), Step(62.0)]);
+        assert_eq!(rotate(motif.clone(), 3), motif);
+        assert_eq!(invert(Vec::<Step>::new()), vec![]);
+        assert_eq!(rotate(Vec::<Step>::new(), 2), vec![]);
+        let letters = vec![LetterOctave(Letter::A, 4), LetterOctave(Letter::C, 5)];
+        assert_eq!(retrograde(letters), vec![Step(72.0), Step(69.0)]);
+    }
+
+    #[test]
+    fn negative_harmony_in_c() {
+        use super::super::Mode;
+        let c_major = Key(Letter::C, Mode::Major);
+        // C major (C4 E4 G4) becomes C minor (C4 Eb4 G4).
+        let c = vec![Step(60.0), Step(64.0), Step(67.0)];
+        assert_eq!(negative_harmony(c, c_major), vec![Step(67.0), Step(63.0), Step(60.0)]);
+        // G7 (G3 B3 D4 F4) becomes a half-diminished seventh on D (D F Ab C).
+        let g7 = [
+            LetterOctave(Letter::G, 3),
+            LetterOctave(Letter::B, 3),
+            LetterOctave(Letter::D, 4),
+            LetterOctave(Letter::F, 4),
+        ];
+        let reflected = negative_harmony_spelled(g7.iter().cloned(), c_major);
+        assert_eq!(reflected, vec![
+            LetterOctave(Letter::C, 5),
+            LetterOctave(Letter::Ab, 4),
+            LetterOctave(Letter::F, 4),
+            LetterOctave(Letter::D, 4),
+        ]);
+        assert_eq!(format!("{}", reflected[1]), "Ab4");
+        // The axis follows the tonic.
+        let d_major = Key(Letter::D, Mode::Major);
+        let d = [LetterOctave(Letter::D, 4), LetterOctave(Letter::Fsh, 4)];
+        let reflected = negative_harmony_spelled(d.iter().cloned(), d_major);
+        assert_eq!(format!("{} {}", reflected[0], reflected[1]), "A4 F4");
+    }
+}
@@ -0,0 +1,283 @@
+//! Reference tunings fixing the frequency of a single pitch, from which the frequencies of all
+//! other pitches follow according to a temperament.
+
+use super::{
+    calc,
+    Error,
+    Hz,
+    OCTAVE_CENTS,
+    Scale,
+    Step,
+    Temperament,
+};
+
+/// A tuning determined by the frequency of a single reference pitch and the temperament of the
+/// remaining pitch classes relative to it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Tuning {
+    /// The reference pitch.
+    pub reference_step: calc::Step,
+    /// The frequency of the reference pitch.
+    pub reference_hz: calc::Hz,
+    /// The temperament of the pitch classes. The reference pitch always sounds at the reference
+    /// frequency, with the other pitch classes offset relative to it.
+    pub temperament: Temperament,
+    /// The amount in cents by which every octave is widened (or narrowed, when negative) from a
+    /// pure 2:1 ratio, e.g. to follow the stretched tuning of a piano.
+    #[cfg_attr(feature = "serde_serialization", serde(default))]
+    pub stretch: calc::Cents,
+}
+
+/// Modern concert pitch (ISO 16), where `A 4` is exactly 440hz. This is the tuning used by the
+/// rest of the crate.
+pub const CONCERT_PITCH: Tuning = Tuning {
+    reference_step: 69.0,
+    reference_hz: 440.0,
+    temperament: Temperament::Equal,
+    stretch: 0.0,
+};
+
+/// The "philosophical" or "scientific" pitch standard, where `C 4` is exactly 256hz so that every
+/// `C` is a power of two. `A 4` lies at roughly 430.54hz.
+pub const SCIENTIFIC_PITCH: Tuning = Tuning {
+    reference_step: 60.0,
+    reference_hz: 256.0,
+    temperament: Temperament::Equal,
+    stretch: 0.0,
+};
+
+impl Default for Tuning {
+    /// Modern concert pitch.
+    fn default() -> Tuning {
+        CONCERT_PITCH
+    }
+}
+
+impl Tuning {
+
+    /// An equal tempered tuning in which the given pitch sounds at the given frequency.
+    #[inline]
+    pub fn new<P: Into<Step>>(reference: P, hz: calc::Hz) -> Tuning {
+        Tuning {
+            reference_step: reference.into().step(),
+            reference_hz: hz,
+            temperament: Temperament::Equal,
+            stretch: 0.0,
+        }
+    }
+
+    /// A builder for a tuning, beginning from modern concert pitch, e.g.
+    /// `Tuning::builder().a4(442.0).stretch_cents_per_octave(1.0).build()`.
+    #[inline]
+    pub fn builder() -> TuningBuilder {
+        TuningBuilder { tuning: CONCERT_PITCH }
+    }
+
+    /// An equal tempered tuning in which `A 4` sounds at the given frequency.
+    #[inline]
+    pub fn from_a4(hz: calc::Hz) -> Tuning {
+        Tuning::new(Step(69.0), hz)
+    }
+
+    /// The same tuning with the given temperament, e.g. `Tuning::from_a4(415.0)` tempered with
+    /// `Temperament::WerckmeisterIII` for a baroque organ.
+    #[inline]
+    pub fn with_temperament(self, temperament: Temperament) -> Tuning {
+        Tuning { temperament, ..self }
+    }
+
+    /// The size in semitones of each step of the tuning, including any stretch.
+    #[inline]
+    fn semitones_per_step(&self) -> f64 {
+        1.0 + self.stretch as f64 / 1_200.0
+    }
+
+    /// The offset of the given pitch from its equal tempered position relative to the reference.
+    #[inline]
+    fn offset(&self, step: calc::Step) -> calc::Cents {
+        self.temperament.offset(Step(step)) - self.temperament.offset(Step(self.reference_step))
+    }
+
+    /// The frequency of `A 4` within the tuning.
+    #[inline]
+    pub fn a4(&self) -> calc::Hz {
+        self.hz(Step(69.0)).hz()
+    }
+
+    /// The frequency of the given pitch within the tuning.
+    pub fn hz<P: Into<Step>>(&self, pitch: P) -> Hz {
+        let step = pitch.into().step();
+        let steps = (step - self.reference_step) as f64 * self.semitones_per_step();
+        let semitones = steps + self.offset(step) as f64 / 100.0;
+        Hz((self.reference_hz as f64 * (semitones / 12.0).exp2()) as calc::Hz)
+    }
+
+    /// The pitch sounding at the given frequency within the tuning.
+    ///
+    /// Within a tempered tuning the result is relative to the nearest tempered pitch, so that the
+    /// frequency of each tempered pitch produces its whole Step.
+    pub fn step(&self, hz: Hz) -> Step {
+        let octaves = (hz.hz() as f64 / self.reference_hz as f64).log2();
+        let per_step = self.semitones_per_step();
+        let equal = self.reference_step as f64 + octaves * 12.0 / per_step;
+        // Refine the estimate once, as the offset of the nearest pitch may move it across the
+        // boundary between two pitch classes.
+        let mut step = equal;
+        for _ in 0..2 {
+            step = equal - self.offset(step as calc::Step) as f64 / 100.0 / per_step;
+        }
+        Step(step as calc::Step)
+    }
+
+    /// The twelve pitch classes of the tuning as a Scale from `C`, e.g. for writing to a Scala
+    /// file with `scala::write_scl`.
+    pub fn scale(&self) -> Scale {
+        let offsets = self.temperament.offsets();
+        let step = self.semitones_per_step() as calc::Cents * 100.0;
+        let degrees = (0..12)
+            .map(|degree| degree as calc::Cents * step + offsets[degree] - offsets[0]);
+        Scale::normalize(degrees, OCTAVE_CENTS + self.stretch)
+    }
+
+}
+
+/// A builder for a Tuning, validating the configuration once it is complete. See
+/// `Tuning::builder`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TuningBuilder {
+    tuning: Tuning,
+}
+
+impl TuningBuilder {
+
+    /// Tune `A 4` to the given frequency.
+    #[inline]
+    pub fn a4(self, hz: calc::Hz) -> TuningBuilder {
+        self.reference(Step(69.0), hz)
+    }
+
+    /// Tune the given reference pitch to the given frequency.
+    #[inline]
+    pub fn reference<P: Into<Step>>(self, pitch: P, hz: calc::Hz) -> TuningBuilder {
+        let reference_step = pitch.into().step();
+        TuningBuilder { tuning: Tuning { reference_step, reference_hz: hz, ..self.tuning } }
+    }
+
+    /// Temper the pitch classes with the given temperament.
+    #[inline]
+    pub fn temperament(self, temperament: Temperament) -> TuningBuilder {
+        TuningBuilder { tuning: Tuning { temperament, ..self.tuning } }
+    }
+
+    /// Widen every octave by the given number of cents (or narrow it, when negative).
+    #[inline]
+    pub fn stretch_cents_per_octave(self, stretch: calc::Cents) -> TuningBuilder {
+        TuningBuilder { tuning: Tuning { stretch, ..self.tuning } }
+    }
+
+    /// The configured tuning.
+    ///
+    /// Returns `Error::InvalidValue` if the reference pitch, reference frequency, stretch or any
+    /// temperament offset is not finite, and `Error::OutOfRange` if the reference frequency is
+    /// not greater than zero or the stretch would shrink the octave to nothing.
+    pub fn build(self) -> Result<Tuning, Error> {
+        let tuning = self.tuning;
+        let finite = tuning.reference_step.is_finite()
+            && tuning.reference_hz.is_finite()
+            && tuning.stretch.is_finite()
+            && tuning.temperament.offsets().iter().all(|offset| offset.is_finite());
+        if !finite {
+            return Err(Error::InvalidValue);
+        }
+        if tuning.reference_hz <= 0.0 || tuning.stretch <= -OCTAVE_CENTS {
+            return Err(Error::OutOfRange);
+        }
+        Ok(tuning)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{Letter, LetterOctave};
+
+    #[test]
+    fn scientific_pitch() {
+        assert_eq!(SCIENTIFIC_PITCH.hz(LetterOctave(Letter::C, 4)), Hz(256.0));
+        assert_eq!(SCIENTIFIC_PITCH.hz(LetterOctave(Letter::C, 0)), Hz(16.0));
+        assert!((SCIENTIFIC_PITCH.a4() - 430.539).abs() < 0.001);
+        assert!((SCIENTIFIC_PITCH.step(Hz(512.0)).step() - 72.0).abs() < 0.0001);
+        assert_eq!(Tuning::default().hz(Step(69.0)), Hz(440.0));
+        assert!((Tuning::from_a4(415.0).hz(Step(57.0)).hz() - 207.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn per_call_reference() {
+        use calc::{hz_from_step_with_a4, letter_octave_from_hz_with_a4, step_from_hz_with_a4};
+        let baroque = Tuning::from_a4(415.0);
+        for &step in &[21.0, 57.0, 60.0, 69.0, 100.5] {
+            let hz = hz_from_step_with_a4(step, 415.0);
+            assert!((hz - baroque.hz(Step(step)).hz()).abs() < 0.01);
+            assert!((step_from_hz_with_a4(hz, 415.0) - step).abs() < 0.001);
+        }
+        assert_eq!(letter_octave_from_hz_with_a4(415.0, 415.0), (Letter::A, 4));
+        assert_eq!(letter_octave_from_hz_with_a4(440.0, 415.0), (Letter::Ash, 4));
+    }
+
+    #[test]
+    fn well_temperaments() {
+        let werckmeister = Tuning::from_a4(415.0).with_temperament(Temperament::WerckmeisterIII);
+        // The reference pitch is untouched.
+        assert!((werckmeister.a4() - 415.0).abs() < 0.001);
+        // `C` lies 11.73 cents above its equal tempered position relative to `A`.
+        let c = werckmeister.hz(LetterOctave(Letter::C, 5)).hz();
+        let expected = Tuning::from_a4(415.0).hz(Step(72.1173)).hz();
+        assert!((c - expected).abs() < 0.01);
+        for &temperament in &[
+            Temperament::WerckmeisterIII,
+            Temperament::KirnbergerIII,
+            Temperament::YoungII,
+            Temperament::Vallotti,
+        ] {
+            let tuning = CONCERT_PITCH.with_temperament(temperament);
+            for step in 48..84 {
+                let hz = tuning.hz(Step(step as calc::Step));
+                assert!((tuning.step(hz).step() - step as calc::Step).abs() < 0.001);
+            }
+        }
+        let meantone = CONCERT_PITCH.with_temperament(::QUARTER_COMMA_MEANTONE);
+        let e = meantone.hz(LetterOctave(Letter::E, 5)).hz();
+        let c = meantone.hz(LetterOctave(Letter::C, 5)).hz();
+        assert!((e / c - 1.25).abs() < 0.0001);
+        let equal = CONCERT_PITCH.with_temperament(Temperament::Custom([0.0; 12]));
+        assert_eq!(equal.hz(Step(60.0)), CONCERT_PITCH.hz(Step(60.0)));
+    }
+
+    #[test]
+    fn builder() {
+        let tuning = Tuning::builder()
+            .a4(442.0)
+            .temperament(Temperament::Meantone(0.25))
+            .stretch_cents_per_octave(1.0)
+            .build()
+            .unwrap();
+        assert!((tuning.a4() - 442.0).abs() < 0.001);
+        assert_eq!(tuning.temperament, ::QUARTER_COMMA_MEANTONE);
+        // Each octave is a cent wider than 2:1.
+        let a5 = tuning.hz(Step(81.0)).hz();
+        assert!(((a5 / 442.0).log2() * 1_200.0 - 1_201.0).abs() < 0.01);
+        for step in 48..84 {
+            let hz = tuning.hz(Step(step as calc::Step));
+            assert!((tuning.step(hz).step() - step as calc::Step).abs() < 0.001);
+        }
+        assert_eq!(tuning.scale().period, 1_201.0);
+        let c4 = Tuning::builder().reference(LetterOctave(Letter::C, 4), 256.0).build();
+        assert_eq!(c4, Ok(SCIENTIFIC_PITCH));
+        assert_eq!(Tuning::builder().a4(0.0).build(), Err(Error::OutOfRange));
+        assert_eq!(Tuning::builder().a4(calc::Hz::NAN).build(), Err(Error::InvalidValue));
+        assert_eq!(Tuning::builder().stretch_cents_per_octave(-1_200.0).build(),
+                   Err(Error::OutOfRange));
+    }
+}
@@ -5,7 +5,19 @@ use num::PrimInt as Int;
 pub fn modulo<I: Int>(a: I, b: I) -> I {
     match a % b {
         r if (r > I::zero() && b < I::zero())
-          || (r < I::zero() && b > I::zero()) => (r + b),
+          || (r < I::zero() && b > I::zero()) => r + b,
         r                                     => r,
     }
 }
+
+/// The greatest common divisor of two integers (Euclid's algorithm).
+#[inline]
+pub fn gcd<I: Int>(a: I, b: I) -> I {
+    let (mut a, mut b) = (a, b);
+    while b != I::zero() {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
@@ -0,0 +1,115 @@
+//! Estimation of the rate and depth of vibrato within a pitch track.
+
+use super::{
+    calc,
+    PitchTrack,
+    Step,
+};
+
+/// The depth in cents below which a pitch is considered steady rather than vibrating.
+pub const MIN_DEPTH: calc::Cents = 1.0;
+
+/// The vibrato of a segment of a PitchTrack.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct Vibrato {
+    /// The pitch about which the vibrato oscillates (the mean of the segment).
+    pub center: Step,
+    /// The number of oscillations per second. Typical vocal vibrato lies between `4.5` and `7`.
+    pub rate: calc::Hz,
+    /// The amplitude of the oscillation in cents, i.e. half of the distance between its peaks and
+    /// troughs.
+    pub depth: calc::Cents,
+}
+
+impl Vibrato {
+
+    /// Estimate the vibrato of the voiced points of the given track segment.
+    ///
+    /// Any steady drift of the pitch across the segment (e.g. a singer going slightly flat) is
+    /// removed first by subtracting a least-squares line. The rate is found from the times at which
+    /// the remaining oscillation crosses the center, and the depth from its RMS (scaled by `√2`,
+    /// which gives the exact amplitude for a sinusoidal vibrato).
+    ///
+    /// Returns `None` if the segment contains less than a full oscillation, or if the depth is
+    /// below `MIN_DEPTH` (i.e. the pitch is steady, and only noise crosses the center).
+    pub fn analyze<P: Copy + Into<Step>>(track: &PitchTrack<P>) -> Option<Vibrato> {
+        let points: Vec<(f64, f64)> = track.points().iter()
+            .filter(|p| p.voiced)
+            .map(|p| (p.time, p.pitch.into().step() as f64))
+            .filter(|&(_, step)| !step.is_nan())
+            .collect();
+        if points.len() < 3 {
+            return None;
+        }
+
+        // Remove the linear trend.
+        let n = points.len() as f64;
+        let mean_time = points.iter().map(|&(t, _)| t).sum::<f64>() / n;
+        let mean_step = points.iter().map(|&(_, s)| s).sum::<f64>() / n;
+        let covariance: f64 = points.iter().map(|&(t, s)| (t - mean_time) * (s - mean_step)).sum();
+        let variance: f64 = points.iter().map(|&(t, _)| (t - mean_time).powi(2)).sum();
+        let slope = if variance > 0.0 { covariance / variance } else { 0.0 };
+        let deviations: Vec<(f64, f64)> = points.iter()
+            .map(|&(t, s)| (t, s - mean_step - slope * (t - mean_time)))
+            .collect();
+
+        // The interpolated times at which the deviation changes sign.
+        let crossings: Vec<f64> = deviations.windows(2)
+            .filter(|pair| (pair[0].1 < 0.0) != (pair[1].1 < 0.0))
+            .map(|pair| {
+                let ((t0, d0), (t1, d1)) = (pair[0], pair[1]);
+                t0 + (t1 - t0) * d0 / (d0 - d1)
+            })
+            .collect();
+        if crossings.len() < 3 {
+            return None;
+        }
+        let span = crossings[crossings.len() - 1] - crossings[0];
+        let rate = (crossings.len() - 1) as f64 / (2.0 * span);
+
+        let rms = (deviations.iter().map(|&(_, d)| d * d).sum::<f64>() / n).sqrt();
+        let depth = (rms * 2.0f64.sqrt() * 100.0) as calc::Cents;
+        if depth < MIN_DEPTH {
+            return None;
+        }
+        Some(Vibrato {
+            center: Step(mean_step as calc::Step),
+            rate: rate as calc::Hz,
+            depth,
+        })
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn sinusoidal_vibrato() {
+        // One second of 5.5hz vibrato of 50 cents about A4 that drifts flat by 20 cents, sampled
+        // at 200hz.
+        let mut track = PitchTrack::new();
+        for i in 0..200 {
+            let t = i as f64 / 200.0;
+            let step = 69.0 + 0.5 * (2.0 * PI * 5.5 * t + 0.3).sin() - 0.2 * t;
+            track.push(t, Step(step as calc::Step));
+        }
+        let vibrato = Vibrato::analyze(&track).unwrap();
+        assert!((vibrato.rate - 5.5).abs() < 0.1, "{:?}", vibrato);
+        assert!((vibrato.depth - 50.0).abs() < 2.0, "{:?}", vibrato);
+        assert!((vibrato.center.step() - 68.9).abs() < 0.05, "{:?}", vibrato);
+    }
+
+    #[test]
+    fn steady_pitch() {
+        let mut track = PitchTrack::new();
+        for i in 0..10 {
+            track.push(i as f64 * 0.01, Step(60.0 + i as calc::Step * 0.01));
+        }
+        assert_eq!(Vibrato::analyze(&track), None);
+        assert_eq!(Vibrato::analyze(&PitchTrack::<Step>::new()), None);
+    }
+}
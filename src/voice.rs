@@ -0,0 +1,124 @@
+//! The standard classical voice types and classification of sung ranges between them.
+
+use super::{
+    calc,
+    PitchRange,
+    Step,
+};
+
+/// The typical range of a bass voice (E2 - E4).
+pub const BASS_RANGE: PitchRange = PitchRange(Step(40.0), Step(64.0));
+/// The typical range of a baritone voice (A2 - A4).
+pub const BARITONE_RANGE: PitchRange = PitchRange(Step(45.0), Step(69.0));
+/// The typical range of a tenor voice (C3 - C5).
+pub const TENOR_RANGE: PitchRange = PitchRange(Step(48.0), Step(72.0));
+/// The typical range of an alto (contralto) voice (F3 - F5).
+pub const ALTO_RANGE: PitchRange = PitchRange(Step(53.0), Step(77.0));
+/// The typical range of a mezzo-soprano voice (A3 - A5).
+pub const MEZZO_SOPRANO_RANGE: PitchRange = PitchRange(Step(57.0), Step(81.0));
+/// The typical range of a soprano voice (C4 - C6).
+pub const SOPRANO_RANGE: PitchRange = PitchRange(Step(60.0), Step(84.0));
+
+/// The standard classical voice types, from lowest to highest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum VoiceType {
+    Bass,
+    Baritone,
+    Tenor,
+    Alto,
+    MezzoSoprano,
+    Soprano,
+}
+
+/// Every VoiceType variant, from lowest to highest.
+pub const ALL_VOICE_TYPES: [VoiceType; 6] = [
+    VoiceType::Bass,
+    VoiceType::Baritone,
+    VoiceType::Tenor,
+    VoiceType::Alto,
+    VoiceType::MezzoSoprano,
+    VoiceType::Soprano,
+];
+
+impl VoiceType {
+
+    /// The reference range for the voice type.
+    pub fn range(&self) -> PitchRange {
+        match *self {
+            VoiceType::Bass         => BASS_RANGE,
+            VoiceType::Baritone     => BARITONE_RANGE,
+            VoiceType::Tenor        => TENOR_RANGE,
+            VoiceType::Alto         => ALTO_RANGE,
+            VoiceType::MezzoSoprano => MEZZO_SOPRANO_RANGE,
+            VoiceType::Soprano      => SOPRANO_RANGE,
+        }
+    }
+
+}
+
+/// The centre of a range in steps.
+#[inline]
+fn centre(range: &PitchRange) -> calc::Step {
+    (range.lowest().step() + range.highest().step()) / 2.0
+}
+
+/// Classify a vocal range as the voice type whose reference range is centred closest to it.
+///
+/// A range centred exactly between two voice types is classified as the lower of the two.
+pub fn classify_range(range: &PitchRange) -> VoiceType {
+    let target = centre(range);
+    let distance = |voice: &VoiceType| (centre(&voice.range()) - target).abs();
+    let mut best = ALL_VOICE_TYPES[0];
+    for voice in ALL_VOICE_TYPES.iter().skip(1) {
+        if distance(voice) < distance(&best) {
+            best = *voice;
+        }
+    }
+    best
+}
+
+/// Classify a collection of sung pitches by the range that they span.
+///
+/// Returns `None` if the collection is empty.
+pub fn classify_pitches<I, P>(pitches: I) -> Option<VoiceType>
+    where I: IntoIterator<Item=P>,
+          P: Into<Step>,
+{
+    let mut steps = pitches.into_iter().map(|p| p.into().step());
+    let first = steps.next()?;
+    let (lowest, highest) = steps.fold((first, first), |(lo, hi), s| (lo.min(s), hi.max(s)));
+    Some(classify_range(&PitchRange(Step(lowest), Step(highest))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_voice_type() {
+        for voice in ALL_VOICE_TYPES.iter() {
+            assert_eq!(classify_range(&voice.range()), *voice);
+        }
+        assert_eq!(classify_range(&PitchRange(Step(30.0), Step(50.0))), VoiceType::Bass);
+        assert_eq!(classify_range(&PitchRange(Step(70.0), Step(96.0))), VoiceType::Soprano);
+    }
+
+    #[test]
+    fn ties() {
+        // Centred at 54.5, halfway between the bass (52) and baritone (57).
+        let between = PitchRange(Step(42.0), Step(67.0));
+        assert_eq!(classify_range(&between), VoiceType::Bass);
+        // Centred at 70.5, halfway between the mezzo-soprano (69) and soprano (72).
+        let between = PitchRange(Step(59.0), Step(82.0));
+        assert_eq!(classify_range(&between), VoiceType::MezzoSoprano);
+    }
+
+    #[test]
+    fn pitches() {
+        assert_eq!(classify_pitches(Vec::<Step>::new()), None);
+        assert_eq!(classify_pitches(vec![Step(60.0)]), Some(VoiceType::Tenor));
+        let sung = vec![Step(53.0), Step(77.0), Step(60.0), Step(65.0)];
+        assert_eq!(classify_pitches(sung), Some(VoiceType::Alto));
+    }
+}
@@ -0,0 +1,136 @@
+//! The distance between chords measured by the motion of their voices, for preferring smooth
+//! voice leading when generating or analysing progressions.
+
+use super::{
+    calc,
+    Step,
+};
+
+/// An assignment of the voices of one chord to the pitches of another.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct VoiceLeading {
+    /// The motion of each voice from its pitch in the first chord to its pitch in the second, in
+    /// the order in which the voices of the first chord were given.
+    pub moves: Vec<(Step, Step)>,
+    /// The total distance moved by all voices in semitones.
+    pub distance: calc::Step,
+}
+
+impl VoiceLeading {
+
+    /// Construct a VoiceLeading from the motion of each voice, summing the distance.
+    fn new(moves: Vec<(Step, Step)>) -> VoiceLeading {
+        let distance = moves.iter().map(|&(a, b)| (b.step() - a.step()).abs()).sum();
+        VoiceLeading { moves, distance }
+    }
+
+}
+
+/// The indices of the pitches, sorted from lowest to highest pitch.
+fn sorted_indices(steps: &[Step]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..steps.len()).collect();
+    indices.sort_by(|&a, &b| steps[a].step().total_cmp(&steps[b].step()));
+    indices
+}
+
+/// The voice leading between two chords of exactly the given pitches that moves the voices the
+/// least total distance in semitones.
+///
+/// Voices never cross in the result: the lowest voice of the first chord moves to the lowest
+/// pitch of the second and so on, which is always among the smoothest voice leadings.
+///
+/// Returns `None` if the chords have a different number of pitches, in which case a pitch of
+/// the smaller chord may be doubled to choose how the voices divide or converge.
+pub fn voice_leading<A, B, P, Q>(from: A, to: B) -> Option<VoiceLeading>
+    where A: IntoIterator<Item=P>,
+          B: IntoIterator<Item=Q>,
+          P: Into<Step>,
+          Q: Into<Step>,
+{
+    let from: Vec<Step> = from.into_iter().map(Into::into).collect();
+    let to: Vec<Step> = to.into_iter().map(Into::into).collect();
+    if from.len() != to.len() {
+        return None;
+    }
+    let mut moves = vec![(Step(0.0), Step(0.0)); from.len()];
+    for (&i, &j) in sorted_indices(&from).iter().zip(sorted_indices(&to).iter()) {
+        moves[i] = (from[i], to[j]);
+    }
+    Some(VoiceLeading::new(moves))
+}
+
+/// The smoothest voice leading from the given chord to any voicing of the pitch classes of the
+/// other, in which each voice moves by at most a tritone to the nearest octave of its target.
+///
+/// Only the pitch classes of `to` are considered, so the result describes how the voices of
+/// `from` should move rather than the voicing of `to` as given. Returns `None` if the chords
+/// have a different number of pitches.
+pub fn pitch_class_voice_leading<A, B, P, Q>(from: A, to: B) -> Option<VoiceLeading>
+    where A: IntoIterator<Item=P>,
+          B: IntoIterator<Item=Q>,
+          P: Into<Step>,
+          Q: Into<Step>,
+{
+    let from: Vec<Step> = from.into_iter().map(Into::into).collect();
+    let to: Vec<Step> = to.into_iter().map(Into::into).collect();
+    if from.len() != to.len() {
+        return None;
+    }
+    // Order the voices of each chord by pitch class: the smoothest voice leading between pitch
+    // class sets is always one of the rotations of one ordering against the other.
+    let by_pitch_class = |steps: &[Step]| {
+        let mut indices: Vec<usize> = (0..steps.len()).collect();
+        indices.sort_by(|&a, &b| steps[a].pitch_class().total_cmp(&steps[b].pitch_class()));
+        indices
+    };
+    let (from_order, to_order) = (by_pitch_class(&from), by_pitch_class(&to));
+    let n = from.len();
+    (0..n.max(1))
+        .map(|rotation| {
+            let mut moves = vec![(Step(0.0), Step(0.0)); n];
+            for (k, &i) in from_order.iter().enumerate() {
+                let target = to[to_order[(k + rotation) % n]].pitch_class();
+                let motion = (target - from[i].pitch_class() + 6.0).rem_euclid(12.0) - 6.0;
+                moves[i] = (from[i], Step(from[i].step() + motion));
+            }
+            VoiceLeading::new(moves)
+        })
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(steps: &[calc::Step]) -> Vec<Step> {
+        steps.iter().cloned().map(Step).collect()
+    }
+
+    #[test]
+    fn exact_voicings() {
+        // C major (C4 E4 G4) to F major in second inversion (C4 F4 A4).
+        let leading = voice_leading(steps(&[64.0, 60.0, 67.0]), steps(&[60.0, 65.0, 69.0]));
+        let leading = leading.unwrap();
+        assert_eq!(leading.distance, 3.0);
+        assert_eq!(leading.moves[0], (Step(64.0), Step(65.0)));
+        assert_eq!(leading.moves[1], (Step(60.0), Step(60.0)));
+        assert!(voice_leading(steps(&[60.0]), steps(&[60.0, 64.0])).is_none());
+    }
+
+    #[test]
+    fn octave_equivalent() {
+        // C major (C4 E4 G4) to A minor: only G moves, up a tone to A.
+        let leading = pitch_class_voice_leading(steps(&[60.0, 64.0, 67.0]),
+                                                steps(&[57.0, 48.0, 64.0])).unwrap();
+        assert_eq!(leading.distance, 2.0);
+        assert_eq!(leading.moves[2], (Step(67.0), Step(69.0)));
+        // G7 (G3 B3 D4 F4) to C major with a doubled root: B up to C and F down to E.
+        let leading = pitch_class_voice_leading(steps(&[55.0, 59.0, 62.0, 65.0]),
+                                                steps(&[60.0, 64.0, 67.0, 72.0])).unwrap();
+        assert_eq!(leading.distance, 4.0);
+        assert_eq!(leading.moves[3], (Step(65.0), Step(64.0)));
+        assert_eq!(pitch_class_voice_leading(Vec::<Step>::new(), Vec::<Step>::new()).unwrap()
+                       .distance, 0.0);
+    }
+}
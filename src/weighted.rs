@@ -0,0 +1,148 @@
+//! Pitch estimates paired with the confidence in them, allowing detectors, filters and other
+//! downstream code to handle uncertainty consistently.
+
+use super::{
+    calc,
+    Step,
+};
+
+/// A pitch estimate along with the confidence in it, from `0` (unvoiced or pure guesswork) to
+/// `1` (certain).
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct WeightedPitch<P> {
+    /// The estimated pitch.
+    pub pitch: P,
+    /// The confidence in the estimate.
+    pub confidence: f32,
+}
+
+impl<P> WeightedPitch<P> {
+
+    /// A pitch estimate with the given confidence, clamped to the range `[0, 1]`.
+    #[inline]
+    pub fn new(pitch: P, confidence: f32) -> WeightedPitch<P> {
+        WeightedPitch { pitch, confidence: confidence.clamp(0.0, 1.0) }
+    }
+
+    /// An estimate that is certain.
+    #[inline]
+    pub fn certain(pitch: P) -> WeightedPitch<P> {
+        WeightedPitch { pitch, confidence: 1.0 }
+    }
+
+    /// Whether or not the confidence reaches the given threshold, i.e. whether the estimate
+    /// should be treated as voiced.
+    #[inline]
+    pub fn is_voiced(&self, threshold: f32) -> bool {
+        self.confidence >= threshold
+    }
+
+    /// Convert the pitch to another representation, keeping the confidence.
+    #[inline]
+    pub fn convert<Q: From<P>>(self) -> WeightedPitch<Q> {
+        self.map(Q::from)
+    }
+
+    /// Apply the given function to the pitch, keeping the confidence.
+    #[inline]
+    pub fn map<Q, F: FnOnce(P) -> Q>(self, f: F) -> WeightedPitch<Q> {
+        WeightedPitch { pitch: f(self.pitch), confidence: self.confidence }
+    }
+
+}
+
+impl<P: Into<Step>> WeightedPitch<P> {
+
+    /// Merge two estimates of the same pitch, see `merge`.
+    #[inline]
+    pub fn merge<Q: Into<Step>>(self, other: WeightedPitch<Q>) -> WeightedPitch<Step> {
+        merge(vec![self.map(Into::into), other.map(Into::into)]).unwrap()
+    }
+
+}
+
+impl<P> From<(P, f32)> for WeightedPitch<P> {
+    /// Convert from a `(pitch, confidence)` pair, as returned by `detection::detect_pitch`.
+    #[inline]
+    fn from((pitch, confidence): (P, f32)) -> WeightedPitch<P> {
+        WeightedPitch::new(pitch, confidence)
+    }
+}
+
+/// Merge several estimates of the same pitch (e.g. from different detectors or channels).
+///
+/// The merged pitch is the mean of the pitches in step space, weighted by their confidence. The
+/// merged confidence is the mean of the confidences weighted by themselves, so that the
+/// confident estimates dominate. If every confidence is zero the pitches are weighted equally
+/// and the merged confidence is zero.
+///
+/// Returns `None` if there are no estimates.
+pub fn merge<I, P>(estimates: I) -> Option<WeightedPitch<Step>>
+    where I: IntoIterator<Item=WeightedPitch<P>>,
+          P: Into<Step>,
+{
+    let estimates: Vec<(f64, f64)> = estimates.into_iter()
+        .map(|e| (e.pitch.into().step() as f64, e.confidence as f64))
+        .collect();
+    if estimates.is_empty() {
+        return None;
+    }
+    let total: f64 = estimates.iter().map(|&(_, confidence)| confidence).sum();
+    if total <= 0.0 {
+        let mean = estimates.iter().map(|&(step, _)| step).sum::<f64>() / estimates.len() as f64;
+        return Some(WeightedPitch { pitch: Step(mean as calc::Step), confidence: 0.0 });
+    }
+    let step = estimates.iter().map(|&(step, confidence)| step * confidence).sum::<f64>() / total;
+    let confidence = estimates.iter().map(|&(_, confidence)| confidence * confidence).sum::<f64>()
+        / total;
+    Some(WeightedPitch { pitch: Step(step as calc::Step), confidence: confidence as f32 })
+}
+
+/// The estimate with the highest confidence, ignoring any whose confidence is not a number.
+///
+/// Returns `None` if there are no such estimates.
+pub fn most_confident<I, P>(estimates: I) -> Option<WeightedPitch<P>>
+    where I: IntoIterator<Item=WeightedPitch<P>>,
+{
+    estimates.into_iter()
+        .filter(|e| !e.confidence.is_nan())
+        .fold(None, |best: Option<WeightedPitch<P>>, e| match best {
+            Some(ref b) if b.confidence >= e.confidence => best,
+            _ => Some(e),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{Hz, LetterOctave, Letter};
+
+    #[test]
+    fn merging() {
+        let a = WeightedPitch::new(Step(60.0), 1.0);
+        let b = WeightedPitch::new(Hz(Step(63.0).hz()), 0.5);
+        let merged = a.merge(b);
+        assert!((merged.pitch.step() - 61.0).abs() < 0.001);
+        assert!((merged.confidence - 1.25 / 1.5).abs() < 0.001);
+        let guesses = vec![WeightedPitch::new(Step(60.0), 0.0),
+                           WeightedPitch::new(Step(62.0), 0.0)];
+        assert_eq!(merge(guesses), Some(WeightedPitch { pitch: Step(61.0), confidence: 0.0 }));
+        assert_eq!(merge(Vec::<WeightedPitch<Step>>::new()), None);
+    }
+
+    #[test]
+    fn selection_and_conversion() {
+        let estimates = vec![
+            WeightedPitch::from((Hz(440.0), 0.8)),
+            WeightedPitch::new(Hz(220.0), 0.9),
+            WeightedPitch::new(Hz(110.0), 2.0),
+        ];
+        assert_eq!(estimates[2].confidence, 1.0);
+        let best = most_confident(estimates.clone()).unwrap();
+        assert_eq!(best.pitch, Hz(110.0));
+        assert!(best.is_voiced(0.5));
+        let letter = estimates[0].convert::<LetterOctave>();
+        assert_eq!(letter, WeightedPitch { pitch: LetterOctave(Letter::A, 4), confidence: 0.8 });
+    }
+}